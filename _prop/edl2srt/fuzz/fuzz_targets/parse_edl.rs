@@ -0,0 +1,41 @@
+//! 模糊测试：任意字节走完 解码 -> 解析 -> 换算 -> 写出 的流程，不能崩溃。
+//!
+//! 运行（需要 nightly 和 cargo-fuzz）：
+//!
+//! ```text
+//! cd fuzz
+//! cargo +nightly fuzz run parse_edl -- -max_total_time=300
+//! ```
+//!
+//! 可以用 `../tests/fixtures` 下的 EDL 作为初始语料：
+//! `cargo +nightly fuzz run parse_edl corpus ../tests/fixtures`
+
+#![no_main]
+
+use edl2srt::format::edl::write_edl;
+use edl2srt::{
+    decode_edl_bytes, entries_to_cues, events_to_entries, infer_fps, locators, parse_edl,
+    retimed_events, write_srt,
+};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(decoded) = decode_edl_bytes(data, None) else {
+        return;
+    };
+    let edl = parse_edl(&decoded.content);
+
+    let fps = infer_fps(&edl).resolve().unwrap_or(25.0);
+    let _ = locators(&edl);
+    let _ = retimed_events(&edl, fps);
+
+    let cues = entries_to_cues(&events_to_entries(&edl.events), fps);
+    let mut out = Vec::new();
+    write_srt(&mut out, &cues).expect("写入内存不会失败");
+
+    // 写回 EDL 再解析，事件数应保持不变
+    let mut round_trip = Vec::new();
+    write_edl(&mut round_trip, &edl).expect("写入内存不会失败");
+    let again = parse_edl(&String::from_utf8_lossy(&round_trip));
+    assert_eq!(again.events.len(), edl.events.len());
+});
@@ -0,0 +1,566 @@
+//! 双语字幕：主语言来自 EDL，副语言来自另一条轨道或翻译表。
+//!
+//! 两种来源：
+//! - 同一份 EDL 的两条轨道，事件按录制入点一一对应（例如 A1 中文、A2 英文）
+//! - 一份 EDL 加一张按片段名索引的翻译表（CSV / TSV / JSON）
+//!
+//! 得到的 [`BilingualCue`] 可以合成两行的普通字幕（任何写出器都能用），
+//! 也可以交给 [`crate::format::ass::write_bilingual_ass`] 按上下两个样式输出。
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::fps::nominal_rate;
+use crate::model::{Cue, Edl, EdlEvent, Warning};
+use crate::t;
+use crate::transform::{event_text, strip_audio_extension};
+
+/// 一条双语字幕。
+#[derive(Debug, Clone, PartialEq)]
+pub struct BilingualCue {
+    pub start_ms: u64,
+    pub end_ms: u64,
+
+    /// 主语言文本
+    pub primary: String,
+
+    /// 副语言文本，没有译文时为 None
+    pub secondary: Option<String>,
+}
+
+impl BilingualCue {
+    /// 合成一条两行的普通字幕：主语言在上，副语言在下。
+    pub fn to_stacked_cue(&self) -> Cue {
+        let text = match &self.secondary {
+            Some(secondary) => format!("{}\n{}", self.primary, secondary),
+            None => self.primary.clone(),
+        };
+        Cue {
+            start_ms: self.start_ms,
+            end_ms: self.end_ms,
+            text,
+        }
+    }
+}
+
+/// 双语字幕在画面上的排布方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BilingualLayout {
+    /// 每条字幕两行，适用于所有输出格式
+    #[default]
+    Stacked,
+
+    /// 主语言在底部、副语言在顶部，两个样式，仅 ASS 支持
+    TopBottom,
+}
+
+impl BilingualLayout {
+    /// 按名称解析：`stacked` / `top-bottom`。
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "stacked" => Some(BilingualLayout::Stacked),
+            "top-bottom" => Some(BilingualLayout::TopBottom),
+            _ => None,
+        }
+    }
+}
+
+/// 配对结果。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Bilingual {
+    /// 按开始时间排序的双语字幕
+    pub cues: Vec<BilingualCue>,
+
+    /// 没有译文、时间对不上等问题，行号指向 EDL
+    pub warnings: Vec<Warning>,
+
+    /// 翻译表中没有被任何片段用到的条目
+    pub unused_translations: Vec<String>,
+}
+
+impl Bilingual {
+    /// 整体前移 `origin_ms`，规则同 [`crate::transform::rebase_cues`]。
+    pub fn rebase(&mut self, origin_ms: u64) {
+        self.cues.retain(|cue| cue.end_ms > origin_ms);
+        for cue in &mut self.cues {
+            cue.start_ms = cue.start_ms.saturating_sub(origin_ms);
+            cue.end_ms -= origin_ms;
+        }
+    }
+
+    /// 全部合成为两行的普通字幕。
+    pub fn stacked_cues(&self) -> Vec<Cue> {
+        self.cues.iter().map(BilingualCue::to_stacked_cue).collect()
+    }
+}
+
+/// 翻译表的文件格式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableFormat {
+    Csv,
+    Tsv,
+    Json,
+}
+
+impl TableFormat {
+    /// 按扩展名判断。
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        match ext.as_str() {
+            "csv" => Some(TableFormat::Csv),
+            "tsv" | "tab" => Some(TableFormat::Tsv),
+            "json" => Some(TableFormat::Json),
+            _ => None,
+        }
+    }
+
+    /// 按内容判断：以 `{` / `[` 开头为 JSON，第一行有制表符为 TSV，否则为 CSV。
+    pub fn sniff(content: &str) -> Self {
+        let trimmed = content.trim_start_matches('\u{feff}').trim_start();
+        if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            TableFormat::Json
+        } else if trimmed.lines().next().is_some_and(|l| l.contains('\t')) {
+            TableFormat::Tsv
+        } else {
+            TableFormat::Csv
+        }
+    }
+}
+
+/// 按片段名索引的翻译表。
+///
+/// 片段名比较时忽略常见音频扩展名，所以 `VO_001.wav` 和 `VO_001` 是同一个键。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TranslationTable {
+    /// (原始片段名, 译文)，保持文件中的顺序
+    entries: Vec<(String, String)>,
+
+    /// 去掉扩展名的片段名 -> entries 下标
+    index: HashMap<String, usize>,
+}
+
+impl TranslationTable {
+    /// 解析翻译表。
+    ///
+    /// - CSV / TSV：第一列为片段名，第二列为译文；第一行是表头（clip / name / 片段名）时跳过
+    /// - JSON：`{"片段名": "译文", ...}`，或 `[{"clip_name": ..., "text": ...}, ...]`
+    ///
+    /// 同一片段名出现多次时以最后一次为准。
+    pub fn parse(content: &str, format: TableFormat) -> Result<Self> {
+        let content = content.trim_start_matches('\u{feff}');
+        let rows = match format {
+            TableFormat::Csv => parse_delimited(content, ',')?,
+            TableFormat::Tsv => parse_delimited(content, '\t')?,
+            TableFormat::Json => parse_json_table(content)?,
+        };
+
+        let mut table = TranslationTable::default();
+        for (name, text) in rows {
+            table.insert(name, text);
+        }
+        Ok(table)
+    }
+
+    pub fn insert(&mut self, clip_name: String, text: String) {
+        let key = table_key(&clip_name);
+        match self.index.get(&key) {
+            Some(&i) => self.entries[i] = (clip_name, text),
+            None => {
+                self.index.insert(key, self.entries.len());
+                self.entries.push((clip_name, text));
+            }
+        }
+    }
+
+    /// 查找片段名对应的译文。
+    pub fn get(&self, clip_name: &str) -> Option<&str> {
+        self.index
+            .get(&table_key(clip_name))
+            .map(|&i| self.entries[i].1.as_str())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// 用翻译表为 EDL 中的字幕配上副语言。
+pub fn translate_with_table(edl: &Edl, table: &TranslationTable, fps: f64) -> Bilingual {
+    let mut used = vec![false; table.entries.len()];
+    let mut result = Bilingual::default();
+
+    for event in &edl.events {
+        let Some(primary) = event_text(event) else {
+            continue;
+        };
+
+        let name = event.clip_name.as_deref().unwrap_or(&primary);
+        let secondary = match table.index.get(&table_key(name)) {
+            Some(&i) => {
+                used[i] = true;
+                let text = table.entries[i].1.trim();
+                if text.is_empty() {
+                    result.warnings.push(Warning {
+                        line: event.line,
+                        message: t!("bilingual.empty_translation", name),
+                    });
+                    None
+                } else {
+                    Some(text.to_string())
+                }
+            }
+            None => {
+                result.warnings.push(Warning {
+                    line: event.line,
+                    message: t!("bilingual.missing_translation", name),
+                });
+                None
+            }
+        };
+
+        result.cues.push(BilingualCue {
+            start_ms: event.record_in.to_millis(fps),
+            end_ms: event.record_out.to_millis(fps),
+            primary,
+            secondary,
+        });
+    }
+
+    result.unused_translations = table
+        .entries
+        .iter()
+        .zip(&used)
+        .filter(|(_, used)| !**used)
+        .map(|((name, _), _)| name.clone())
+        .collect();
+
+    result.cues.sort_by_key(|c| (c.start_ms, c.end_ms));
+    result
+}
+
+/// 把同一份 EDL 中两条轨道上的字幕配成双语。
+///
+/// 副轨事件按录制入点与主轨对应；入点对不上时退而找时间有重叠的事件，
+/// 并给出警告。时间一律以主轨为准。
+pub fn pair_tracks(
+    edl: &Edl,
+    primary_track: &str,
+    secondary_track: &str,
+    fps: f64,
+) -> Result<Bilingual> {
+    let rate = nominal_rate(fps);
+    let on_track = |track: &str| -> Vec<(&EdlEvent, String)> {
+        edl.events
+            .iter()
+            .filter(|e| e.on_track(track))
+            .filter_map(|e| Some((e, event_text(e)?)))
+            .collect()
+    };
+
+    let primary = on_track(primary_track);
+    let secondary = on_track(secondary_track);
+    for (track, events) in [(primary_track, &primary), (secondary_track, &secondary)] {
+        if events.is_empty() {
+            return Err(Error::Invalid(t!("bilingual.empty_track", track)));
+        }
+    }
+
+    let frames = |e: &EdlEvent| (e.record_in.to_frames(rate), e.record_out.to_frames(rate));
+    let mut used = vec![false; secondary.len()];
+    let mut result = Bilingual::default();
+
+    for (event, text) in &primary {
+        let (begin, end) = frames(event);
+
+        let exact = secondary
+            .iter()
+            .enumerate()
+            .position(|(j, (other, _))| !used[j] && frames(other).0 == begin);
+        let overlapping = || {
+            secondary.iter().enumerate().position(|(j, (other, _))| {
+                let (other_begin, other_end) = frames(other);
+                !used[j] && other_begin < end && begin < other_end
+            })
+        };
+
+        let secondary_text = match exact.or_else(overlapping) {
+            Some(j) => {
+                used[j] = true;
+                let (other, other_text) = &secondary[j];
+                if frames(other) != (begin, end) {
+                    result.warnings.push(Warning {
+                        line: event.line,
+                        message: t!(
+                            "bilingual.time_mismatch",
+                            event.record_in,
+                            event.record_out,
+                            other.record_in,
+                            other.record_out,
+                            other.line
+                        ),
+                    });
+                }
+                Some(other_text.clone())
+            }
+            None => {
+                result.warnings.push(Warning {
+                    line: event.line,
+                    message: t!("bilingual.unmatched_primary", text, secondary_track),
+                });
+                None
+            }
+        };
+
+        result.cues.push(BilingualCue {
+            start_ms: event.record_in.to_millis(fps),
+            end_ms: event.record_out.to_millis(fps),
+            primary: text.clone(),
+            secondary: secondary_text,
+        });
+    }
+
+    for ((event, text), used) in secondary.iter().zip(&used) {
+        if !used {
+            result.warnings.push(Warning {
+                line: event.line,
+                message: t!("bilingual.unmatched_secondary", text, primary_track),
+            });
+        }
+    }
+
+    result.cues.sort_by_key(|c| (c.start_ms, c.end_ms));
+    Ok(result)
+}
+
+fn table_key(clip_name: &str) -> String {
+    strip_audio_extension(clip_name.trim())
+}
+
+/// 解析 CSV / TSV，支持双引号包裹（其中可以有分隔符、换行和 `""` 转义）。
+fn parse_delimited(content: &str, delimiter: char) -> Result<Vec<(String, String)>> {
+    let mut rows = Vec::new();
+    let mut fields: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut line = 1;
+    let mut row_line = 1;
+    let mut chars = content.chars().peekable();
+
+    let mut finish_row = |fields: &mut Vec<String>, row_line: usize| -> Result<()> {
+        if fields.iter().all(|f| f.trim().is_empty()) {
+            fields.clear();
+            return Ok(());
+        }
+        if fields.len() < 2 {
+            return Err(Error::Parse {
+                line: row_line,
+                message: t!("bilingual.row_too_short"),
+            });
+        }
+        let name = fields[0].trim().to_string();
+        let is_header = rows.is_empty()
+            && matches!(
+                name.to_ascii_lowercase().as_str(),
+                "clip" | "clip_name" | "clip name" | "name" | "片段名"
+            );
+        if !is_header {
+            rows.push((name, fields[1].clone()));
+        }
+        fields.clear();
+        Ok(())
+    };
+
+    while let Some(c) = chars.next() {
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                '\n' => {
+                    line += 1;
+                    field.push('\n');
+                }
+                '\r' => {}
+                _ => field.push(c),
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                in_quotes = true;
+            }
+            '\r' => {}
+            '\n' => {
+                fields.push(std::mem::take(&mut field));
+                finish_row(&mut fields, row_line)?;
+                line += 1;
+                row_line = line;
+            }
+            c if c == delimiter => fields.push(std::mem::take(&mut field)),
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(Error::Parse {
+            line: row_line,
+            message: t!("bilingual.unclosed_quote"),
+        });
+    }
+    fields.push(field);
+    finish_row(&mut fields, row_line)?;
+
+    Ok(rows)
+}
+
+/// 解析 JSON 翻译表。
+fn parse_json_table(content: &str) -> Result<Vec<(String, String)>> {
+    let value: serde_json::Value = serde_json::from_str(content)?;
+
+    let invalid = || Error::Invalid(t!("bilingual.json_shape"));
+
+    match value {
+        serde_json::Value::Object(map) => map
+            .into_iter()
+            .map(|(name, text)| match text {
+                serde_json::Value::String(text) => Ok((name, text)),
+                _ => Err(Error::Invalid(t!("bilingual.json_not_string", name))),
+            })
+            .collect(),
+        serde_json::Value::Array(items) => items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| {
+                let field = |keys: &[&str]| {
+                    keys.iter()
+                        .find_map(|k| item.get(*k).and_then(|v| v.as_str()))
+                        .map(str::to_string)
+                };
+                match (
+                    field(&["clip_name", "clip", "name"]),
+                    field(&["text", "translation"]),
+                ) {
+                    (Some(name), Some(text)) => Ok((name, text)),
+                    _ => Err(Error::Invalid(t!("bilingual.json_missing_field", i + 1))),
+                }
+            })
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::parse_edl;
+
+    const EDL: &str = "\
+001 AX A1 C 00:00:00:00 00:00:02:00 01:00:00:00 01:00:02:00
+* FROM CLIP NAME: 你好.wav
+002 AX A2 C 00:00:00:00 00:00:02:00 01:00:00:00 01:00:02:00
+* FROM CLIP NAME: Hello
+003 AX A1 C 00:00:00:00 00:00:02:00 01:00:03:00 01:00:05:00
+* FROM CLIP NAME: 再见.wav
+004 AX A2 C 00:00:00:00 00:00:02:00 01:00:03:10 01:00:05:00
+* FROM CLIP NAME: Goodbye
+005 AX A1 C 00:00:00:00 00:00:01:00 01:00:06:00 01:00:07:00
+* FROM CLIP NAME: 谢谢.wav
+";
+
+    #[test]
+    fn pairs_two_tracks() {
+        let bilingual = pair_tracks(&parse_edl(EDL), "a1", "A2", 25.0).unwrap();
+
+        let secondary: Vec<Option<&str>> = bilingual
+            .cues
+            .iter()
+            .map(|c| c.secondary.as_deref())
+            .collect();
+        assert_eq!(secondary, [Some("Hello"), Some("Goodbye"), None]);
+        assert_eq!(bilingual.cues[1].start_ms, 3_603_000);
+
+        // 再见 的副轨入点偏了 10 帧，谢谢 没有副轨
+        assert_eq!(bilingual.warnings.len(), 2);
+        assert_eq!(bilingual.warnings[0].line, 5);
+        assert!(bilingual.warnings[0].message.contains("时间不一致"));
+        assert_eq!(bilingual.warnings[1].line, 9);
+
+        assert_eq!(bilingual.stacked_cues()[0].text, "你好\nHello");
+    }
+
+    #[test]
+    fn missing_track_is_an_error() {
+        assert!(pair_tracks(&parse_edl(EDL), "A1", "A9", 25.0).is_err());
+    }
+
+    #[test]
+    fn translation_table_formats() {
+        let csv = "clip_name,text\n你好.wav,Hello\n\"再见\",\"Good, \"\"bye\"\"\nnow\"\n";
+        let table = TranslationTable::parse(csv, TableFormat::Csv).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.get("你好"), Some("Hello"));
+        assert_eq!(table.get("再见.WAV"), Some("Good, \"bye\"\nnow"));
+
+        let tsv = "你好\tHello\n";
+        assert_eq!(
+            TranslationTable::parse(tsv, TableFormat::sniff(tsv))
+                .unwrap()
+                .get("你好.wav"),
+            Some("Hello")
+        );
+
+        let json = r#"[{"clip_name": "你好.wav", "text": "Hello"}]"#;
+        let table = TranslationTable::parse(json, TableFormat::sniff(json)).unwrap();
+        assert_eq!(table.get("你好"), Some("Hello"));
+
+        let json = r#"{"你好": "Hello"}"#;
+        assert_eq!(
+            TranslationTable::parse(json, TableFormat::Json)
+                .unwrap()
+                .get("你好"),
+            Some("Hello")
+        );
+
+        assert!(TranslationTable::parse("只有一列\n", TableFormat::Csv).is_err());
+    }
+
+    #[test]
+    fn table_reports_untranslated_and_unused() {
+        let table =
+            TranslationTable::parse("你好,Hello\n再见,\n多余,Extra\n", TableFormat::Csv).unwrap();
+        let bilingual = translate_with_table(&parse_edl(EDL), &table, 25.0);
+
+        // 只看主语言片段（A2 上的英文片段也会被当成字幕）
+        let zh: Vec<&BilingualCue> = bilingual
+            .cues
+            .iter()
+            .filter(|c| !c.primary.is_ascii())
+            .collect();
+        assert_eq!(zh[0].secondary.as_deref(), Some("Hello"));
+        assert_eq!(zh[1].secondary, None);
+        assert_eq!(zh[2].secondary, None);
+
+        assert!(
+            bilingual
+                .warnings
+                .iter()
+                .any(|w| w.line == 5 && w.message.contains("译文为空"))
+        );
+        assert!(
+            bilingual
+                .warnings
+                .iter()
+                .any(|w| w.line == 9 && w.message.contains("没有译文"))
+        );
+        assert_eq!(bilingual.unused_translations, ["多余"]);
+    }
+}
@@ -0,0 +1,827 @@
+//! 命令行参数：子命令、具名选项和 shell 补全。
+//!
+//! 帮助、用法和报错都从消息目录中取，跟随 `--lang` 切换语言。
+//! 省略子命令时按 `convert` 处理，旧的 `edl2srt <帧率> <输入> <输出> [选项]` 写法照常可用。
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::error::{ContextKind, ContextValue, ErrorKind};
+use clap::{Arg, ArgAction, ArgMatches, Command, value_parser};
+use clap_complete::Shell;
+use edl2srt::fps::is_valid_fps;
+use edl2srt::i18n::{Lang, text};
+use edl2srt::pipeline::{
+    CheckArgs, ConvertArgs, parse_bilingual_layout, parse_render_size, parse_tracks,
+};
+use edl2srt::{EncodingHint, SpeakerStyle, TextSource, Timecode, t};
+
+/// 命令名，用于用法、补全脚本和 `--version`
+pub const BIN_NAME: &str = "edl2srt";
+
+/// 用法错误的退出码，与 clap 的约定一致；运行时出错为 1
+pub const USAGE_ERROR: u8 = 2;
+
+const SUBCOMMANDS: [&str; 5] = ["convert", "check", "diff", "detect-encoding", "completions"];
+
+/// 解析后要执行的操作。
+pub enum Invocation {
+    Convert(Box<ConvertArgs>),
+    Check(CheckArgs),
+    Diff(DiffArgs),
+    DetectEncoding(DetectArgs),
+    Completions(Shell),
+    ListFormats,
+}
+
+/// `diff` 的参数。
+#[derive(Debug)]
+pub struct DiffArgs {
+    pub old: PathBuf,
+    pub new: PathBuf,
+    pub fps: Option<f64>,
+    pub json: Option<PathBuf>,
+    pub change_list: Option<PathBuf>,
+    pub input_encoding: Option<String>,
+}
+
+/// `detect-encoding` 的参数。
+#[derive(Debug)]
+pub struct DetectArgs {
+    pub input: PathBuf,
+
+    /// 地区提示，没给出时四个都试
+    pub hints: Vec<EncodingHint>,
+    pub lines: usize,
+}
+
+/// 命令行中的 `--lang <语言>` / `--lang=<语言>`，可以出现在任何位置。
+///
+/// 要在构造命令、输出任何内容之前确定语言，所以先于 clap 单独扫描一遍。
+pub fn lang_from_args(args: &[String]) -> Result<Option<Lang>, String> {
+    let mut lang = None;
+    let mut iter = args.iter().skip(1);
+    while let Some(arg) = iter.next() {
+        let value = if arg == "--lang" {
+            iter.next()
+                .ok_or_else(|| t!("cli.missing_value", "--lang"))?
+        } else if let Some(value) = arg.strip_prefix("--lang=") {
+            value
+        } else {
+            continue;
+        };
+        lang = Some(parse_lang(value)?);
+    }
+    Ok(lang)
+}
+
+/// 解析命令行；出错或要显示帮助时返回 [`ParseError`]，交给 [`report_error`]。
+pub fn parse(args: Vec<String>) -> Result<Invocation, ParseError> {
+    let args = with_default_subcommand(args);
+    let subcommand = args
+        .iter()
+        .skip(1)
+        .find(|arg| SUBCOMMANDS.contains(&arg.as_str()))
+        .cloned();
+    let matches = command()
+        .try_get_matches_from(args)
+        .map_err(|err| render_clap_error(err, subcommand.as_deref()))?;
+
+    if matches.get_flag("list-formats") {
+        return Ok(Invocation::ListFormats);
+    }
+    match matches.subcommand() {
+        Some(("convert", sub)) => convert_args(sub).map(|args| Invocation::Convert(Box::new(args))),
+        Some(("check", sub)) => check_args(sub).map(Invocation::Check),
+        Some(("diff", sub)) => Ok(Invocation::Diff(diff_args(sub))),
+        Some(("detect-encoding", sub)) => Ok(Invocation::DetectEncoding(detect_args(sub))),
+        Some(("completions", sub)) => Ok(Invocation::Completions(
+            *sub.get_one::<Shell>("shell").expect("必填参数"),
+        )),
+        _ => unreachable!("arg_required_else_help"),
+    }
+}
+
+/// 第一个参数（跳过 `--lang`）既不是子命令也不是顶层选项时，在它前面补上 `convert`。
+fn with_default_subcommand(mut args: Vec<String>) -> Vec<String> {
+    let top_level = ["-h", "--help", "-V", "--version", "--list-formats"];
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--lang" => i += 2,
+            arg if arg.starts_with("--lang=") => i += 1,
+            arg if SUBCOMMANDS.contains(&arg) || top_level.contains(&arg) => return args,
+            _ => {
+                args.insert(i, "convert".to_string());
+                return args;
+            }
+        }
+    }
+    args
+}
+
+/// 把 `parse` 的错误打印出来，返回进程退出码。
+///
+/// 帮助和版本信息也以错误的形式返回，打印到标准输出，退出码为 0。
+pub fn report_error(err: ParseError) -> ExitCode {
+    match err {
+        ParseError::Display(text) => {
+            print!("{}", text);
+            ExitCode::SUCCESS
+        }
+        ParseError::Usage { message, command } => {
+            eprintln!("{}", t!("cli.error", message));
+            eprintln!("{}", t!("cli.see_help", command));
+            ExitCode::from(USAGE_ERROR)
+        }
+    }
+}
+
+/// 命令行解析失败的原因。
+pub enum ParseError {
+    /// 帮助或版本信息
+    Display(String),
+
+    /// 用法错误，`command` 为查看帮助的命令，例如 `edl2srt convert --help`
+    Usage { message: String, command: String },
+}
+
+impl ParseError {
+    pub fn usage(message: String, subcommand: Option<&str>) -> ParseError {
+        let command = match subcommand {
+            Some(name) => format!("{} {} --help", BIN_NAME, name),
+            None => format!("{} --help", BIN_NAME),
+        };
+        ParseError::Usage { message, command }
+    }
+}
+
+/// 把 clap 的报错换成当前语言的消息；clap 自带的英文消息只在没有对应翻译时使用。
+fn render_clap_error(err: clap::Error, subcommand: Option<&str>) -> ParseError {
+    let context = |kind| match err.get(kind) {
+        Some(ContextValue::String(value)) => value.clone(),
+        Some(ContextValue::Strings(values)) => values.join(text("list.separator")),
+        Some(ContextValue::StyledStr(value)) => value.to_string(),
+        Some(ContextValue::Number(value)) => value.to_string(),
+        _ => String::new(),
+    };
+    let arg = context(ContextKind::InvalidArg);
+    let value = context(ContextKind::InvalidValue);
+
+    let message = match err.kind() {
+        ErrorKind::DisplayHelp | ErrorKind::DisplayVersion => {
+            return ParseError::Display(err.render().to_string());
+        }
+        ErrorKind::DisplayHelpOnMissingArgumentOrSubcommand => {
+            // 什么都没给时显示帮助，但仍按用法错误退出
+            eprint!("{}", err.render());
+            return ParseError::Usage {
+                message: t!("cli.too_few_arguments"),
+                command: format!("{} --help", BIN_NAME),
+            };
+        }
+        ErrorKind::UnknownArgument => {
+            let suggested = context(ContextKind::SuggestedArg);
+            if suggested.is_empty() {
+                t!("cli.unknown_option", arg)
+            } else {
+                t!("cli.unknown_option_suggest", arg, suggested)
+            }
+        }
+        ErrorKind::InvalidSubcommand => t!("cli.unknown_subcommand", arg),
+        ErrorKind::InvalidValue if value.is_empty() => t!("cli.missing_value", arg),
+        ErrorKind::InvalidValue => {
+            t!(
+                "cli.invalid_value_choices",
+                arg,
+                value,
+                context(ContextKind::ValidValue)
+            )
+        }
+        ErrorKind::ValueValidation => match std::error::Error::source(&err) {
+            Some(reason) => t!("cli.invalid_value_reason", arg, reason),
+            None => t!("cli.invalid_value", arg, value),
+        },
+        ErrorKind::MissingRequiredArgument => {
+            t!("cli.missing_arguments", context(ContextKind::InvalidArg))
+        }
+        ErrorKind::ArgumentConflict => {
+            let prior = context(ContextKind::PriorArg);
+            if prior.is_empty() || prior == arg {
+                t!("cli.used_twice", arg)
+            } else {
+                t!("cli.argument_conflict", arg, prior)
+            }
+        }
+        ErrorKind::WrongNumberOfValues => t!(
+            "cli.wrong_number_of_values",
+            arg,
+            context(ContextKind::ExpectedNumValues)
+        ),
+        ErrorKind::TooManyValues => t!("cli.extra_argument", value),
+        _ => {
+            // 取 clap 报错的第一行，去掉 "error: " 前缀
+            let rendered = err.render().to_string();
+            let first = rendered.lines().next().unwrap_or_default();
+            first.strip_prefix("error: ").unwrap_or(first).to_string()
+        }
+    };
+    ParseError::usage(message, subcommand)
+}
+
+/// 完整的命令定义，也用来生成补全脚本。
+pub fn command() -> Command {
+    let usage_template = format!(
+        "{{about-with-newline}}\n{}\n  {{usage}}\n\n{{all-args}}{{after-help}}",
+        text("cli.heading.usage")
+    );
+    let localize = |command: Command| {
+        command
+            .help_template(usage_template.clone())
+            .next_help_heading(text("cli.heading.options"))
+            .disable_help_flag(true)
+            .arg(
+                Arg::new("help")
+                    .short('h')
+                    .long("help")
+                    .action(ArgAction::Help)
+                    .help(text("arg.help")),
+            )
+    };
+
+    let convert = localize(
+        Command::new("convert")
+            .about(text("about.convert"))
+            .override_usage(t!("usage.convert", BIN_NAME))
+            .after_help(t!("help.convert", BIN_NAME)),
+    )
+    .arg(positionals("arg.convert_positionals"))
+    .args(timeline_args())
+    .arg(path_opt("output", "OUTPUT", "arg.output").short('o'))
+    .arg(opt("format", "FORMAT", "arg.format").short('f'))
+    .arg(path_opt("dump-json", "PATH", "arg.dump_json"))
+    .arg(path_opt("reel", "PATH", "arg.reel").action(ArgAction::Append))
+    .arg(timecodes_opt("reel-starts", "arg.reel_starts"))
+    .arg(timecodes_opt("reel-lengths", "arg.reel_lengths"))
+    .arg(
+        Arg::new("conform")
+            .long("conform")
+            .num_args(2)
+            .value_names(["OLD", "TRANSLATED"])
+            .value_parser(value_parser!(PathBuf))
+            .help(text("arg.conform")),
+    )
+    .arg(opt("tracks", "PRIMARY,SECONDARY", "arg.tracks").value_parser(parse_tracks))
+    .arg(path_opt("translations", "PATH", "arg.translations"))
+    .arg(
+        opt("bilingual-layout", "LAYOUT", "arg.bilingual_layout")
+            .value_parser(parse_bilingual_layout),
+    )
+    .arg(flag("transcribe", "arg.transcribe"))
+    .arg(opt("transcribe-url", "URL", "arg.transcribe_url"))
+    .arg(path_opt("media-path", "DIR", "arg.media_path").action(ArgAction::Append))
+    .arg(path_opt("transcribe-cache", "DIR", "arg.transcribe_cache"))
+    .arg(opt(
+        "transcribe-language",
+        "LANGUAGE",
+        "arg.transcribe_language",
+    ))
+    .arg(origin_opt())
+    .arg(opt("text-source", "SOURCE", "arg.text_source").value_parser(parse_text_source))
+    .arg(opt("locator-color", "COLOR", "arg.locator_color").action(ArgAction::Append))
+    .arg(opt("speaker-pattern", "REGEX", "arg.speaker_pattern"))
+    .arg(
+        opt("speaker-reel", "REEL=SPEAKER", "arg.speaker_reel")
+            .action(ArgAction::Append)
+            .value_parser(parse_speaker_reel),
+    )
+    .arg(opt("speaker-style", "STYLE", "arg.speaker_style").value_parser(parse_speaker_style))
+    .arg(path_opt("render", "DIR", "arg.render"))
+    .arg(path_opt("render-font", "PATH", "arg.render_font"))
+    .arg(opt("render-size", "WxH", "arg.render_size").value_parser(parse_render_size))
+    .args(preset_args())
+    .arg(flag("edit", "arg.edit"))
+    .arg(flag("stream", "arg.stream"));
+
+    let check = localize(
+        Command::new("check")
+            .about(text("about.check"))
+            .override_usage(t!("usage.check", BIN_NAME)),
+    )
+    .arg(positionals("arg.check_positionals"))
+    .args(timeline_args())
+    .arg(opt("format", "FORMAT", "arg.check_format").short('f'))
+    .arg(origin_opt())
+    .args(preset_args());
+
+    let diff = localize(
+        Command::new("diff")
+            .about(text("about.diff"))
+            .override_usage(t!("usage.diff", BIN_NAME)),
+    )
+    .arg(
+        Arg::new("old")
+            .required(true)
+            .value_name("OLD")
+            .value_parser(value_parser!(PathBuf))
+            .help_heading(text("cli.heading.arguments"))
+            .help(text("arg.diff_old")),
+    )
+    .arg(
+        Arg::new("new")
+            .required(true)
+            .value_name("NEW")
+            .value_parser(value_parser!(PathBuf))
+            .help_heading(text("cli.heading.arguments"))
+            .help(text("arg.diff_new")),
+    )
+    .arg(fps_opt().help(text("arg.diff_fps")))
+    .arg(path_opt("json", "PATH", "arg.diff_json"))
+    .arg(path_opt("change-list", "PATH", "arg.diff_change_list"))
+    .arg(opt("input-encoding", "ENCODING", "arg.input_encoding"));
+
+    let detect = localize(
+        Command::new("detect-encoding")
+            .about(text("about.detect_encoding"))
+            .override_usage(t!("usage.detect_encoding", BIN_NAME)),
+    )
+    .arg(
+        Arg::new("input")
+            .required(true)
+            .value_name("INPUT")
+            .value_parser(value_parser!(PathBuf))
+            .help_heading(text("cli.heading.arguments"))
+            .help(text("arg.detect_input")),
+    )
+    .arg(
+        opt("encoding-hint", "REGION", "arg.encoding_hint")
+            .action(ArgAction::Append)
+            .value_parser(parse_encoding_hint),
+    )
+    .arg(
+        opt("lines", "COUNT", "arg.lines")
+            .default_value("5")
+            .hide_default_value(true)
+            .value_parser(parse_lines),
+    );
+
+    let completions = localize(
+        Command::new("completions")
+            .about(text("about.completions"))
+            .override_usage(t!("usage.completions", BIN_NAME))
+            .after_help(t!("help.completions", BIN_NAME)),
+    )
+    .arg(
+        Arg::new("shell")
+            .required(true)
+            .value_name("SHELL")
+            .value_parser(value_parser!(Shell))
+            .hide_possible_values(true)
+            .help_heading(text("cli.heading.arguments"))
+            .help(text("arg.shell")),
+    );
+
+    localize(
+        Command::new(BIN_NAME)
+            .bin_name(BIN_NAME)
+            .version(env!("CARGO_PKG_VERSION"))
+            .about(text("about.main"))
+            .override_usage(t!("usage.main", BIN_NAME))
+            .after_help(t!("help.main", BIN_NAME))
+            .subcommand_help_heading(text("cli.heading.commands"))
+            .disable_help_subcommand(true)
+            .disable_version_flag(true)
+            .arg_required_else_help(true),
+    )
+    .arg(
+        Arg::new("version")
+            .short('V')
+            .long("version")
+            .action(ArgAction::Version)
+            .help(text("arg.version")),
+    )
+    .arg(flag("list-formats", "arg.list_formats").exclusive(true))
+    .arg(
+        opt("lang", "LANG", "arg.lang")
+            .global(true)
+            .value_parser(parse_lang),
+    )
+    .subcommands([convert, check, diff, detect, completions])
+}
+
+/// 位置参数 `[帧率] 输入 [输出]`，可以与选项穿插，个数和含义见 [`split_positionals`]
+fn positionals(help: &'static str) -> Arg {
+    Arg::new("positionals")
+        .num_args(1..)
+        .action(ArgAction::Append)
+        .value_name("ARGS")
+        .help_heading(text("cli.heading.arguments"))
+        .help(text(help))
+}
+
+/// 读取时间线用到的选项：帧率、输入文件和输入编码
+fn timeline_args() -> [Arg; 3] {
+    [
+        fps_opt().help(text("arg.fps")),
+        path_opt("input", "INPUT", "arg.input").short('i'),
+        opt("input-encoding", "ENCODING", "arg.input_encoding"),
+    ]
+}
+
+fn preset_args() -> [Arg; 2] {
+    [
+        opt("preset", "NAME", "arg.preset"),
+        path_opt("config", "PATH", "arg.config"),
+    ]
+}
+
+fn fps_opt() -> Arg {
+    Arg::new("fps")
+        .long("fps")
+        .value_name("FPS")
+        .value_parser(parse_fps)
+}
+
+fn origin_opt() -> Arg {
+    opt("origin", "TIMECODE", "arg.origin").value_parser(parse_timecode)
+}
+
+fn timecodes_opt(id: &'static str, help: &'static str) -> Arg {
+    opt(id, "TIMECODES", help).value_parser(parse_timecode_list)
+}
+
+/// 带一个值的长选项
+fn opt(id: &'static str, value_name: &'static str, help: &'static str) -> Arg {
+    Arg::new(id)
+        .long(id)
+        .value_name(value_name)
+        .help(text(help))
+}
+
+fn path_opt(id: &'static str, value_name: &'static str, help: &'static str) -> Arg {
+    opt(id, value_name, help).value_parser(value_parser!(PathBuf))
+}
+
+fn flag(id: &'static str, help: &'static str) -> Arg {
+    Arg::new(id)
+        .long(id)
+        .action(ArgAction::SetTrue)
+        .help(text(help))
+}
+
+fn convert_args(matches: &ArgMatches) -> Result<ConvertArgs, ParseError> {
+    let mut input = matches.get_one::<PathBuf>("input").cloned();
+    let mut output = matches.get_one::<PathBuf>("output").cloned();
+    let fps = split_positionals(matches, "convert", &mut [&mut input, &mut output])?;
+
+    let transcribe = [
+        "transcribe-url",
+        "transcribe-cache",
+        "transcribe-language",
+        "media-path",
+    ]
+    .iter()
+    .any(|id| matches.contains_id(id))
+        || matches.get_flag("transcribe");
+
+    Ok(ConvertArgs {
+        fps,
+        input: input.expect("已检查"),
+        output: output.expect("已检查"),
+        input_encoding: matches.get_one("input-encoding").cloned(),
+        format: matches.get_one("format").cloned(),
+        dump_json: matches.get_one("dump-json").cloned(),
+        reels: many(matches, "reel"),
+        reel_starts: matches.get_one("reel-starts").cloned(),
+        reel_lengths: matches.get_one("reel-lengths").cloned(),
+        conform: matches
+            .get_many::<PathBuf>("conform")
+            .map(|mut paths| (paths.next().unwrap().clone(), paths.next().unwrap().clone())),
+        tracks: matches.get_one("tracks").cloned(),
+        translations: matches.get_one("translations").cloned(),
+        bilingual_layout: matches.get_one("bilingual-layout").copied(),
+        transcribe,
+        transcribe_url: matches.get_one("transcribe-url").cloned(),
+        transcribe_cache: matches.get_one("transcribe-cache").cloned(),
+        transcribe_language: matches.get_one("transcribe-language").cloned(),
+        media_paths: many(matches, "media-path"),
+        origin: matches.get_one("origin").copied(),
+        text_source: matches.get_one("text-source").copied(),
+        locator_colors: many(matches, "locator-color"),
+        speaker_pattern: matches.get_one("speaker-pattern").cloned(),
+        speaker_style: matches.get_one("speaker-style").copied(),
+        speaker_reels: many(matches, "speaker-reel"),
+        render_dir: matches.get_one("render").cloned(),
+        render_font: matches.get_one("render-font").cloned(),
+        render_options: matches.get_one("render-size").copied(),
+        preset: matches.get_one("preset").cloned(),
+        config: matches.get_one("config").cloned(),
+        edit: matches.get_flag("edit"),
+        stream: matches.get_flag("stream"),
+    })
+}
+
+fn check_args(matches: &ArgMatches) -> Result<CheckArgs, ParseError> {
+    let mut input = matches.get_one::<PathBuf>("input").cloned();
+    let fps = split_positionals(matches, "check", &mut [&mut input])?;
+
+    Ok(CheckArgs {
+        fps,
+        input: input.expect("已检查"),
+        input_encoding: matches.get_one("input-encoding").cloned(),
+        format: matches.get_one("format").cloned(),
+        origin: matches.get_one("origin").copied(),
+        preset: matches.get_one("preset").cloned(),
+        config: matches.get_one("config").cloned(),
+    })
+}
+
+fn diff_args(matches: &ArgMatches) -> DiffArgs {
+    DiffArgs {
+        old: matches.get_one::<PathBuf>("old").expect("必填参数").clone(),
+        new: matches.get_one::<PathBuf>("new").expect("必填参数").clone(),
+        fps: matches.get_one("fps").copied(),
+        json: matches.get_one("json").cloned(),
+        change_list: matches.get_one("change-list").cloned(),
+        input_encoding: matches.get_one("input-encoding").cloned(),
+    }
+}
+
+fn detect_args(matches: &ArgMatches) -> DetectArgs {
+    let hints: Vec<EncodingHint> = many(matches, "encoding-hint");
+    DetectArgs {
+        input: matches
+            .get_one::<PathBuf>("input")
+            .expect("必填参数")
+            .clone(),
+        hints: if hints.is_empty() {
+            EncodingHint::ALL.to_vec()
+        } else {
+            hints
+        },
+        lines: *matches.get_one("lines").expect("有默认值"),
+    }
+}
+
+fn many<T: Clone + Send + Sync + 'static>(matches: &ArgMatches, id: &str) -> Vec<T> {
+    matches
+        .get_many::<T>(id)
+        .map(|values| values.cloned().collect())
+        .unwrap_or_default()
+}
+
+/// 把位置参数填进具名选项没给出的路径，多出一个时第一个是帧率。
+///
+/// 例如 `convert` 的三个位置参数依次是帧率、输入、输出；
+/// 用 `--output` 给出输出后，两个位置参数就是帧率和输入。
+fn split_positionals(
+    matches: &ArgMatches,
+    subcommand: &str,
+    paths: &mut [&mut Option<PathBuf>],
+) -> Result<Option<f64>, ParseError> {
+    let usage = |message| ParseError::usage(message, Some(subcommand));
+    let values: Vec<&String> = matches
+        .get_many::<String>("positionals")
+        .map(Iterator::collect)
+        .unwrap_or_default();
+    let mut fps = matches.get_one::<f64>("fps").copied();
+    let missing = paths.iter().filter(|path| path.is_none()).count();
+
+    // 第一个位置参数是数字时按旧写法当作帧率，后面必须跟齐文件路径，
+    // 否则 `edl2srt 25 in.edl` 会把 in.edl 当成输出文件
+    let leading_fps = fps.is_none() && values.first().is_some_and(|v| v.parse::<f64>().is_ok());
+    let values = if leading_fps && values.len() <= missing {
+        return Err(usage(t!("cli.fps_without_paths", values[0])));
+    } else if values.len() == missing + 1 && fps.is_none() {
+        fps = Some(parse_fps(values[0]).map_err(usage)?);
+        &values[1..]
+    } else if values.len() > missing {
+        return Err(usage(t!("cli.extra_argument", values[missing])));
+    } else if values.len() < missing {
+        return Err(usage(t!("cli.too_few_arguments")));
+    } else {
+        &values[..]
+    };
+
+    let mut values = values.iter();
+    for path in paths.iter_mut().filter(|path| path.is_none()) {
+        **path = values.next().map(PathBuf::from);
+    }
+    Ok(fps)
+}
+
+fn parse_lang(value: &str) -> Result<Lang, String> {
+    Lang::parse(value).ok_or_else(|| t!("cli.bad_lang", value))
+}
+
+fn parse_fps(value: &str) -> Result<f64, String> {
+    let fps: f64 = value.parse().map_err(|_| t!("cli.bad_fps", value))?;
+    if !is_valid_fps(fps) {
+        return Err(t!("cli.fps_not_positive"));
+    }
+    Ok(fps)
+}
+
+fn parse_timecode(value: &str) -> Result<Timecode, String> {
+    Timecode::parse(value).ok_or_else(|| t!("cli.bad_timecode", value))
+}
+
+/// 解析逗号分隔的时间码列表，例如 "00:20:13:05,00:18:00:00"。
+fn parse_timecode_list(value: &str) -> Result<Vec<Timecode>, String> {
+    value
+        .split(',')
+        .map(|part| parse_timecode(part.trim()))
+        .collect()
+}
+
+fn parse_text_source(value: &str) -> Result<TextSource, String> {
+    TextSource::parse(value).ok_or_else(|| t!("cli.bad_text_source", value))
+}
+
+fn parse_speaker_style(value: &str) -> Result<SpeakerStyle, String> {
+    SpeakerStyle::parse(value).ok_or_else(|| t!("cli.bad_speaker_style", value))
+}
+
+/// 解析 "卷名=说话人"
+fn parse_speaker_reel(value: &str) -> Result<(String, String), String> {
+    value
+        .split_once('=')
+        .filter(|(reel, speaker)| !reel.is_empty() && !speaker.is_empty())
+        .map(|(reel, speaker)| (reel.to_string(), speaker.to_string()))
+        .ok_or_else(|| t!("cli.bad_speaker_reel", value))
+}
+
+fn parse_encoding_hint(value: &str) -> Result<EncodingHint, String> {
+    EncodingHint::parse(value).ok_or_else(|| t!("cli.bad_encoding_hint", value))
+}
+
+fn parse_lines(value: &str) -> Result<usize, String> {
+    value.parse().map_err(|_| t!("cli.bad_lines", value))
+}
+
+/// 把补全脚本写到标准输出
+pub fn print_completions(shell: Shell) {
+    clap_complete::generate(shell, &mut command(), BIN_NAME, &mut std::io::stdout());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    fn convert(line: &str) -> ConvertArgs {
+        match parse(args(line)) {
+            Ok(Invocation::Convert(args)) => *args,
+            _ => panic!("{} 应解析为 convert", line),
+        }
+    }
+
+    fn usage_error(line: &str) -> String {
+        match parse(args(line)) {
+            Err(ParseError::Usage { message, .. }) => message,
+            _ => panic!("{} 应报用法错误", line),
+        }
+    }
+
+    #[test]
+    fn command_is_consistent() {
+        command().debug_assert();
+    }
+
+    #[test]
+    fn legacy_positionals_mean_convert() {
+        let legacy = convert("edl2srt 25 in.edl out.srt --input-encoding shift_jis");
+        assert_eq!(legacy.fps, Some(25.0));
+        assert_eq!(legacy.input, PathBuf::from("in.edl"));
+        assert_eq!(legacy.output, PathBuf::from("out.srt"));
+        assert_eq!(legacy.input_encoding.as_deref(), Some("shift_jis"));
+
+        let inferred = convert("edl2srt in.edl out.srt");
+        assert_eq!(inferred.fps, None);
+        assert_eq!(inferred.input, PathBuf::from("in.edl"));
+    }
+
+    #[test]
+    fn named_flags_match_positionals() {
+        let named = convert("edl2srt convert --fps 23.976 --input in.edl -o out.vtt --format vtt");
+        assert_eq!(named.fps, Some(23.976));
+        assert_eq!(named.input, PathBuf::from("in.edl"));
+        assert_eq!(named.output, PathBuf::from("out.vtt"));
+        assert_eq!(named.format.as_deref(), Some("vtt"));
+
+        // 给出 --output 后，剩下的位置参数是帧率和输入
+        let mixed = convert("edl2srt convert 24 in.edl --output out.srt");
+        assert_eq!(mixed.fps, Some(24.0));
+        assert_eq!(mixed.output, PathBuf::from("out.srt"));
+    }
+
+    #[test]
+    fn parses_option_values() {
+        let args = convert(
+            "edl2srt 25 a.edl b.srt --reel r2.edl --reel r3.edl --reel-lengths 00:20:00:00 \
+             --conform old.edl old.srt --speaker-reel R01=小明 --media-path vo",
+        );
+        assert_eq!(args.reels.len(), 2);
+        assert_eq!(args.reel_lengths.map(|l| l.len()), Some(1));
+        assert_eq!(
+            args.conform,
+            Some((PathBuf::from("old.edl"), PathBuf::from("old.srt")))
+        );
+        assert_eq!(
+            args.speaker_reels,
+            [("R01".to_string(), "小明".to_string())]
+        );
+        assert!(args.transcribe, "--media-path 隐含 --transcribe");
+    }
+
+    #[test]
+    fn reports_usage_errors() {
+        assert_eq!(usage_error("edl2srt in.edl"), "参数不足。");
+        for line in [
+            "edl2srt 25 in.edl",
+            "edl2srt convert 25",
+            "edl2srt check 25",
+        ] {
+            assert_eq!(
+                usage_error(line),
+                "'25' 被当作帧率，后面缺少输入或输出文件。",
+                "{}",
+                line
+            );
+        }
+        assert_eq!(
+            usage_error("edl2srt abc in.edl out.srt"),
+            "无效的帧率格式 'abc'。请输入数字（例如 24 或 23.976）。"
+        );
+        for fps in ["0", "-25", "nan", "inf"] {
+            let message = usage_error(&format!("edl2srt convert --fps={} in.edl out.srt", fps));
+            assert!(
+                message.ends_with("帧率必须是大于 0 的有限数字。"),
+                "{}",
+                message
+            );
+        }
+        assert!(
+            usage_error("edl2srt 25 in.edl out.srt --origin 1:2").contains("无效的时间码: 1:2")
+        );
+        assert!(usage_error("edl2srt 25 in.edl out.srt --bogus").contains("--bogus"));
+        assert!(usage_error("edl2srt diff old.edl").contains("<NEW>"));
+    }
+
+    #[test]
+    fn help_and_version_anywhere() {
+        for line in [
+            "edl2srt --help",
+            "edl2srt 25 a.edl b.srt -h",
+            "edl2srt diff --help",
+        ] {
+            assert!(
+                matches!(parse(args(line)), Err(ParseError::Display(_))),
+                "{}",
+                line
+            );
+        }
+        match parse(args("edl2srt --version")) {
+            Err(ParseError::Display(text)) => {
+                assert_eq!(
+                    text.trim(),
+                    format!("edl2srt {}", env!("CARGO_PKG_VERSION"))
+                )
+            }
+            _ => panic!("--version 应显示版本"),
+        }
+    }
+
+    #[test]
+    fn lang_can_appear_anywhere() {
+        assert_eq!(
+            lang_from_args(&args("edl2srt --lang en 25 a b")),
+            Ok(Some(Lang::En))
+        );
+        assert_eq!(
+            lang_from_args(&args("edl2srt diff a b --lang=zh")),
+            Ok(Some(Lang::Zh))
+        );
+        assert_eq!(lang_from_args(&args("edl2srt a b")), Ok(None));
+        assert!(lang_from_args(&args("edl2srt a b --lang fr")).is_err());
+        assert!(matches!(
+            parse(args("edl2srt --lang en 25 a.edl b.srt")),
+            Ok(Invocation::Convert(_))
+        ));
+
+        let interleaved = convert("edl2srt 25 a.edl --lang en b.srt");
+        assert_eq!(interleaved.output, PathBuf::from("b.srt"));
+    }
+
+    #[test]
+    fn generates_completions() {
+        let mut out = Vec::new();
+        clap_complete::generate(Shell::Bash, &mut command(), BIN_NAME, &mut out);
+        let script = String::from_utf8(out).unwrap();
+        assert!(script.contains("detect-encoding"));
+        assert!(script.contains("--speaker-style"));
+    }
+}
@@ -0,0 +1,342 @@
+//! 项目配置文件 `edl2srt.toml` 与命名预设。
+//!
+//! 配置文件依次在当前目录和用户配置目录（例如 `~/.config/edl2srt/`）中查找，
+//! 也可以用 `--config` 指定。键名与命令行参数相同（去掉前面的 `--`）：
+//!
+//! ```toml
+//! # 不指定 --preset 时使用
+//! [default]
+//! input-encoding = "gbk"
+//!
+//! [preset.netflix-zh]
+//! fps = 23.976
+//! format = "srt"
+//! text-source = "transcribe"
+//! transcribe-language = "zh"
+//!
+//! [preset.netflix-zh.cleanup]
+//! replace = [["_", " "]]
+//! collapse-spaces = true
+//!
+//! [preset.netflix-zh.qc]
+//! max-chars-per-line = 16
+//! max-lines = 2
+//! min-duration-ms = 833
+//! max-chars-per-second = 9
+//!
+//! [preset.netflix-zh.speakers]
+//! pattern = "^[A-Z]_(?P<speaker>[^_]+)_"
+//! style = "dash"
+//! ```
+//!
+//! 预设中没写的项取 `[default]` 中的值；命令行参数总是优先于配置文件。
+//! 相对路径按配置文件所在目录解析。
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::i18n::text;
+use crate::qc::QcLimits;
+use crate::speaker::SpeakerRules;
+use crate::t;
+use crate::transform::CleanupRules;
+
+/// 配置文件名
+pub const CONFIG_FILE_NAME: &str = "edl2srt.toml";
+
+/// 字幕文本的来源。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TextSource {
+    /// 片段名（去掉音频扩展名），默认
+    ClipName,
+
+    /// 转写服务的结果
+    Transcribe,
+
+    /// Avid 定位点（`* LOC:` 注释）的文本
+    Locators,
+}
+
+impl TextSource {
+    pub fn parse(text: &str) -> Option<TextSource> {
+        match text {
+            "clip-name" => Some(TextSource::ClipName),
+            "transcribe" => Some(TextSource::Transcribe),
+            "locators" => Some(TextSource::Locators),
+            _ => None,
+        }
+    }
+}
+
+/// 一组预设值，所有项都可省略。
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Preset {
+    /// 帧率
+    #[serde(alias = "rate")]
+    pub fps: Option<f64>,
+
+    /// 输入编码
+    #[serde(alias = "encoding")]
+    pub input_encoding: Option<String>,
+
+    /// 输出格式名
+    pub format: Option<String>,
+
+    pub text_source: Option<TextSource>,
+    pub transcribe_url: Option<String>,
+    pub transcribe_cache: Option<PathBuf>,
+    pub transcribe_language: Option<String>,
+
+    /// 使用定位点作为字幕时只取这些颜色，空表示全部
+    #[serde(alias = "locator-color")]
+    pub locator_colors: Vec<String>,
+
+    /// 查找音频源文件的目录
+    #[serde(alias = "media-paths")]
+    pub media_path: Vec<PathBuf>,
+
+    /// 双语：`"主轨,副轨"`
+    pub tracks: Option<String>,
+
+    /// 双语：翻译表路径
+    pub translations: Option<PathBuf>,
+
+    /// 双语排布：`stacked` 或 `top-bottom`
+    pub bilingual_layout: Option<String>,
+
+    /// 成片起点时间码
+    pub origin: Option<String>,
+
+    pub cleanup: Option<CleanupRules>,
+    pub qc: Option<QcLimits>,
+
+    /// 说话人识别与标注
+    pub speakers: Option<SpeakerRules>,
+
+    /// 烧录预览使用的字体文件
+    pub render_font: Option<PathBuf>,
+
+    /// 烧录预览的分辨率，例如 `"1920x1080"`
+    pub render_size: Option<String>,
+}
+
+impl Preset {
+    /// 逐项合并：本预设没写的项取 `fallback` 中的值。
+    pub fn or(self, fallback: &Preset) -> Preset {
+        Preset {
+            fps: self.fps.or(fallback.fps),
+            input_encoding: self.input_encoding.or(fallback.input_encoding.clone()),
+            format: self.format.or(fallback.format.clone()),
+            text_source: self.text_source.or(fallback.text_source),
+            transcribe_url: self.transcribe_url.or(fallback.transcribe_url.clone()),
+            transcribe_cache: self.transcribe_cache.or(fallback.transcribe_cache.clone()),
+            transcribe_language: self
+                .transcribe_language
+                .or(fallback.transcribe_language.clone()),
+            locator_colors: if self.locator_colors.is_empty() {
+                fallback.locator_colors.clone()
+            } else {
+                self.locator_colors
+            },
+            media_path: if self.media_path.is_empty() {
+                fallback.media_path.clone()
+            } else {
+                self.media_path
+            },
+            tracks: self.tracks.or(fallback.tracks.clone()),
+            translations: self.translations.or(fallback.translations.clone()),
+            bilingual_layout: self.bilingual_layout.or(fallback.bilingual_layout.clone()),
+            origin: self.origin.or(fallback.origin.clone()),
+            cleanup: self.cleanup.or(fallback.cleanup.clone()),
+            qc: self.qc.or(fallback.qc.clone()),
+            speakers: self.speakers.or(fallback.speakers.clone()),
+            render_font: self.render_font.or(fallback.render_font.clone()),
+            render_size: self.render_size.or(fallback.render_size.clone()),
+        }
+    }
+
+    /// 把相对路径换成相对 `base` 的路径。
+    fn resolve_paths(&mut self, base: &Path) {
+        let resolve = |path: &mut PathBuf| {
+            if path.is_relative() {
+                *path = base.join(&*path);
+            }
+        };
+        self.transcribe_cache
+            .iter_mut()
+            .chain(self.translations.iter_mut())
+            .chain(self.media_path.iter_mut())
+            .chain(self.render_font.iter_mut())
+            .for_each(resolve);
+    }
+}
+
+/// 解析后的配置文件。
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProjectConfig {
+    /// 不指定预设时使用的值，也是所有预设的缺省值
+    pub default: Preset,
+
+    /// 命名预设
+    pub preset: BTreeMap<String, Preset>,
+
+    /// 配置文件路径，用于错误信息
+    #[serde(skip)]
+    pub path: PathBuf,
+}
+
+impl ProjectConfig {
+    /// 解析配置文本，相对路径按 `base_dir` 解析。
+    pub fn parse(text: &str, base_dir: &Path) -> Result<ProjectConfig> {
+        let mut config: ProjectConfig = toml::from_str(text).map_err(|err| Error::Config {
+            path: PathBuf::new(),
+            message: err.message().to_string(),
+        })?;
+
+        config.default.resolve_paths(base_dir);
+        for preset in config.preset.values_mut() {
+            preset.resolve_paths(base_dir);
+        }
+        Ok(config)
+    }
+
+    /// 读取并解析配置文件。
+    pub fn load(path: &Path) -> Result<ProjectConfig> {
+        let text = fs::read_to_string(path)?;
+        let base_dir = path.parent().unwrap_or(Path::new(""));
+        let mut config = ProjectConfig::parse(&text, base_dir).map_err(|err| match err {
+            Error::Config { message, .. } => Error::Config {
+                path: path.to_path_buf(),
+                message,
+            },
+            other => other,
+        })?;
+        config.path = path.to_path_buf();
+        Ok(config)
+    }
+
+    /// 按顺序查找配置文件：当前目录，然后是用户配置目录下的 `edl2srt/`。
+    pub fn discover() -> Option<PathBuf> {
+        let local = PathBuf::from(CONFIG_FILE_NAME);
+        if local.is_file() {
+            return Some(local);
+        }
+
+        let user = dirs::config_dir()?.join("edl2srt").join(CONFIG_FILE_NAME);
+        user.is_file().then_some(user)
+    }
+
+    /// 读取配置文件中的预设。
+    ///
+    /// - 给出 `config_path` 时该文件必须存在
+    /// - 否则用 [`ProjectConfig::discover`] 查找，找不到时：指定了预设名则报错，没指定则视为空预设
+    pub fn load_preset(config_path: Option<&Path>, name: Option<&str>) -> Result<Preset> {
+        let path = match config_path
+            .map(Path::to_path_buf)
+            .or_else(ProjectConfig::discover)
+        {
+            Some(path) => path,
+            None if name.is_some() => {
+                return Err(Error::Config {
+                    path: PathBuf::new(),
+                    message: t!("cli.config_not_found", CONFIG_FILE_NAME),
+                });
+            }
+            None => return Ok(Preset::default()),
+        };
+
+        ProjectConfig::load(&path)?.preset(name)
+    }
+
+    /// 取出生效的预设：指定名字时为该预设叠加 `[default]`，否则就是 `[default]`。
+    pub fn preset(&self, name: Option<&str>) -> Result<Preset> {
+        let Some(name) = name else {
+            return Ok(self.default.clone());
+        };
+
+        match self.preset.get(name) {
+            Some(preset) => Ok(preset.clone().or(&self.default)),
+            None => {
+                let names: Vec<&str> = self.preset.keys().map(String::as_str).collect();
+                Err(Error::Config {
+                    path: self.path.clone(),
+                    message: if names.is_empty() {
+                        t!("config.no_presets", name)
+                    } else {
+                        t!(
+                            "config.unknown_preset",
+                            name,
+                            names.join(text("list.separator"))
+                        )
+                    },
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = r#"
+[default]
+encoding = "gbk"
+format = "vtt"
+
+[preset.netflix-zh]
+rate = 23.976
+format = "srt"
+text-source = "transcribe"
+media-path = ["VO", "/mnt/audio"]
+
+[preset.netflix-zh.cleanup]
+replace = [["_", " "]]
+collapse-spaces = true
+
+[preset.netflix-zh.qc]
+max-chars-per-line = 16
+min-duration-ms = 833
+"#;
+
+    #[test]
+    fn preset_falls_back_to_default() {
+        let config = ProjectConfig::parse(TEXT, Path::new("/proj")).unwrap();
+        let preset = config.preset(Some("netflix-zh")).unwrap();
+
+        assert_eq!(preset.fps, Some(23.976));
+        assert_eq!(preset.format.as_deref(), Some("srt"));
+        assert_eq!(preset.input_encoding.as_deref(), Some("gbk"));
+        assert_eq!(preset.text_source, Some(TextSource::Transcribe));
+        assert_eq!(
+            preset.media_path,
+            [PathBuf::from("/proj/VO"), PathBuf::from("/mnt/audio")]
+        );
+        assert!(preset.cleanup.unwrap().collapse_spaces);
+        assert_eq!(preset.qc.unwrap().max_chars_per_line, Some(16));
+
+        let default = config.preset(None).unwrap();
+        assert_eq!(default.format.as_deref(), Some("vtt"));
+        assert_eq!(default.fps, None);
+    }
+
+    #[test]
+    fn unknown_preset_lists_choices() {
+        let config = ProjectConfig::parse(TEXT, Path::new("")).unwrap();
+        let err = config.preset(Some("bbc")).unwrap_err().to_string();
+        assert!(err.contains("可选: netflix-zh"));
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let err = ProjectConfig::parse("[preset.x]\nfsp = 25\n", Path::new("")).unwrap_err();
+        assert!(err.to_string().contains("fsp"));
+    }
+}
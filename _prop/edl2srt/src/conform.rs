@@ -0,0 +1,361 @@
+//! 套底（conform）：把按旧 EDL 做好的译文字幕，迁移到改剪后的新 EDL 上。
+//!
+//! 步骤：
+//! 1. 把现有字幕逐条对应到旧 EDL 中录制范围包含它的事件，一个事件可以有多条字幕
+//! 2. 用“片段名 + 源入点”在新 EDL 中找到同一个片段；找不到时退而只按片段名找
+//! 3. 找到的字幕按它在源素材中的位置移到新事件上，保留各自的入出点，超出新事件的部分截掉；
+//!    找不到片段或整条落在被修剪掉的部分里的，视为已被剪掉
+//! 4. 新 EDL 中没有被任何旧字幕占用的事件，列为需要补翻译的新片段
+
+use std::collections::HashMap;
+
+use crate::model::{Cue, Edl, EdlEvent, Timecode};
+use crate::transform::event_text;
+
+/// 被剪掉的字幕。
+#[derive(Debug, Clone, PartialEq)]
+pub struct DroppedCue {
+    pub cue: Cue,
+
+    /// 旧 EDL 中对应事件的片段名
+    pub clip_name: Option<String>,
+}
+
+/// 套底结果。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConformResult {
+    /// 迁移后的字幕，按开始时间排序
+    pub cues: Vec<Cue>,
+
+    /// 位置发生变化的字幕数量
+    pub moved: usize,
+
+    /// 位置没有变化的字幕数量
+    pub unchanged: usize,
+
+    /// 片段已被剪掉的字幕
+    pub dropped: Vec<DroppedCue>,
+
+    /// 在旧 EDL 中找不到对应事件的字幕（原样丢弃）
+    pub unmatched: Vec<Cue>,
+
+    /// 新 EDL 中需要补翻译的事件
+    pub needs_translation: Vec<EdlEvent>,
+}
+
+/// 执行套底。
+///
+/// `cues` 是按旧 EDL 生成、已经翻译好的字幕；时间按 `fps` 与旧 EDL 对齐。
+/// 字幕入点落在旧事件的录制范围内（入点前允许半帧误差）就算属于这个事件，
+/// 同一片段里拆开或改过时间的字幕都能跟着片段移动。
+pub fn conform(old: &Edl, new: &Edl, cues: &[Cue], fps: f64) -> ConformResult {
+    let tolerance = (500.0 / fps).ceil() as u64;
+
+    let old_events: Vec<&EdlEvent> = old
+        .events
+        .iter()
+        .filter(|e| event_text(e).is_some())
+        .collect();
+    let new_events: Vec<&EdlEvent> = new
+        .events
+        .iter()
+        .filter(|e| event_text(e).is_some())
+        .collect();
+
+    // 旧事件按录制入点排序，便于按时间查找
+    let mut old_by_time: Vec<(u64, usize)> = old_events
+        .iter()
+        .enumerate()
+        .map(|(i, e)| (e.record_in.to_millis(fps), i))
+        .collect();
+    old_by_time.sort();
+
+    let mut by_key: HashMap<(String, Option<Timecode>), Vec<usize>> = HashMap::new();
+    let mut by_name: HashMap<String, Vec<usize>> = HashMap::new();
+    for (i, event) in new_events.iter().enumerate() {
+        let name = match_name(event);
+        by_key
+            .entry((name.clone(), event.source_in))
+            .or_default()
+            .push(i);
+        by_name.entry(name).or_default().push(i);
+    }
+
+    // 每个旧事件只找一次新事件，同一事件的多条字幕跟着同一个新事件走
+    let mut targets: Vec<Option<Option<usize>>> = vec![None; old_events.len()];
+    let mut new_used = vec![false; new_events.len()];
+    let mut result = ConformResult::default();
+
+    for cue in cues {
+        // 入点不晚于字幕入点、出点晚于字幕入点的事件中，取入点最晚的一个
+        let end = old_by_time.partition_point(|&(ms, _)| ms <= cue.start_ms + tolerance);
+        let old_index = old_by_time[..end]
+            .iter()
+            .rev()
+            .map(|&(_, i)| i)
+            .find(|&i| old_events[i].record_out.to_millis(fps) > cue.start_ms);
+
+        let Some(old_index) = old_index else {
+            result.unmatched.push(cue.clone());
+            continue;
+        };
+
+        let old_event = old_events[old_index];
+        let target = *targets[old_index].get_or_insert_with(|| {
+            let name = match_name(old_event);
+            let key = (name.clone(), old_event.source_in);
+            let found = by_key
+                .get(&key)
+                .and_then(|list| list.iter().copied().find(|&i| !new_used[i]))
+                .or_else(|| {
+                    by_name
+                        .get(&name)
+                        .and_then(|list| list.iter().copied().find(|&i| !new_used[i]))
+                });
+            if let Some(i) = found {
+                new_used[i] = true;
+            }
+            found
+        });
+
+        let moved = target.and_then(|i| move_cue(cue, old_event, new_events[i], fps));
+        match moved {
+            Some(moved) => {
+                if moved.start_ms == cue.start_ms && moved.end_ms == cue.end_ms {
+                    result.unchanged += 1;
+                } else {
+                    result.moved += 1;
+                }
+                result.cues.push(moved);
+            }
+            None => result.dropped.push(DroppedCue {
+                cue: cue.clone(),
+                clip_name: old_event.clip_name.clone(),
+            }),
+        }
+    }
+
+    result.needs_translation = new_events
+        .iter()
+        .zip(&new_used)
+        .filter(|(_, used)| !**used)
+        .map(|(e, _)| (*e).clone())
+        .collect();
+
+    result.cues.sort_by_key(|c| (c.start_ms, c.end_ms));
+    result
+}
+
+/// 按字幕在源素材中的位置，把它从旧事件移到新事件上。
+///
+/// 相对旧事件的偏移和时长保持不变，再按两个事件源入点的差值平移；
+/// 超出新事件录制范围的部分截掉，整条都被截掉时返回 None。
+fn move_cue(cue: &Cue, old: &EdlEvent, new: &EdlEvent, fps: f64) -> Option<Cue> {
+    let old_in = old.record_in.to_millis(fps) as i64;
+    let new_in = new.record_in.to_millis(fps) as i64;
+    let new_out = new.record_out.to_millis(fps) as i64;
+
+    // 源入点不同（片段被修剪过）时，同一句话在新事件里的位置随之前后移动
+    let trimmed = match (old.source_in, new.source_in) {
+        (Some(old_src), Some(new_src)) => {
+            old_src.to_millis(fps) as i64 - new_src.to_millis(fps) as i64
+        }
+        _ => 0,
+    };
+
+    let shift = new_in - old_in + trimmed;
+    let start = (cue.start_ms as i64 + shift).clamp(new_in, new_out);
+    let end = (cue.end_ms as i64 + shift).clamp(new_in, new_out);
+    (start < end).then(|| Cue {
+        start_ms: start as u64,
+        end_ms: end as u64,
+        text: cue.text.clone(),
+    })
+}
+
+/// 用于匹配的片段名：优先片段名，其次字幕文本。
+pub(crate) fn match_name(event: &EdlEvent) -> String {
+    event
+        .clip_name
+        .clone()
+        .or_else(|| event_text(event))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::parse_edl;
+    use crate::transform::{entries_to_cues, events_to_entries};
+
+    const OLD: &str = "\
+001 AX A C 00:00:10:00 00:00:12:00 01:00:00:00 01:00:02:00
+* FROM CLIP NAME: VO_001.wav
+002 AX A C 00:00:20:00 00:00:23:00 01:00:02:00 01:00:05:00
+* FROM CLIP NAME: VO_002.wav
+003 AX A C 00:00:30:00 00:00:31:00 01:00:05:00 01:00:06:00
+* FROM CLIP NAME: VO_003.wav
+";
+
+    // 002 被剪掉，003 前移，新增 004
+    const NEW: &str = "\
+001 AX A C 00:00:10:00 00:00:12:00 01:00:00:00 01:00:02:00
+* FROM CLIP NAME: VO_001.wav
+002 AX A C 00:00:30:00 00:00:31:00 01:00:02:00 01:00:03:00
+* FROM CLIP NAME: VO_003.wav
+003 AX A C 00:00:40:00 00:00:41:00 01:00:03:00 01:00:04:00
+* FROM CLIP NAME: VO_004.wav
+";
+
+    fn translated(old: &Edl, fps: f64) -> Vec<Cue> {
+        let mut cues = entries_to_cues(&events_to_entries(&old.events), fps);
+        for (i, cue) in cues.iter_mut().enumerate() {
+            cue.text = format!("译文 {}", i + 1);
+        }
+        cues
+    }
+
+    #[test]
+    fn conform_moves_drops_and_lists_new_clips() {
+        let old = parse_edl(OLD);
+        let new = parse_edl(NEW);
+        let cues = translated(&old, 25.0);
+
+        let result = conform(&old, &new, &cues, 25.0);
+
+        assert_eq!(result.unchanged, 1);
+        assert_eq!(result.moved, 1);
+        assert_eq!(
+            result.cues,
+            vec![
+                Cue {
+                    start_ms: 3_600_000,
+                    end_ms: 3_602_000,
+                    text: "译文 1".to_string()
+                },
+                Cue {
+                    start_ms: 3_602_000,
+                    end_ms: 3_603_000,
+                    text: "译文 3".to_string()
+                },
+            ]
+        );
+
+        assert_eq!(result.dropped.len(), 1);
+        assert_eq!(result.dropped[0].clip_name.as_deref(), Some("VO_002.wav"));
+        assert_eq!(result.needs_translation.len(), 1);
+        assert_eq!(
+            result.needs_translation[0].clip_name.as_deref(),
+            Some("VO_004.wav")
+        );
+        assert!(result.unmatched.is_empty());
+    }
+
+    #[test]
+    fn trimmed_clip_falls_back_to_clip_name() {
+        let old = parse_edl(OLD);
+        // VO_001 的源入点被修剪过
+        let new = parse_edl(
+            "001 AX A C 00:00:10:12 00:00:12:00 01:00:00:00 01:00:01:12\n\
+             * FROM CLIP NAME: VO_001.wav\n",
+        );
+        let cues = translated(&old, 25.0);
+
+        let result = conform(&old, &new, &cues, 25.0);
+        assert_eq!(result.cues.len(), 1);
+        assert_eq!(result.cues[0].end_ms, 3_601_480);
+        assert_eq!(result.dropped.len(), 2);
+    }
+
+    #[test]
+    fn split_and_retimed_cues_keep_their_timing() {
+        let old = parse_edl(OLD);
+        let new = parse_edl(NEW);
+        // VO_003 一条拆成两条，译者还把第二条往后挪了一点
+        let cues = vec![
+            Cue {
+                start_ms: 3_605_000,
+                end_ms: 3_605_400,
+                text: "前半句".to_string(),
+            },
+            Cue {
+                start_ms: 3_605_500,
+                end_ms: 3_606_000,
+                text: "后半句".to_string(),
+            },
+        ];
+
+        let result = conform(&old, &new, &cues, 25.0);
+        assert!(result.unmatched.is_empty());
+        assert_eq!(result.moved, 2);
+        assert_eq!(
+            result.cues,
+            vec![
+                Cue {
+                    start_ms: 3_602_000,
+                    end_ms: 3_602_400,
+                    text: "前半句".to_string()
+                },
+                Cue {
+                    start_ms: 3_602_500,
+                    end_ms: 3_603_000,
+                    text: "后半句".to_string()
+                },
+            ]
+        );
+        // 两条字幕同属一个片段，新 EDL 里只占用 VO_003 一个事件
+        let rest: Vec<_> = result
+            .needs_translation
+            .iter()
+            .filter_map(|e| e.clip_name.as_deref())
+            .collect();
+        assert_eq!(rest, ["VO_001.wav", "VO_004.wav"]);
+    }
+
+    #[test]
+    fn cues_in_trimmed_away_part_are_dropped() {
+        let old = parse_edl(OLD);
+        // VO_002 的前 2 秒被修剪掉
+        let new = parse_edl(
+            "001 AX A C 00:00:22:00 00:00:23:00 01:00:00:00 01:00:01:00\n\
+             * FROM CLIP NAME: VO_002.wav\n",
+        );
+        let cues = vec![
+            Cue {
+                start_ms: 3_602_000,
+                end_ms: 3_603_000,
+                text: "剪掉了".to_string(),
+            },
+            Cue {
+                start_ms: 3_604_000,
+                end_ms: 3_605_000,
+                text: "留下".to_string(),
+            },
+        ];
+
+        let result = conform(&old, &new, &cues, 25.0);
+        assert_eq!(result.dropped.len(), 1);
+        assert_eq!(result.dropped[0].cue.text, "剪掉了");
+        assert_eq!(
+            result.cues,
+            vec![Cue {
+                start_ms: 3_600_000,
+                end_ms: 3_601_000,
+                text: "留下".to_string()
+            }]
+        );
+    }
+
+    #[test]
+    fn cues_not_in_old_edl_are_unmatched() {
+        let old = parse_edl(OLD);
+        let cues = vec![Cue {
+            start_ms: 1,
+            end_ms: 2,
+            text: "stray".to_string(),
+        }];
+        let result = conform(&old, &old, &cues, 25.0);
+        assert_eq!(result.unmatched.len(), 1);
+        assert_eq!(result.needs_translation.len(), 3);
+    }
+}
@@ -0,0 +1,435 @@
+//! 两个版本 EDL 之间的字幕变更列表。
+//!
+//! 对应规则（依次尝试）：
+//! 1. 片段名 + 源入点都相同：视为同一条字幕，比较时间和文本
+//! 2. 录制入 / 出点完全相同：视为同一位置的字幕，比较文本
+//! 3. 仅片段名相同：视为同一条字幕被移动或修剪
+//!
+//! 剩下的旧字幕为“删除”，新字幕为“新增”。
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::io::{self, Write};
+
+use crate::conform::match_name;
+use crate::error::Result;
+use crate::fps::{infer_fps, nominal_rate};
+use crate::i18n::text;
+use crate::model::{Edl, EdlEvent, Timecode};
+use crate::t;
+use crate::transform::event_text;
+
+/// 变更类型。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Retimed,
+    Retexted,
+    RetimedAndRetexted,
+}
+
+impl ChangeKind {
+    /// 表格中显示的名称。
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeKind::Added => text("diff.added"),
+            ChangeKind::Removed => text("diff.removed"),
+            ChangeKind::Retimed => text("diff.retimed"),
+            ChangeKind::Retexted => text("diff.retexted"),
+            ChangeKind::RetimedAndRetexted => text("diff.retimed_and_retexted"),
+        }
+    }
+}
+
+/// 变更中某一侧（旧或新）的字幕。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffSide {
+    /// 事件编号
+    pub number: String,
+    pub record_in: Timecode,
+    pub record_out: Timecode,
+    pub text: String,
+
+    /// 在该版本 EDL 中的序号（从 0 开始），用于回查事件
+    #[serde(skip)]
+    pub index: usize,
+}
+
+/// 一条变更。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Change {
+    pub kind: ChangeKind,
+    pub old: Option<DiffSide>,
+    pub new: Option<DiffSide>,
+
+    /// 入点变化的帧数（新 - 旧），新增 / 删除时为 0
+    pub in_delta_frames: i64,
+
+    /// 出点变化的帧数（新 - 旧）
+    pub out_delta_frames: i64,
+}
+
+/// 差异报告。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DiffReport {
+    /// 计算帧差时使用的名义帧率
+    pub nominal_rate: u32,
+
+    /// 没有变化的字幕数量
+    pub unchanged: usize,
+
+    /// 按新版本时间排序的变更（删除的按旧版本时间插在相应位置）
+    pub changes: Vec<Change>,
+}
+
+impl DiffReport {
+    /// 某类变更的数量。
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+
+    /// 一行摘要。
+    pub fn summary(&self) -> String {
+        t!(
+            "diff.summary",
+            self.count(ChangeKind::Added),
+            self.count(ChangeKind::Removed),
+            self.count(ChangeKind::Retimed),
+            self.count(ChangeKind::Retexted),
+            self.count(ChangeKind::RetimedAndRetexted),
+            self.unchanged
+        )
+    }
+}
+
+/// 配对事件时用的键，按顺序尝试。
+#[derive(PartialEq, Eq, Hash)]
+enum MatchKey {
+    /// 片段名 + 源入点
+    Source(String, Option<Timecode>),
+    /// 录制入出点
+    Record(Timecode, Timecode),
+    /// 只看片段名
+    Name(String),
+}
+
+/// 比较时使用的名义帧率：给出 `fps` 时直接换算，否则综合两个版本推断。
+///
+/// 帧差只依赖名义帧率，所以 23.976 / 24 这类歧义不影响结果，
+/// 只有候选的名义帧率也不一致时才返回错误。
+pub fn diff_rate(old: &Edl, new: &Edl, fps: Option<f64>) -> Result<u32> {
+    if let Some(fps) = fps {
+        return Ok(nominal_rate(fps));
+    }
+
+    let mut combined = old.clone();
+    combined.events.extend(new.events.iter().cloned());
+    let inference = infer_fps(&combined);
+
+    let rates: Vec<u32> = inference
+        .candidates
+        .iter()
+        .map(|&fps| nominal_rate(fps))
+        .collect();
+    match rates.first() {
+        Some(&rate) if rates.iter().all(|&r| r == rate) => Ok(rate),
+        _ => inference.resolve().map(nominal_rate),
+    }
+}
+
+/// 比较两个版本的 EDL。
+///
+/// `nominal_rate` 为名义帧率（例如 23.976 对应 24），只用于计算帧差。
+pub fn diff_edls(old: &Edl, new: &Edl, nominal_rate: u32) -> DiffReport {
+    let old_subs = subtitle_sides(old);
+    let new_subs = subtitle_sides(new);
+
+    let mut old_used = vec![false; old_subs.len()];
+    let mut new_used = vec![false; new_subs.len()];
+    let mut pairs: Vec<(usize, usize)> = Vec::new();
+
+    type Key<'a> = Box<dyn Fn(&EdlEvent, &DiffSide) -> MatchKey + 'a>;
+    let strategies: [Key; 3] = [
+        Box::new(|e, _| MatchKey::Source(match_name(e), e.source_in)),
+        Box::new(|_, s| MatchKey::Record(s.record_in, s.record_out)),
+        Box::new(|e, _| MatchKey::Name(match_name(e))),
+    ];
+
+    for key in &strategies {
+        let mut index: HashMap<MatchKey, Vec<usize>> = HashMap::new();
+        for (i, (event, side)) in new_subs.iter().enumerate() {
+            if !new_used[i] {
+                index.entry(key(event, side)).or_default().push(i);
+            }
+        }
+
+        for (i, (event, side)) in old_subs.iter().enumerate() {
+            if old_used[i] {
+                continue;
+            }
+            let found = index
+                .get(&key(event, side))
+                .and_then(|list| list.iter().copied().find(|&j| !new_used[j]));
+            if let Some(j) = found {
+                old_used[i] = true;
+                new_used[j] = true;
+                pairs.push((i, j));
+            }
+        }
+    }
+
+    let rate = nominal_rate as i64;
+    let frames = |tc: Timecode| tc.to_frames(rate as u32) as i64;
+
+    let mut changes = Vec::new();
+    let mut unchanged = 0;
+
+    for (i, j) in pairs {
+        let (old_side, new_side) = (&old_subs[i].1, &new_subs[j].1);
+        let in_delta = frames(new_side.record_in) - frames(old_side.record_in);
+        let out_delta = frames(new_side.record_out) - frames(old_side.record_out);
+        let retimed = in_delta != 0 || out_delta != 0;
+        let retexted = old_side.text != new_side.text;
+
+        let kind = match (retimed, retexted) {
+            (false, false) => {
+                unchanged += 1;
+                continue;
+            }
+            (true, false) => ChangeKind::Retimed,
+            (false, true) => ChangeKind::Retexted,
+            (true, true) => ChangeKind::RetimedAndRetexted,
+        };
+
+        changes.push(Change {
+            kind,
+            old: Some(old_side.clone()),
+            new: Some(new_side.clone()),
+            in_delta_frames: in_delta,
+            out_delta_frames: out_delta,
+        });
+    }
+
+    for (i, (_, side)) in old_subs.iter().enumerate() {
+        if !old_used[i] {
+            changes.push(Change {
+                kind: ChangeKind::Removed,
+                old: Some(side.clone()),
+                new: None,
+                in_delta_frames: 0,
+                out_delta_frames: 0,
+            });
+        }
+    }
+
+    for (j, (_, side)) in new_subs.iter().enumerate() {
+        if !new_used[j] {
+            changes.push(Change {
+                kind: ChangeKind::Added,
+                old: None,
+                new: Some(side.clone()),
+                in_delta_frames: 0,
+                out_delta_frames: 0,
+            });
+        }
+    }
+
+    changes.sort_by_key(|c| {
+        let side = c.new.as_ref().or(c.old.as_ref()).expect("至少有一侧");
+        (side.record_in, side.record_out)
+    });
+
+    DiffReport {
+        nominal_rate,
+        unchanged,
+        changes,
+    }
+}
+
+/// 以表格形式写出报告。
+pub fn write_diff_table<W: Write>(mut out: W, report: &DiffReport) -> io::Result<()> {
+    writeln!(
+        out,
+        "{:<12} {:<9} {:<25} {:<25} {:>6} {:>6}  {}",
+        text("diff.column_change"),
+        text("diff.column_event"),
+        text("diff.column_old_time"),
+        text("diff.column_new_time"),
+        text("diff.column_in_delta"),
+        text("diff.column_out_delta"),
+        text("diff.column_text")
+    )?;
+
+    for change in &report.changes {
+        let number = |side: &Option<DiffSide>| {
+            side.as_ref()
+                .map_or_else(|| "-".to_string(), |s| s.number.clone())
+        };
+        let range = |side: &Option<DiffSide>| {
+            side.as_ref().map_or_else(
+                || "-".to_string(),
+                |s| format!("{}-{}", s.record_in, s.record_out),
+            )
+        };
+        let text = match (&change.old, &change.new) {
+            (Some(old), Some(new)) if old.text != new.text => {
+                format!("{} → {}", one_line(&old.text), one_line(&new.text))
+            }
+            (_, Some(side)) | (Some(side), None) => one_line(&side.text),
+            (None, None) => String::new(),
+        };
+
+        writeln!(
+            out,
+            "{:<12} {:<9} {:<25} {:<25} {:>+6} {:>+6}  {}",
+            change.kind.label(),
+            format!("{}→{}", number(&change.old), number(&change.new)),
+            range(&change.old),
+            range(&change.new),
+            change.in_delta_frames,
+            change.out_delta_frames,
+            text
+        )?;
+    }
+
+    writeln!(out)?;
+    writeln!(out, "{}", report.summary())?;
+    out.flush()
+}
+
+/// 只包含变更事件的 EDL：新增和修改的事件取自新版本，删除的事件不写出。
+pub fn change_list_edl(new: &Edl, report: &DiffReport) -> Edl {
+    let mut indices: Vec<usize> = report
+        .changes
+        .iter()
+        .filter(|c| c.kind != ChangeKind::Removed)
+        .filter_map(|c| c.new.as_ref().map(|s| s.index))
+        .collect();
+    indices.sort_unstable();
+
+    Edl {
+        title: new.title.as_ref().map(|t| format!("{} (CHANGES)", t)),
+        fcm: new.fcm.clone(),
+        frame_rate: new.frame_rate,
+        events: indices.iter().map(|&i| new.events[i].clone()).collect(),
+        warnings: Vec::new(),
+    }
+}
+
+/// 生成字幕的事件，以及它们在比较中的样子。
+fn subtitle_sides(edl: &Edl) -> Vec<(&EdlEvent, DiffSide)> {
+    edl.events
+        .iter()
+        .enumerate()
+        .filter_map(|(index, event)| {
+            let text = event_text(event)?;
+            Some((
+                event,
+                DiffSide {
+                    number: event.number.clone(),
+                    record_in: event.record_in,
+                    record_out: event.record_out,
+                    text,
+                    index,
+                },
+            ))
+        })
+        .collect()
+}
+
+fn one_line(text: &str) -> String {
+    text.replace('\n', " / ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::parse_edl;
+
+    const V3: &str = "\
+001 AX A C 00:00:10:00 00:00:12:00 01:00:00:00 01:00:02:00
+* FROM CLIP NAME: 你好.wav
+002 AX A C 00:00:20:00 00:00:23:00 01:00:02:00 01:00:05:00
+* FROM CLIP NAME: 再见.wav
+003 AX A C 00:00:30:00 00:00:31:00 01:00:05:00 01:00:06:00
+* FROM CLIP NAME: 谢谢.wav
+";
+
+    // 再见 换成了新台词，谢谢 后移，新增 结尾
+    const V4: &str = "\
+001 AX A C 00:00:10:00 00:00:12:00 01:00:00:00 01:00:02:00
+* FROM CLIP NAME: 你好.wav
+002 AX A C 00:00:40:00 00:00:43:00 01:00:02:00 01:00:05:00
+* FROM CLIP NAME: 新台词.wav
+003 AX A C 00:00:30:00 00:00:31:00 01:00:06:12 01:00:07:12
+* FROM CLIP NAME: 谢谢.wav
+004 AX A C 00:00:50:00 00:00:51:00 01:00:08:00 01:00:09:00
+* FROM CLIP NAME: 结尾.wav
+";
+
+    #[test]
+    fn detects_each_kind_of_change() {
+        let report = diff_edls(&parse_edl(V3), &parse_edl(V4), 25);
+
+        assert_eq!(report.unchanged, 1);
+        assert_eq!(report.count(ChangeKind::Retexted), 1);
+        assert_eq!(report.count(ChangeKind::Retimed), 1);
+        assert_eq!(report.count(ChangeKind::Added), 1);
+        assert_eq!(report.count(ChangeKind::Removed), 0);
+
+        let kinds: Vec<ChangeKind> = report.changes.iter().map(|c| c.kind).collect();
+        assert_eq!(
+            kinds,
+            [ChangeKind::Retexted, ChangeKind::Retimed, ChangeKind::Added]
+        );
+
+        // 同一位置换了片段，视为改文本
+        let retexted = &report.changes[0];
+        assert_eq!(retexted.old.as_ref().unwrap().text, "再见");
+        assert_eq!(retexted.new.as_ref().unwrap().text, "新台词");
+
+        // 01:00:05:00 -> 01:00:06:12 = 1 秒 12 帧
+        let retimed = &report.changes[1];
+        assert_eq!(retimed.in_delta_frames, 37);
+        assert_eq!(retimed.out_delta_frames, 37);
+    }
+
+    #[test]
+    fn added_and_removed_when_nothing_lines_up() {
+        let old = parse_edl(
+            "001 AX A C 00:00:00:00 00:00:01:00 00:00:00:00 00:00:01:00\n* FROM CLIP NAME: a\n",
+        );
+        let new = parse_edl(
+            "001 AX A C 00:00:00:00 00:00:01:00 00:00:05:00 00:00:06:00\n* FROM CLIP NAME: b\n",
+        );
+        let report = diff_edls(&old, &new, 24);
+
+        let kinds: Vec<ChangeKind> = report.changes.iter().map(|c| c.kind).collect();
+        assert_eq!(kinds, vec![ChangeKind::Removed, ChangeKind::Added]);
+    }
+
+    #[test]
+    fn table_json_and_change_list() {
+        let new = parse_edl(V4);
+        let report = diff_edls(&parse_edl(V3), &new, 25);
+
+        let mut table = Vec::new();
+        write_diff_table(&mut table, &report).unwrap();
+        let table = String::from_utf8(table).unwrap();
+        assert!(table.contains("改时间"));
+        assert!(table.contains("再见 → 新台词"));
+        assert!(table.contains("+37"));
+        assert!(table.contains(&report.summary()));
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["changes"][1]["kind"], "retimed");
+        assert_eq!(json["changes"][1]["in_delta_frames"], 37);
+        assert_eq!(json["changes"][1]["old"]["record_in"], "01:00:05:00");
+
+        let changes = change_list_edl(&new, &report);
+        let numbers: Vec<&str> = changes.events.iter().map(|e| e.number.as_str()).collect();
+        assert_eq!(numbers, ["002", "003", "004"]);
+    }
+}
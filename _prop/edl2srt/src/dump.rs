@@ -0,0 +1,240 @@
+//! JSON 中间表示：导出解析后的完整事件列表，以及从 JSON 读回。
+//!
+//! 导出的 JSON 可以用 jq 或脚本处理后再作为输入，重新走各个字幕写出器。
+//! 每个事件的 `text` 字段会填入最终的字幕文本，修改它即可改字幕；
+//! `converted` 字段只是方便查看的换算结果，导入时会被忽略。
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+
+use crate::error::{Error, Result};
+use crate::fps::is_valid_fps;
+use crate::model::{Edl, EdlEvent, Warning};
+use crate::t;
+use crate::transform::event_text;
+use crate::writer::format_srt_timestamp;
+
+/// 当前 JSON 格式的版本号。
+pub const DUMP_VERSION: u32 = 1;
+
+/// `--dump-json` 输出的顶层结构。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EdlDump {
+    #[serde(default = "default_version")]
+    pub version: u32,
+
+    /// 换算时使用的帧率
+    #[serde(default)]
+    pub fps: Option<f64>,
+
+    /// 输入文件的编码
+    #[serde(default)]
+    pub encoding: Option<String>,
+
+    #[serde(default)]
+    pub title: Option<String>,
+
+    #[serde(default)]
+    pub fcm: Option<String>,
+
+    pub events: Vec<DumpEvent>,
+
+    #[serde(default)]
+    pub warnings: Vec<Warning>,
+}
+
+/// 一个事件：原始解析结果 + 换算后的时间。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DumpEvent {
+    #[serde(flatten)]
+    pub event: EdlEvent,
+
+    /// 按帧率换算后的时间，只在导出时填写
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub converted: Option<ConvertedTimes>,
+
+    /// 变速片段的播放速度（百分比），只在导出且已知帧率时填写
+    #[serde(default, skip_deserializing, skip_serializing_if = "Option::is_none")]
+    pub speed_percent: Option<f64>,
+}
+
+/// 录制入 / 出点换算后的结果。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConvertedTimes {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub duration_ms: i64,
+
+    /// SRT 格式的开始时间，例如 "01:00:00,500"
+    pub start: String,
+
+    /// SRT 格式的结束时间
+    pub end: String,
+}
+
+fn default_version() -> u32 {
+    DUMP_VERSION
+}
+
+impl EdlDump {
+    /// 由解析结果构造导出结构。
+    ///
+    /// 提供帧率时，每个事件会附带换算后的时间。
+    pub fn new(edl: &Edl, fps: Option<f64>, encoding: Option<&str>) -> Self {
+        let events = edl
+            .events
+            .iter()
+            .map(|event| {
+                let mut event = event.clone();
+                event.text = event_text(&event);
+
+                let converted = fps.map(|fps| {
+                    let start_ms = event.record_in.to_millis(fps);
+                    let end_ms = event.record_out.to_millis(fps);
+                    ConvertedTimes {
+                        start_ms,
+                        end_ms,
+                        duration_ms: end_ms as i64 - start_ms as i64,
+                        start: format_srt_timestamp(start_ms),
+                        end: format_srt_timestamp(end_ms),
+                    }
+                });
+
+                let speed_percent = fps.and_then(|fps| event.speed_percent(fps));
+                DumpEvent {
+                    event,
+                    converted,
+                    speed_percent,
+                }
+            })
+            .collect();
+
+        EdlDump {
+            version: DUMP_VERSION,
+            fps,
+            encoding: encoding.map(str::to_string),
+            title: edl.title.clone(),
+            fcm: edl.fcm.clone(),
+            events,
+            warnings: edl.warnings.clone(),
+        }
+    }
+
+    /// 还原为 [`Edl`]。
+    pub fn into_edl(self) -> Edl {
+        Edl {
+            title: self.title,
+            fcm: self.fcm,
+            frame_rate: self.fps,
+            events: self.events.into_iter().map(|e| e.event).collect(),
+            warnings: self.warnings,
+        }
+    }
+}
+
+/// 以带缩进的 JSON 写出。
+pub fn write_dump_json<W: Write>(mut out: W, dump: &EdlDump) -> Result<()> {
+    serde_json::to_writer_pretty(&mut out, dump).map_err(Error::from)?;
+    writeln!(out)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// 从 JSON 文本读回 [`Edl`]。
+pub fn parse_dump_json(text: &str) -> Result<Edl> {
+    let dump: EdlDump = serde_json::from_str(text)?;
+    if dump.version > DUMP_VERSION {
+        return Err(Error::Parse {
+            line: 1,
+            message: t!("dump.unsupported_version", dump.version),
+        });
+    }
+    if let Some(fps) = dump.fps
+        && !is_valid_fps(fps)
+    {
+        return Err(Error::Parse {
+            line: 1,
+            message: t!("dump.bad_fps", fps),
+        });
+    }
+    Ok(dump.into_edl())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::parse_edl;
+    use crate::transform::events_to_entries;
+
+    const SAMPLE: &str = "TITLE: demo
+001  AX  A  C  00:00:00:00 00:00:01:12 01:00:00:00 01:00:01:12
+* FROM CLIP NAME: hello.wav
+* orphan? no, belongs to 001
+002  AX  A  C  bad line
+";
+
+    #[test]
+    fn dump_contains_converted_times_and_warnings() {
+        let edl = parse_edl(SAMPLE);
+        let dump = EdlDump::new(&edl, Some(24.0), Some("UTF-8"));
+
+        let mut out = Vec::new();
+        write_dump_json(&mut out, &dump).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+        let event = &value["events"][0];
+        assert_eq!(event["number"], "001");
+        assert_eq!(event["record_in"], "01:00:00:00");
+        assert_eq!(event["text"], "hello");
+        assert_eq!(event["converted"]["end"], "01:00:01,500");
+        assert_eq!(event["converted"]["duration_ms"], 1500);
+        assert_eq!(event["source_lines"].as_array().unwrap().len(), 3);
+        assert_eq!(value["warnings"][0]["line"], 5);
+        assert_eq!(value["encoding"], "UTF-8");
+    }
+
+    #[test]
+    fn dump_round_trip_preserves_entries() {
+        let edl = parse_edl(SAMPLE);
+        let mut out = Vec::new();
+        write_dump_json(&mut out, &EdlDump::new(&edl, Some(24.0), None)).unwrap();
+
+        let back = parse_dump_json(std::str::from_utf8(&out).unwrap()).unwrap();
+        assert_eq!(back.title, edl.title);
+        assert_eq!(
+            events_to_entries(&back.events),
+            events_to_entries(&edl.events)
+        );
+    }
+
+    #[test]
+    fn edited_text_overrides_clip_name() {
+        let json = r#"{
+            "events": [
+                {"record_in": "00:00:01:00", "record_out": "00:00:02:00",
+                 "clip_name": "VO_001.wav", "text": "改过的字幕"}
+            ]
+        }"#;
+        let edl = parse_dump_json(json).unwrap();
+        let entries = events_to_entries(&edl.events);
+        assert_eq!(entries[0].text, "改过的字幕");
+    }
+
+    #[test]
+    fn rejects_unusable_fps() {
+        for fps in ["0", "-25"] {
+            let json = format!(r#"{{"fps": {}, "events": []}}"#, fps);
+            assert!(matches!(parse_dump_json(&json), Err(Error::Parse { .. })));
+        }
+        assert!(parse_dump_json(r#"{"fps": 25, "events": []}"#).is_ok());
+    }
+
+    #[test]
+    fn invalid_json_is_an_error() {
+        assert!(matches!(parse_dump_json("{"), Err(Error::Json(_))));
+        assert!(matches!(
+            parse_dump_json(r#"{"events": [{"record_in": "x", "record_out": "y"}]}"#),
+            Err(Error::Json(_))
+        ));
+    }
+}
@@ -0,0 +1,283 @@
+//! 写出前的字幕编辑：`--edit` 终端界面背后的数据模型。
+//!
+//! 界面只负责显示和按键，所有修改都经过 [`CueEditor`]，
+//! 每次修改前保存一份快照，可以逐步撤销。
+//! 时间按帧平移：先换算成时间码，平移后再换回毫秒，与 EDL 的帧边界保持一致。
+
+use crate::fps::nominal_rate;
+use crate::model::{Cue, Timecode};
+use crate::qc::{QcLimits, chars_per_second, check_cues};
+use crate::t;
+
+/// 平移哪一端。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Edge {
+    /// 入点
+    Start,
+
+    /// 出点
+    End,
+
+    /// 入点和出点一起平移
+    Both,
+}
+
+/// 编辑表中的一行。
+#[derive(Debug, Clone, PartialEq)]
+pub struct CueRow {
+    /// 序号（从 1 开始）
+    pub index: usize,
+
+    pub start: Timecode,
+    pub end: Timecode,
+    pub duration_ms: u64,
+
+    /// 阅读速度，时长为 0 时为 None
+    pub cps: Option<f64>,
+
+    pub text: String,
+    pub warnings: Vec<String>,
+}
+
+/// 可撤销的字幕编辑器。
+#[derive(Debug, Clone)]
+pub struct CueEditor {
+    cues: Vec<Cue>,
+    fps: f64,
+    limits: QcLimits,
+    history: Vec<Vec<Cue>>,
+}
+
+impl CueEditor {
+    /// `limits` 用于每行的质检提示，没有限制时传 `QcLimits::default()`。
+    pub fn new(cues: Vec<Cue>, fps: f64, limits: QcLimits) -> Self {
+        CueEditor {
+            cues,
+            fps,
+            limits,
+            history: Vec::new(),
+        }
+    }
+
+    pub fn cues(&self) -> &[Cue] {
+        &self.cues
+    }
+
+    pub fn into_cues(self) -> Vec<Cue> {
+        self.cues
+    }
+
+    pub fn len(&self) -> usize {
+        self.cues.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cues.is_empty()
+    }
+
+    /// 是否有未撤销的修改
+    pub fn is_modified(&self) -> bool {
+        !self.history.is_empty()
+    }
+
+    /// 生成显示用的表格行。
+    pub fn rows(&self) -> Vec<CueRow> {
+        let mut warnings = vec![Vec::new(); self.cues.len()];
+        for issue in check_cues(&self.cues, &self.limits) {
+            warnings[issue.index - 1].push(issue.message);
+        }
+        for (i, pair) in self.cues.windows(2).enumerate() {
+            if pair[1].start_ms < pair[0].end_ms {
+                warnings[i + 1].push(t!("editor.overlap", i + 1));
+            }
+        }
+
+        self.cues
+            .iter()
+            .zip(warnings)
+            .enumerate()
+            .map(|(i, (cue, mut warnings))| {
+                if cue.end_ms <= cue.start_ms {
+                    warnings.insert(0, t!("editor.out_not_after_in"));
+                }
+                CueRow {
+                    index: i + 1,
+                    start: self.timecode(cue.start_ms),
+                    end: self.timecode(cue.end_ms),
+                    duration_ms: cue.end_ms.saturating_sub(cue.start_ms),
+                    cps: chars_per_second(cue),
+                    text: cue.text.clone(),
+                    warnings,
+                }
+            })
+            .collect()
+    }
+
+    /// 修改第 `index` 条（从 0 开始）的文本。
+    pub fn set_text(&mut self, index: usize, text: &str) {
+        if index < self.cues.len() && self.cues[index].text != text {
+            self.save();
+            self.cues[index].text = text.to_string();
+        }
+    }
+
+    /// 删除第 `index` 条。
+    pub fn delete(&mut self, index: usize) {
+        if index < self.cues.len() {
+            self.save();
+            self.cues.remove(index);
+        }
+    }
+
+    /// 把第 `index` 条与下一条合并：文本分两行，时间取两条的并集。
+    pub fn merge_with_next(&mut self, index: usize) -> bool {
+        if index + 1 >= self.cues.len() {
+            return false;
+        }
+
+        self.save();
+        let next = self.cues.remove(index + 1);
+        let cue = &mut self.cues[index];
+        cue.start_ms = cue.start_ms.min(next.start_ms);
+        cue.end_ms = cue.end_ms.max(next.end_ms);
+        cue.text = match (cue.text.is_empty(), next.text.is_empty()) {
+            (_, true) => cue.text.clone(),
+            (true, false) => next.text,
+            (false, false) => format!("{}\n{}", cue.text, next.text),
+        };
+        true
+    }
+
+    /// 把第 `index` 条的入点、出点或整条平移 `frames` 帧。
+    ///
+    /// 入点不会早于 0，单独平移一端时入点不会越过出点。
+    pub fn nudge(&mut self, index: usize, edge: Edge, frames: i64) -> bool {
+        let Some(cue) = self.cues.get(index) else {
+            return false;
+        };
+
+        let (mut start_ms, mut end_ms) = (cue.start_ms, cue.end_ms);
+        match edge {
+            Edge::Start => start_ms = self.offset(start_ms, frames).min(end_ms),
+            Edge::End => end_ms = self.offset(end_ms, frames).max(start_ms),
+            Edge::Both => {
+                // 整条平移时保持帧数不变，到 0 为止
+                let shift = frames.max(-(self.frames(start_ms) as i64));
+                start_ms = self.offset(start_ms, shift);
+                end_ms = self.offset(end_ms, shift);
+            }
+        }
+
+        if (start_ms, end_ms) == (cue.start_ms, cue.end_ms) {
+            return false;
+        }
+        self.save();
+        self.cues[index].start_ms = start_ms;
+        self.cues[index].end_ms = end_ms;
+        true
+    }
+
+    /// 撤销最近一次修改。
+    pub fn undo(&mut self) -> bool {
+        match self.history.pop() {
+            Some(cues) => {
+                self.cues = cues;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn save(&mut self) {
+        self.history.push(self.cues.clone());
+    }
+
+    fn timecode(&self, ms: u64) -> Timecode {
+        Timecode::from_millis(ms, self.fps, false)
+    }
+
+    fn frames(&self, ms: u64) -> u64 {
+        self.timecode(ms).to_frames(nominal_rate(self.fps))
+    }
+
+    fn offset(&self, ms: u64, frames: i64) -> u64 {
+        self.timecode(ms)
+            .offset_frames(frames, nominal_rate(self.fps))
+            .to_millis(self.fps)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue(start_ms: u64, end_ms: u64, text: &str) -> Cue {
+        Cue {
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+        }
+    }
+
+    fn editor() -> CueEditor {
+        CueEditor::new(
+            vec![
+                cue(1_000, 2_000, "第一句"),
+                cue(1_960, 3_000, "第二句"),
+                cue(4_000, 5_000, "第三句"),
+            ],
+            25.0,
+            QcLimits {
+                max_chars_per_second: Some(2.5),
+                ..QcLimits::default()
+            },
+        )
+    }
+
+    #[test]
+    fn rows_show_timecodes_and_warnings() {
+        let rows = editor().rows();
+        assert_eq!(rows[0].start.to_string(), "00:00:01:00");
+        assert_eq!(rows[1].start.to_string(), "00:00:01:24");
+        assert_eq!(rows[1].duration_ms, 1_040);
+        assert_eq!(rows[0].cps, Some(3.0));
+        assert_eq!(rows[0].warnings, ["阅读速度 3.0 字/秒，超过上限 2.5"]);
+        assert!(rows[1].warnings.contains(&"与第 1 条重叠".to_string()));
+    }
+
+    #[test]
+    fn nudge_by_frames() {
+        let mut editor = editor();
+        assert!(editor.nudge(0, Edge::End, -1));
+        assert_eq!(editor.cues()[0].end_ms, 1_960);
+        assert!(editor.nudge(2, Edge::Both, 2));
+        assert_eq!(editor.cues()[2], cue(4_080, 5_080, "第三句"));
+
+        // 入点不越过出点，到 0 为止
+        assert!(editor.nudge(2, Edge::Start, 100));
+        assert_eq!(editor.cues()[2].start_ms, 5_080);
+        assert!(editor.nudge(0, Edge::Both, -1_000));
+        assert_eq!(editor.cues()[0], cue(0, 960, "第一句"));
+        assert!(!editor.nudge(0, Edge::Both, -1));
+    }
+
+    #[test]
+    fn merge_delete_and_undo() {
+        let mut editor = editor();
+        assert!(editor.merge_with_next(0));
+        assert_eq!(editor.cues()[0], cue(1_000, 3_000, "第一句\n第二句"));
+        assert!(!editor.merge_with_next(1));
+
+        editor.delete(1);
+        editor.set_text(0, "改过");
+        assert_eq!(editor.cues(), [cue(1_000, 3_000, "改过")]);
+        assert!(editor.is_modified());
+
+        assert!(editor.undo());
+        assert!(editor.undo());
+        assert!(editor.undo());
+        assert_eq!(editor.cues().len(), 3);
+        assert!(!editor.is_modified());
+        assert!(!editor.undo());
+    }
+}
@@ -0,0 +1,235 @@
+//! 编码诊断：列出 BOM、chardetng 在不同地区提示下的猜测，以及每个候选编码的解码效果。
+//!
+//! [`decode_edl_bytes`](crate::reader::decode_edl_bytes) 只用不带提示的猜测结果。
+//! 日文、繁体中文等文件解码成乱码时，用 [`diagnose_encoding`] 对比各个候选，
+//! 再用 `--input-encoding` 指定正确的编码。
+
+use chardetng::EncodingDetector;
+use encoding_rs::Encoding;
+
+use crate::reader::decode_with_encoding;
+use crate::t;
+
+/// 传给 chardetng 的地区提示（顶级域名）。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EncodingHint {
+    /// 日本，倾向 Shift_JIS / EUC-JP
+    Jp,
+
+    /// 中国大陆，倾向 GBK
+    Cn,
+
+    /// 台湾，倾向 Big5
+    Tw,
+
+    /// 韩国，倾向 EUC-KR
+    Kr,
+}
+
+impl EncodingHint {
+    pub const ALL: [EncodingHint; 4] = [
+        EncodingHint::Jp,
+        EncodingHint::Cn,
+        EncodingHint::Tw,
+        EncodingHint::Kr,
+    ];
+
+    pub fn parse(text: &str) -> Option<EncodingHint> {
+        match text.to_ascii_lowercase().as_str() {
+            "jp" | "ja" => Some(EncodingHint::Jp),
+            "cn" | "zh" => Some(EncodingHint::Cn),
+            "tw" => Some(EncodingHint::Tw),
+            "kr" | "ko" => Some(EncodingHint::Kr),
+            _ => None,
+        }
+    }
+
+    /// 顶级域名，小写 ASCII
+    pub fn tld(&self) -> &'static str {
+        match self {
+            EncodingHint::Jp => "jp",
+            EncodingHint::Cn => "cn",
+            EncodingHint::Tw => "tw",
+            EncodingHint::Kr => "kr",
+        }
+    }
+}
+
+/// 候选编码是怎么得到的。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CandidateSource {
+    /// 文件开头的 BOM
+    Bom,
+
+    /// chardetng 不带提示的猜测（与自动检测一致）
+    Guess,
+
+    /// chardetng 带地区提示的猜测
+    HintedGuess(EncodingHint),
+}
+
+impl CandidateSource {
+    pub fn label(&self) -> String {
+        match self {
+            CandidateSource::Bom => "BOM".to_string(),
+            CandidateSource::Guess => t!("encoding.guess"),
+            CandidateSource::HintedGuess(hint) => t!("encoding.hinted_guess", hint.tld()),
+        }
+    }
+}
+
+/// 一个候选编码及其解码效果。
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncodingCandidate {
+    pub encoding: &'static Encoding,
+
+    /// 得到这个编码的所有途径
+    pub sources: Vec<CandidateSource>,
+
+    /// chardetng 是否认为猜测可信（得分高于其它候选）；BOM 没有这一项
+    pub confident: Option<bool>,
+
+    /// 解码时是否遇到非法字节
+    pub had_errors: bool,
+
+    /// 解码结果中替换字符（U+FFFD）的个数
+    pub replacements: usize,
+
+    /// 解码后的前几行非空文本
+    pub preview: Vec<String>,
+}
+
+/// 诊断结果。
+#[derive(Debug, Clone, PartialEq)]
+pub struct EncodingDiagnosis {
+    /// 文件开头的 BOM 对应的编码
+    pub bom: Option<&'static Encoding>,
+
+    /// 不同的候选编码，按 BOM、无提示猜测、带提示猜测的顺序
+    pub candidates: Vec<EncodingCandidate>,
+}
+
+impl EncodingDiagnosis {
+    /// 建议使用的候选：有 BOM 时用 BOM；否则取没有解码错误的候选中
+    /// 替换字符最少、且 chardetng 认为可信的那个。
+    pub fn recommended(&self) -> Option<&EncodingCandidate> {
+        if self.bom.is_some() {
+            return self.candidates.first();
+        }
+        self.candidates
+            .iter()
+            .min_by_key(|c| (c.had_errors, c.replacements, c.confident != Some(true)))
+    }
+}
+
+/// 诊断字节的编码。
+///
+/// `hints` 为空时只给出不带提示的猜测。`preview_lines` 为每个候选预览的行数。
+pub fn diagnose_encoding(
+    bytes: &[u8],
+    hints: &[EncodingHint],
+    preview_lines: usize,
+) -> EncodingDiagnosis {
+    let mut found: Vec<(&'static Encoding, CandidateSource, Option<bool>)> = Vec::new();
+
+    let bom = Encoding::for_bom(bytes);
+    if let Some((encoding, _)) = bom {
+        found.push((encoding, CandidateSource::Bom, None));
+    }
+
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, true);
+    let (guessed, confident) = detector.guess_assess(None, true);
+    found.push((guessed, CandidateSource::Guess, Some(confident)));
+    for &hint in hints {
+        let (guessed, confident) = detector.guess_assess(Some(hint.tld().as_bytes()), true);
+        found.push((guessed, CandidateSource::HintedGuess(hint), Some(confident)));
+    }
+
+    let mut candidates: Vec<EncodingCandidate> = Vec::new();
+    for (encoding, source, confident) in found {
+        if let Some(existing) = candidates.iter_mut().find(|c| c.encoding == encoding) {
+            existing.sources.push(source);
+            continue;
+        }
+
+        let body = match bom {
+            Some((bom_encoding, len)) if bom_encoding == encoding => &bytes[len..],
+            _ => bytes,
+        };
+        let (content, had_errors) = decode_with_encoding(encoding, body);
+        candidates.push(EncodingCandidate {
+            encoding,
+            sources: vec![source],
+            confident,
+            had_errors,
+            replacements: content.matches('\u{FFFD}').count(),
+            preview: content
+                .lines()
+                .map(str::trim_end)
+                .filter(|line| !line.trim().is_empty())
+                .take(preview_lines)
+                .map(str::to_string)
+                .collect(),
+        });
+    }
+
+    EncodingDiagnosis {
+        bom: bom.map(|(encoding, _)| encoding),
+        candidates,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EDL: &str = "TITLE: テスト\n\
+                       001  AX  A  C  00:00:00:00 00:00:01:00 01:00:00:00 01:00:01:00\n\
+                       * FROM CLIP NAME: こんにちは、今日はいい天気ですね.wav\n";
+
+    #[test]
+    fn shift_jis_candidates() {
+        let (bytes, _, _) = encoding_rs::SHIFT_JIS.encode(EDL);
+        let diagnosis = diagnose_encoding(&bytes, &EncodingHint::ALL, 2);
+
+        assert_eq!(diagnosis.bom, None);
+        let jp = diagnosis
+            .candidates
+            .iter()
+            .find(|c| {
+                c.sources
+                    .contains(&CandidateSource::HintedGuess(EncodingHint::Jp))
+            })
+            .unwrap();
+        assert_eq!(jp.encoding, encoding_rs::SHIFT_JIS);
+        assert!(!jp.had_errors);
+        assert_eq!(jp.preview, ["TITLE: テスト", EDL.lines().nth(1).unwrap()]);
+        assert_eq!(
+            diagnosis.recommended().unwrap().encoding,
+            encoding_rs::SHIFT_JIS
+        );
+    }
+
+    #[test]
+    fn bom_comes_first() {
+        let mut bytes = vec![0xEF, 0xBB, 0xBF];
+        bytes.extend_from_slice(EDL.as_bytes());
+        let diagnosis = diagnose_encoding(&bytes, &[], 1);
+
+        assert_eq!(diagnosis.bom, Some(encoding_rs::UTF_8));
+        assert_eq!(diagnosis.candidates.len(), 1);
+        assert_eq!(
+            diagnosis.candidates[0].sources,
+            [CandidateSource::Bom, CandidateSource::Guess]
+        );
+        assert_eq!(diagnosis.candidates[0].preview, ["TITLE: テスト"]);
+    }
+
+    #[test]
+    fn parse_hint() {
+        assert_eq!(EncodingHint::parse("JP"), Some(EncodingHint::Jp));
+        assert_eq!(EncodingHint::parse("tw"), Some(EncodingHint::Tw));
+        assert_eq!(EncodingHint::parse("fr"), None);
+    }
+}
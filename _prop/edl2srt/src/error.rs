@@ -0,0 +1,98 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::t;
+
+/// edl2srt 库统一使用的错误类型。
+#[derive(Debug)]
+pub enum Error {
+    /// 读写输入输出时发生的 IO 错误
+    Io(io::Error),
+
+    /// 用户指定了 encoding_rs 不认识的编码名
+    UnsupportedEncoding(String),
+
+    /// 注册表里找不到指定的格式
+    UnknownFormat(String),
+
+    /// 输入内容格式错误，line 为出错的行号（从 1 开始）
+    Parse { line: usize, message: String },
+
+    /// JSON 读写失败
+    Json(serde_json::Error),
+
+    /// 调用参数不合法，例如卷数与偏移量数量不一致
+    Invalid(String),
+
+    /// 转写服务返回了错误或无法理解的响应
+    Transcribe(String),
+
+    /// 配置文件格式错误或找不到指定的预设
+    Config { path: PathBuf, message: String },
+
+    /// 转换流程中的某一步失败，消息已写明是哪一步、哪个文件
+    Step(String),
+
+    /// 无法确定帧率，candidates 为符合线索的候选
+    FrameRate {
+        reason: String,
+        candidates: Vec<f64>,
+    },
+}
+
+/// 库内部统一使用的 Result 别名。
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(e) => write!(f, "{}", e),
+            Error::UnsupportedEncoding(label) => {
+                f.write_str(&t!("error.unsupported_encoding", label))
+            }
+            Error::UnknownFormat(name) => f.write_str(&t!("error.unknown_format", name)),
+            Error::Parse { line, message } => f.write_str(&t!("error.parse", line, message)),
+            Error::Json(e) => f.write_str(&t!("error.json", e)),
+            Error::Invalid(message) => f.write_str(&t!("error.invalid", message)),
+            Error::Transcribe(message) => f.write_str(&t!("error.transcribe", message)),
+            Error::Step(message) => f.write_str(message),
+            Error::Config { path, message } if path.as_os_str().is_empty() => {
+                f.write_str(&t!("error.config", message))
+            }
+            Error::Config { path, message } => {
+                f.write_str(&t!("error.config_file", path.display(), message))
+            }
+            Error::FrameRate { reason, candidates } => {
+                f.write_str(&t!("error.frame_rate", reason))?;
+                if !candidates.is_empty() {
+                    let list: Vec<String> = candidates.iter().map(|c| c.to_string()).collect();
+                    f.write_str(&t!("error.frame_rate_candidates", list.join(", ")))?;
+                }
+                f.write_str(&t!("error.frame_rate_hint"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            Error::Json(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        Error::Json(e)
+    }
+}
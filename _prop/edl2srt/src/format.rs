@@ -0,0 +1,363 @@
+//! 可插拔的输入 / 输出格式。
+//!
+//! - [`TimelineReader`]：把时间线文本（EDL 等）解析成 [`Edl`]
+//! - [`SubtitleReader`]：把字幕文件读回 [`Cue`] 列表，用于校验和往返测试
+//! - [`SubtitleWriter`]：把 [`Cue`] 列表写成具体的字幕格式，部分格式支持逐条流式写出
+//!
+//! 新增一种格式只需要在本目录下新建一个模块、实现对应的 trait，
+//! 再在 [`FormatRegistry::builtin`] 中注册即可。
+
+pub mod ass;
+pub mod chapters;
+pub mod edl;
+pub mod json;
+pub mod lrc;
+pub mod sbv;
+pub mod scc;
+pub mod srt;
+pub mod stl;
+pub mod vtt;
+
+use std::io::{self, Read, Write};
+use std::path::Path;
+
+use crate::error::{Error, Result};
+use crate::i18n;
+use crate::model::{Cue, Edl};
+use crate::reader::{DecodeResult, decode_edl_bytes};
+
+/// 读取时间线时的选项。
+#[derive(Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// 用户指定的输入编码，None 表示自动检测
+    pub encoding: Option<String>,
+}
+
+/// 写出字幕时的选项。
+#[derive(Debug, Clone)]
+pub struct WriteOptions {
+    /// 帧率，逐帧计时的格式需要用到
+    pub fps: f64,
+
+    /// 节目标题，通常来自 EDL 的 `TITLE:` 行
+    pub title: Option<String>,
+
+    /// 录制时间码是否为丢帧格式，按实际帧数计时的格式（SCC）需要用到
+    pub drop_frame: bool,
+}
+
+impl WriteOptions {
+    pub fn new(fps: f64) -> Self {
+        WriteOptions {
+            fps,
+            title: None,
+            drop_frame: false,
+        }
+    }
+}
+
+/// 时间线读取器：把已解码的文本解析成事件列表。
+pub trait TimelineReader {
+    /// 格式名，用于 `--format` 之类的选择，例如 "edl"
+    fn name(&self) -> &'static str;
+
+    /// 一句话描述，用于 `--list-formats`
+    fn description(&self) -> &'static str;
+
+    /// 常见扩展名（小写、不带点）
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// 根据文本内容判断是否像本格式。
+    fn sniff(&self, sample: &str) -> bool;
+
+    /// 解析已解码的文本。
+    fn parse(&self, content: &str) -> Result<Edl>;
+
+    /// 从 `Read` 读取字节、解码并解析。
+    fn read(&self, input: &mut dyn Read, options: &ReadOptions) -> Result<(Edl, DecodeResult)> {
+        let mut bytes = Vec::new();
+        input.read_to_end(&mut bytes)?;
+        let decoded = decode_edl_bytes(&bytes, options.encoding.as_deref())?;
+        let edl = self.parse(&decoded.content)?;
+        Ok((edl, decoded))
+    }
+}
+
+/// 字幕读取器：把字幕文件读回 [`Cue`] 列表。
+pub trait SubtitleReader {
+    fn name(&self) -> &'static str;
+
+    fn extensions(&self) -> &'static [&'static str];
+
+    /// 根据文件开头的字节判断是否像本格式。
+    fn sniff(&self, sample: &[u8]) -> bool;
+
+    fn read(&self, bytes: &[u8]) -> Result<Vec<Cue>>;
+}
+
+/// 字幕写出器。
+pub trait SubtitleWriter {
+    fn name(&self) -> &'static str;
+
+    fn description(&self) -> &'static str;
+
+    fn extensions(&self) -> &'static [&'static str];
+
+    fn write(&self, out: &mut dyn Write, cues: &[Cue], options: &WriteOptions) -> io::Result<()>;
+
+    /// 写出前检查格式限制（字符集、行长等），返回给用户看的提示。
+    ///
+    /// 默认没有限制。
+    fn warnings(&self, _cues: &[Cue], _options: &WriteOptions) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// 能否不看后面的字幕就逐条写出，见 [`SubtitleWriter::write_iter`]。
+    fn streams(&self) -> bool {
+        false
+    }
+
+    /// 从迭代器逐条写出，用于流式转换。
+    ///
+    /// 默认先收集全部字幕再调用 [`SubtitleWriter::write`]；
+    /// 能逐条写出的格式覆盖它并让 [`SubtitleWriter::streams`] 返回 true。
+    fn write_iter(
+        &self,
+        out: &mut dyn Write,
+        cues: &mut dyn Iterator<Item = Cue>,
+        options: &WriteOptions,
+    ) -> io::Result<()> {
+        let cues: Vec<Cue> = cues.collect();
+        self.write(out, &cues, options)
+    }
+}
+
+/// 已注册格式的集合。
+pub struct FormatRegistry {
+    timeline_readers: Vec<Box<dyn TimelineReader>>,
+    subtitle_readers: Vec<Box<dyn SubtitleReader>>,
+    subtitle_writers: Vec<Box<dyn SubtitleWriter>>,
+}
+
+impl FormatRegistry {
+    /// 创建一个空注册表。
+    pub fn new() -> Self {
+        FormatRegistry {
+            timeline_readers: Vec::new(),
+            subtitle_readers: Vec::new(),
+            subtitle_writers: Vec::new(),
+        }
+    }
+
+    /// 创建包含全部内置格式的注册表。
+    pub fn builtin() -> Self {
+        let mut registry = FormatRegistry::new();
+
+        registry.register_timeline_reader(Box::new(edl::EdlReader));
+        registry.register_timeline_reader(Box::new(json::JsonReader));
+
+        registry.register_subtitle_reader(Box::new(srt::SrtFormat));
+        registry.register_subtitle_reader(Box::new(vtt::VttFormat));
+        registry.register_subtitle_reader(Box::new(stl::StlFormat));
+        registry.register_subtitle_reader(Box::new(sbv::SbvFormat));
+
+        registry.register_subtitle_writer(Box::new(srt::SrtFormat));
+        registry.register_subtitle_writer(Box::new(vtt::VttFormat));
+        registry.register_subtitle_writer(Box::new(ass::AssFormat));
+        registry.register_subtitle_writer(Box::new(stl::StlFormat));
+        registry.register_subtitle_writer(Box::new(scc::SccFormat));
+        registry.register_subtitle_writer(Box::new(sbv::SbvFormat));
+        registry.register_subtitle_writer(Box::new(lrc::LrcFormat));
+        registry.register_subtitle_writer(Box::new(lrc::EnhancedLrcFormat));
+        registry.register_subtitle_writer(Box::new(chapters::FfmetadataFormat));
+        registry.register_subtitle_writer(Box::new(chapters::MatroskaChaptersFormat));
+        registry.register_subtitle_writer(Box::new(chapters::YoutubeChaptersFormat));
+
+        registry
+    }
+
+    pub fn register_timeline_reader(&mut self, reader: Box<dyn TimelineReader>) {
+        self.timeline_readers.push(reader);
+    }
+
+    pub fn register_subtitle_reader(&mut self, reader: Box<dyn SubtitleReader>) {
+        self.subtitle_readers.push(reader);
+    }
+
+    pub fn register_subtitle_writer(&mut self, writer: Box<dyn SubtitleWriter>) {
+        self.subtitle_writers.push(writer);
+    }
+
+    pub fn timeline_readers(&self) -> impl Iterator<Item = &dyn TimelineReader> {
+        self.timeline_readers.iter().map(|r| r.as_ref())
+    }
+
+    pub fn subtitle_readers(&self) -> impl Iterator<Item = &dyn SubtitleReader> {
+        self.subtitle_readers.iter().map(|r| r.as_ref())
+    }
+
+    pub fn subtitle_writers(&self) -> impl Iterator<Item = &dyn SubtitleWriter> {
+        self.subtitle_writers.iter().map(|w| w.as_ref())
+    }
+
+    /// 按格式名查找时间线读取器。
+    pub fn timeline_reader(&self, name: &str) -> Result<&dyn TimelineReader> {
+        self.timeline_readers()
+            .find(|r| r.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| Error::UnknownFormat(name.to_string()))
+    }
+
+    /// 按格式名查找字幕读取器。
+    pub fn subtitle_reader(&self, name: &str) -> Result<&dyn SubtitleReader> {
+        self.subtitle_readers()
+            .find(|r| r.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| Error::UnknownFormat(name.to_string()))
+    }
+
+    /// 按格式名查找字幕写出器。
+    pub fn subtitle_writer(&self, name: &str) -> Result<&dyn SubtitleWriter> {
+        self.subtitle_writers()
+            .find(|w| w.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| Error::UnknownFormat(name.to_string()))
+    }
+
+    /// 自动选择时间线读取器。
+    ///
+    /// 先看扩展名，扩展名不认识时再看内容。
+    pub fn detect_timeline_reader(
+        &self,
+        path: Option<&Path>,
+        sample: &str,
+    ) -> Option<&dyn TimelineReader> {
+        if let Some(ext) = path.and_then(extension_of)
+            && let Some(reader) = self
+                .timeline_readers()
+                .find(|r| r.extensions().contains(&ext.as_str()))
+        {
+            return Some(reader);
+        }
+
+        self.timeline_readers().find(|r| r.sniff(sample))
+    }
+
+    /// 自动选择字幕读取器，规则同 [`FormatRegistry::detect_timeline_reader`]。
+    pub fn detect_subtitle_reader(
+        &self,
+        path: Option<&Path>,
+        sample: &[u8],
+    ) -> Option<&dyn SubtitleReader> {
+        if let Some(ext) = path.and_then(extension_of)
+            && let Some(reader) = self
+                .subtitle_readers()
+                .find(|r| r.extensions().contains(&ext.as_str()))
+        {
+            return Some(reader);
+        }
+
+        self.subtitle_readers().find(|r| r.sniff(sample))
+    }
+
+    /// 根据输出路径的扩展名选择字幕写出器。
+    pub fn writer_for_path(&self, path: &Path) -> Option<&dyn SubtitleWriter> {
+        let ext = extension_of(path)?;
+        self.subtitle_writers()
+            .find(|w| w.extensions().contains(&ext.as_str()))
+    }
+
+    /// 生成 `--list-formats` 的文本。
+    pub fn describe(&self) -> String {
+        let mut text = format!("{}\n", i18n::text("format.input_formats"));
+        for reader in self.timeline_readers() {
+            text.push_str(&format_line(
+                reader.name(),
+                reader.extensions(),
+                reader.description(),
+            ));
+        }
+
+        text.push_str(&format!("\n{}\n", i18n::text("format.output_formats")));
+        for writer in self.subtitle_writers() {
+            text.push_str(&format_line(
+                writer.name(),
+                writer.extensions(),
+                writer.description(),
+            ));
+        }
+
+        text
+    }
+}
+
+impl Default for FormatRegistry {
+    fn default() -> Self {
+        FormatRegistry::builtin()
+    }
+}
+
+fn format_line(name: &str, extensions: &[&str], description: &str) -> String {
+    let exts: Vec<String> = extensions.iter().map(|e| format!(".{}", e)).collect();
+    format!("  {:<10} {:<16} {}\n", name, exts.join(" "), description)
+}
+
+/// 取小写扩展名。
+fn extension_of(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_reader_by_extension_then_content() {
+        let registry = FormatRegistry::builtin();
+
+        let by_ext = registry
+            .detect_timeline_reader(Some(Path::new("a.EDL")), "")
+            .unwrap();
+        assert_eq!(by_ext.name(), "edl");
+
+        let by_content = registry
+            .detect_timeline_reader(
+                Some(Path::new("a.txt")),
+                "TITLE: x\n001 AX V C 00:00:00:00 00:00:01:00 00:00:00:00 00:00:01:00\n",
+            )
+            .unwrap();
+        assert_eq!(by_content.name(), "edl");
+
+        assert!(
+            registry
+                .detect_timeline_reader(None, "just some text")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn writer_lookup() {
+        let registry = FormatRegistry::builtin();
+        assert_eq!(
+            registry
+                .writer_for_path(Path::new("out.vtt"))
+                .unwrap()
+                .name(),
+            "vtt"
+        );
+        assert_eq!(registry.subtitle_writer("SRT").unwrap().name(), "srt");
+        assert!(matches!(
+            registry.subtitle_writer("nope"),
+            Err(Error::UnknownFormat(_))
+        ));
+    }
+
+    #[test]
+    fn describe_lists_all_formats() {
+        let text = FormatRegistry::builtin().describe();
+        assert!(text.contains("edl"));
+        assert!(text.contains(".srt"));
+        assert!(text.contains(".vtt"));
+        assert!(text.contains(".sbv"));
+        assert!(text.contains("lrc-enhanced"));
+    }
+}
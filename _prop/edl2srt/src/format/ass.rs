@@ -0,0 +1,250 @@
+//! Advanced SubStation Alpha (.ass) 字幕。
+//!
+//! 普通输出只用一个 `Default` 样式；双语输出用两个样式，
+//! 主语言在画面底部，副语言在画面顶部；按说话人输出时每个说话人一个样式。
+
+use std::io::{self, Write};
+
+use crate::bilingual::BilingualCue;
+use crate::format::srt::split_millis;
+use crate::format::{SubtitleWriter, WriteOptions};
+use crate::i18n::text;
+use crate::model::Cue;
+use crate::speaker::SpeakerCue;
+
+/// 底部居中，对应 ASS 的小键盘布局
+const ALIGN_BOTTOM: u8 = 2;
+
+/// 顶部居中
+const ALIGN_TOP: u8 = 8;
+
+/// ASS 写出器。
+pub struct AssFormat;
+
+impl SubtitleWriter for AssFormat {
+    fn name(&self) -> &'static str {
+        "ass"
+    }
+
+    fn description(&self) -> &'static str {
+        text("format.ass")
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["ass"]
+    }
+
+    fn write(&self, out: &mut dyn Write, cues: &[Cue], options: &WriteOptions) -> io::Result<()> {
+        write_header(out, options, &[("Default", ALIGN_BOTTOM)])?;
+        for cue in cues {
+            write_dialogue(out, cue.start_ms, cue.end_ms, "Default", &cue.text)?;
+        }
+        out.flush()
+    }
+}
+
+/// 写出双语 ASS：主语言用 `Primary` 样式（底部），副语言用 `Secondary` 样式（顶部）。
+///
+/// 没有副语言的字幕只写主语言一行。
+pub fn write_bilingual_ass<W: Write>(
+    mut out: W,
+    cues: &[BilingualCue],
+    options: &WriteOptions,
+) -> io::Result<()> {
+    write_header(
+        &mut out,
+        options,
+        &[("Primary", ALIGN_BOTTOM), ("Secondary", ALIGN_TOP)],
+    )?;
+
+    for cue in cues {
+        write_dialogue(&mut out, cue.start_ms, cue.end_ms, "Primary", &cue.primary)?;
+        if let Some(secondary) = &cue.secondary {
+            write_dialogue(&mut out, cue.start_ms, cue.end_ms, "Secondary", secondary)?;
+        }
+    }
+
+    out.flush()
+}
+
+/// 按说话人写出 ASS：每个说话人一个同名样式，对话行的 Name 字段也填说话人。
+///
+/// 样式名中不能有逗号，会换成空格；没有说话人的行用 `Default` 样式。
+/// 多人对话的字幕每人写一行对话，时间相同。
+pub fn write_speaker_ass<W: Write>(
+    mut out: W,
+    cues: &[SpeakerCue],
+    options: &WriteOptions,
+) -> io::Result<()> {
+    let style_name = |speaker: Option<&str>| match speaker {
+        Some(speaker) => speaker.replace(',', " "),
+        None => "Default".to_string(),
+    };
+
+    let mut styles: Vec<String> = Vec::new();
+    for line in cues.iter().flat_map(|cue| &cue.lines) {
+        let name = style_name(line.speaker.as_deref());
+        if !styles.contains(&name) {
+            styles.push(name);
+        }
+    }
+    if styles.is_empty() {
+        styles.push("Default".to_string());
+    }
+    let styles: Vec<(&str, u8)> = styles
+        .iter()
+        .map(|name| (name.as_str(), ALIGN_BOTTOM))
+        .collect();
+    write_header(&mut out, options, &styles)?;
+
+    for cue in cues {
+        for line in &cue.lines {
+            let style = style_name(line.speaker.as_deref());
+            writeln!(
+                out,
+                "Dialogue: 0,{},{},{},{},0,0,0,,{}",
+                format_ass_timestamp(cue.start_ms),
+                format_ass_timestamp(cue.end_ms),
+                style,
+                line.speaker.as_deref().map_or("", |_| style.as_str()),
+                line.text.replace('\n', "\\N")
+            )?;
+        }
+    }
+
+    out.flush()
+}
+
+/// 把毫秒格式化为 ASS 时间戳 `H:MM:SS.cc`（百分之一秒）。
+pub fn format_ass_timestamp(total_ms: u64) -> String {
+    let (h, m, s, ms) = split_millis(total_ms);
+    format!("{}:{:02}:{:02}.{:02}", h, m, s, ms / 10)
+}
+
+fn write_header(
+    out: &mut dyn Write,
+    options: &WriteOptions,
+    styles: &[(&str, u8)],
+) -> io::Result<()> {
+    writeln!(out, "[Script Info]")?;
+    if let Some(title) = &options.title {
+        writeln!(out, "Title: {}", title)?;
+    }
+    writeln!(out, "ScriptType: v4.00+")?;
+    writeln!(out, "WrapStyle: 0")?;
+    writeln!(out, "PlayResX: 1920")?;
+    writeln!(out, "PlayResY: 1080")?;
+    writeln!(out)?;
+
+    writeln!(out, "[V4+ Styles]")?;
+    writeln!(
+        out,
+        "Format: Name, Fontname, Fontsize, PrimaryColour, SecondaryColour, OutlineColour, \
+         BackColour, Bold, Italic, Underline, StrikeOut, ScaleX, ScaleY, Spacing, Angle, \
+         BorderStyle, Outline, Shadow, Alignment, MarginL, MarginR, MarginV, Encoding"
+    )?;
+    for (name, alignment) in styles {
+        writeln!(
+            out,
+            "Style: {},Noto Sans CJK SC,54,&H00FFFFFF,&H000000FF,&H00000000,&H80000000,\
+             0,0,0,0,100,100,0,0,1,2,1,{},60,60,40,1",
+            name, alignment
+        )?;
+    }
+    writeln!(out)?;
+
+    writeln!(out, "[Events]")?;
+    writeln!(
+        out,
+        "Format: Layer, Start, End, Style, Name, MarginL, MarginR, MarginV, Effect, Text"
+    )
+}
+
+fn write_dialogue(
+    out: &mut dyn Write,
+    start_ms: u64,
+    end_ms: u64,
+    style: &str,
+    text: &str,
+) -> io::Result<()> {
+    writeln!(
+        out,
+        "Dialogue: 0,{},{},{},,0,0,0,,{}",
+        format_ass_timestamp(start_ms),
+        format_ass_timestamp(end_ms),
+        style,
+        text.replace('\n', "\\N")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamps_use_centiseconds() {
+        assert_eq!(format_ass_timestamp(0), "0:00:00.00");
+        assert_eq!(format_ass_timestamp(3_723_456), "1:02:03.45");
+    }
+
+    #[test]
+    fn bilingual_uses_two_styles() {
+        let cues = [
+            BilingualCue {
+                start_ms: 1_000,
+                end_ms: 2_000,
+                primary: "你好".to_string(),
+                secondary: Some("Hello".to_string()),
+            },
+            BilingualCue {
+                start_ms: 3_000,
+                end_ms: 4_000,
+                primary: "第一行\n第二行".to_string(),
+                secondary: None,
+            },
+        ];
+
+        let mut out = Vec::new();
+        write_bilingual_ass(&mut out, &cues, &WriteOptions::new(25.0)).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        assert!(text.contains("Style: Primary,"));
+        assert!(text.contains("Style: Secondary,"));
+        assert!(text.contains("Dialogue: 0,0:00:01.00,0:00:02.00,Primary,,0,0,0,,你好\n"));
+        assert!(text.contains("Dialogue: 0,0:00:01.00,0:00:02.00,Secondary,,0,0,0,,Hello\n"));
+        assert!(text.contains("Primary,,0,0,0,,第一行\\N第二行\n"));
+        assert_eq!(text.matches("Dialogue:").count(), 3);
+    }
+
+    #[test]
+    fn speakers_get_their_own_styles() {
+        use crate::speaker::SpeakerLine;
+
+        let line = |speaker: Option<&str>, text: &str| SpeakerLine {
+            speaker: speaker.map(str::to_string),
+            text: text.to_string(),
+        };
+        let cues = [
+            SpeakerCue {
+                start_ms: 1_000,
+                end_ms: 2_000,
+                lines: vec![line(Some("小明"), "你好"), line(Some("小红"), "你也好")],
+            },
+            SpeakerCue {
+                start_ms: 3_000,
+                end_ms: 4_000,
+                lines: vec![line(None, "旁白")],
+            },
+        ];
+
+        let mut out = Vec::new();
+        write_speaker_ass(&mut out, &cues, &WriteOptions::new(25.0)).unwrap();
+        let text = String::from_utf8(out).unwrap();
+
+        assert_eq!(text.matches("\nStyle: ").count(), 3);
+        assert!(text.contains("Style: 小红,"));
+        assert!(text.contains("Dialogue: 0,0:00:01.00,0:00:02.00,小明,小明,0,0,0,,你好\n"));
+        assert!(text.contains("Dialogue: 0,0:00:01.00,0:00:02.00,小红,小红,0,0,0,,你也好\n"));
+        assert!(text.contains("Dialogue: 0,0:00:03.00,0:00:04.00,Default,,0,0,0,,旁白\n"));
+    }
+}
@@ -0,0 +1,367 @@
+//! 章节输出：FFmpeg 元数据、Matroska 章节 XML、YouTube 简介里的章节列表。
+//!
+//! 每条字幕就是一个章节，字幕文本是章节标题（多行时用空格连接）。
+//! 章节首尾相接：每章结束于下一章开始，最后一章结束于自身的出点。
+//! 时间直接取自 [`Cue`]，也就是按帧率换算过的录制时间码，与字幕输出一致。
+
+use std::io::{self, Write};
+
+use crate::format::srt::split_millis;
+use crate::format::{SubtitleWriter, WriteOptions};
+use crate::i18n::text;
+use crate::model::Cue;
+use crate::t;
+
+/// 第一章晚于这个时间时，多半是录制时间码没有减去起点
+const HOUR_MS: u64 = 3_600_000;
+
+/// 一个章节。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapter {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub title: String,
+}
+
+/// 把字幕列表转为首尾相接的章节列表（按开始时间排序）。
+pub fn cues_to_chapters(cues: &[Cue]) -> Vec<Chapter> {
+    let mut sorted: Vec<&Cue> = cues.iter().collect();
+    sorted.sort_by_key(|c| c.start_ms);
+
+    sorted
+        .iter()
+        .enumerate()
+        .map(|(i, cue)| {
+            let end_ms = match sorted.get(i + 1) {
+                Some(next) => next.start_ms,
+                None => cue.end_ms.max(cue.start_ms),
+            };
+            Chapter {
+                start_ms: cue.start_ms,
+                end_ms,
+                title: cue
+                    .text
+                    .lines()
+                    .map(str::trim)
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            }
+        })
+        .collect()
+}
+
+/// FFmpeg 元数据（`;FFMETADATA1`），配合 `ffmpeg -i in.mp4 -i chapters.ffmeta -map_metadata 1` 使用。
+pub struct FfmetadataFormat;
+
+impl SubtitleWriter for FfmetadataFormat {
+    fn name(&self) -> &'static str {
+        "ffmetadata"
+    }
+
+    fn description(&self) -> &'static str {
+        text("format.ffmetadata")
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["ffmeta", "ffmetadata"]
+    }
+
+    fn write(&self, out: &mut dyn Write, cues: &[Cue], options: &WriteOptions) -> io::Result<()> {
+        writeln!(out, ";FFMETADATA1")?;
+        if let Some(title) = &options.title {
+            writeln!(out, "title={}", escape_ffmetadata(title))?;
+        }
+
+        for chapter in cues_to_chapters(cues) {
+            writeln!(out)?;
+            writeln!(out, "[CHAPTER]")?;
+            writeln!(out, "TIMEBASE=1/1000")?;
+            writeln!(out, "START={}", chapter.start_ms)?;
+            writeln!(out, "END={}", chapter.end_ms)?;
+            writeln!(out, "title={}", escape_ffmetadata(&chapter.title))?;
+        }
+
+        out.flush()
+    }
+
+    fn warnings(&self, cues: &[Cue], _options: &WriteOptions) -> Vec<String> {
+        chapter_warnings(cues)
+    }
+}
+
+/// Matroska 章节 XML，配合 `mkvmerge --chapters chapters.xml` 使用。
+pub struct MatroskaChaptersFormat;
+
+impl SubtitleWriter for MatroskaChaptersFormat {
+    fn name(&self) -> &'static str {
+        "mkvchapters"
+    }
+
+    fn description(&self) -> &'static str {
+        text("format.mkvchapters")
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["xml"]
+    }
+
+    fn write(&self, out: &mut dyn Write, cues: &[Cue], _options: &WriteOptions) -> io::Result<()> {
+        writeln!(out, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+        writeln!(out, r#"<!DOCTYPE Chapters SYSTEM "matroskachapters.dtd">"#)?;
+        writeln!(out, "<Chapters>")?;
+        writeln!(out, "  <EditionEntry>")?;
+
+        for chapter in cues_to_chapters(cues) {
+            writeln!(out, "    <ChapterAtom>")?;
+            writeln!(
+                out,
+                "      <ChapterTimeStart>{}</ChapterTimeStart>",
+                format_matroska_timestamp(chapter.start_ms)
+            )?;
+            writeln!(
+                out,
+                "      <ChapterTimeEnd>{}</ChapterTimeEnd>",
+                format_matroska_timestamp(chapter.end_ms)
+            )?;
+            writeln!(out, "      <ChapterDisplay>")?;
+            writeln!(
+                out,
+                "        <ChapterString>{}</ChapterString>",
+                escape_xml(&chapter.title)
+            )?;
+            writeln!(out, "        <ChapterLanguage>und</ChapterLanguage>")?;
+            writeln!(out, "      </ChapterDisplay>")?;
+            writeln!(out, "    </ChapterAtom>")?;
+        }
+
+        writeln!(out, "  </EditionEntry>")?;
+        writeln!(out, "</Chapters>")?;
+        out.flush()
+    }
+
+    fn warnings(&self, cues: &[Cue], _options: &WriteOptions) -> Vec<String> {
+        chapter_warnings(cues)
+    }
+}
+
+/// YouTube 简介中的章节列表：每行 `0:00 标题`。
+///
+/// YouTube 要求第一章从 0:00 开始；第一条字幕不在 0 点时，
+/// 先补一行以节目标题（没有标题时为界面语言的“开始”）命名的 0:00 章节。
+pub struct YoutubeChaptersFormat;
+
+impl SubtitleWriter for YoutubeChaptersFormat {
+    fn name(&self) -> &'static str {
+        "youtube"
+    }
+
+    fn description(&self) -> &'static str {
+        text("format.youtube")
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["txt"]
+    }
+
+    fn write(&self, out: &mut dyn Write, cues: &[Cue], options: &WriteOptions) -> io::Result<()> {
+        let chapters = cues_to_chapters(cues);
+        let long = chapters.iter().any(|c| c.start_ms >= 3_600_000);
+
+        if needs_zero_chapter(&chapters) {
+            writeln!(
+                out,
+                "{} {}",
+                format_youtube_timestamp(0, long),
+                zero_chapter_title(options)
+            )?;
+        }
+
+        for chapter in &chapters {
+            writeln!(
+                out,
+                "{} {}",
+                format_youtube_timestamp(chapter.start_ms, long),
+                chapter.title
+            )?;
+        }
+
+        out.flush()
+    }
+
+    fn warnings(&self, cues: &[Cue], options: &WriteOptions) -> Vec<String> {
+        let mut warnings = chapter_warnings(cues);
+        if warnings.is_empty() && needs_zero_chapter(&cues_to_chapters(cues)) {
+            warnings.push(t!("chapters.youtube_padded", zero_chapter_title(options)));
+        }
+        warnings
+    }
+}
+
+/// YouTube 要求第一章在 0:00，第一章晚于 1 秒时需要补一章
+fn needs_zero_chapter(chapters: &[Chapter]) -> bool {
+    chapters.first().is_some_and(|c| c.start_ms >= 1_000)
+}
+
+fn zero_chapter_title(options: &WriteOptions) -> &str {
+    options
+        .title
+        .as_deref()
+        .unwrap_or(text("format.youtube_start"))
+}
+
+/// 第一章晚于一小时时提示使用 `--origin`，与 LRC 的检查一致。
+fn chapter_warnings(cues: &[Cue]) -> Vec<String> {
+    match cues.iter().map(|cue| cue.start_ms).min() {
+        Some(start_ms) if start_ms >= HOUR_MS => {
+            vec![t!(
+                "chapters.late_start",
+                format_youtube_timestamp(start_ms, true)
+            )]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Matroska 章节时间 `HH:MM:SS.nnnnnnnnn`。
+pub fn format_matroska_timestamp(total_ms: u64) -> String {
+    let (h, m, s, ms) = split_millis(total_ms);
+    format!("{:02}:{:02}:{:02}.{:03}000000", h, m, s, ms)
+}
+
+/// YouTube 章节时间：不足一小时为 `M:SS`，否则为 `H:MM:SS`，秒以下舍去。
+///
+/// `long` 为 true 时统一使用带小时的写法，保证整份列表格式一致。
+pub fn format_youtube_timestamp(total_ms: u64, long: bool) -> String {
+    let (h, m, s, _) = split_millis(total_ms);
+    if long {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{}:{:02}", m, s)
+    }
+}
+
+/// FFMETADATA 中 `=`、`;`、`#`、`\` 和换行需要用反斜杠转义。
+fn escape_ffmetadata(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cues() -> Vec<Cue> {
+        vec![
+            Cue {
+                start_ms: 0,
+                end_ms: 0,
+                text: "片头".to_string(),
+            },
+            Cue {
+                start_ms: 65_480,
+                end_ms: 65_480,
+                text: "第一章\n起 & 落".to_string(),
+            },
+            Cue {
+                start_ms: 130_000,
+                end_ms: 200_000,
+                text: "结尾=片尾".to_string(),
+            },
+        ]
+    }
+
+    fn write(writer: &dyn SubtitleWriter, cues: &[Cue], title: Option<&str>) -> String {
+        let mut options = WriteOptions::new(25.0);
+        options.title = title.map(str::to_string);
+        let mut out = Vec::new();
+        writer.write(&mut out, cues, &options).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn chapters_are_contiguous() {
+        let chapters = cues_to_chapters(&cues());
+        assert_eq!(chapters[0].end_ms, 65_480);
+        assert_eq!(chapters[1].title, "第一章 起 & 落");
+        assert_eq!(chapters[2].end_ms, 200_000);
+    }
+
+    #[test]
+    fn ffmetadata() {
+        let text = write(&FfmetadataFormat, &cues(), Some("节目"));
+        assert!(text.starts_with(
+            ";FFMETADATA1\ntitle=节目\n\n[CHAPTER]\nTIMEBASE=1/1000\nSTART=0\nEND=65480\n"
+        ));
+        assert!(text.contains("title=结尾\\=片尾\n"));
+        assert_eq!(text.matches("[CHAPTER]").count(), 3);
+    }
+
+    #[test]
+    fn matroska_xml() {
+        let text = write(&MatroskaChaptersFormat, &cues(), None);
+        assert!(text.contains("<ChapterTimeStart>00:01:05.480000000</ChapterTimeStart>"));
+        assert!(text.contains("<ChapterString>第一章 起 &amp; 落</ChapterString>"));
+        assert!(text.trim_end().ends_with("</Chapters>"));
+    }
+
+    #[test]
+    fn youtube_list() {
+        assert_eq!(
+            write(&YoutubeChaptersFormat, &cues(), None),
+            "0:00 片头\n1:05 第一章 起 & 落\n2:10 结尾=片尾\n"
+        );
+
+        // 第一章不在 0 点时补一行，超过一小时统一带小时
+        let late = [Cue {
+            start_ms: 3_725_000,
+            end_ms: 3_726_000,
+            text: "后半".to_string(),
+        }];
+        assert_eq!(
+            write(&YoutubeChaptersFormat, &late, Some("节目")),
+            "0:00:00 节目\n1:02:05 后半\n"
+        );
+    }
+
+    #[test]
+    fn warns_about_record_time() {
+        let options = WriteOptions::new(25.0);
+        let late = [Cue {
+            start_ms: 3_600_000,
+            end_ms: 3_605_000,
+            text: "第一章".to_string(),
+        }];
+        for writer in [
+            &FfmetadataFormat as &dyn SubtitleWriter,
+            &MatroskaChaptersFormat,
+            &YoutubeChaptersFormat,
+        ] {
+            let warnings = writer.warnings(&late, &options);
+            assert_eq!(warnings.len(), 1, "{}", writer.name());
+            assert!(warnings[0].contains("1:00:00") && warnings[0].contains("--origin"));
+            assert!(writer.warnings(&cues(), &options).is_empty());
+        }
+
+        // 不到一小时但不在 0 点时，YouTube 提示补了 0:00 章节
+        let padded = [Cue {
+            start_ms: 5_000,
+            end_ms: 6_000,
+            text: "正片".to_string(),
+        }];
+        let warnings = YoutubeChaptersFormat.warnings(&padded, &options);
+        assert!(warnings[0].contains("“开始”"));
+    }
+}
@@ -0,0 +1,156 @@
+//! CMX3600 风格的 EDL 读取与写出。
+
+use std::io::{self, Write};
+
+use crate::error::Result;
+use crate::format::TimelineReader;
+use crate::i18n::text;
+use crate::model::{Edl, Timecode};
+use crate::reader::parse_edl;
+
+/// EDL 时间线读取器，解析规则见 [`parse_edl`]。
+pub struct EdlReader;
+
+impl TimelineReader for EdlReader {
+    fn name(&self) -> &'static str {
+        "edl"
+    }
+
+    fn description(&self) -> &'static str {
+        text("format.edl")
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["edl"]
+    }
+
+    /// 有 `TITLE:` / `FCM:` 头，或者有带时间码的事件行，就认为是 EDL。
+    fn sniff(&self, sample: &str) -> bool {
+        sample.lines().map(str::trim).any(|line| {
+            line.starts_with("TITLE:")
+                || line.starts_with("FCM:")
+                || (line.starts_with(|c: char| c.is_ascii_digit())
+                    && line
+                        .split_whitespace()
+                        .rev()
+                        .take(2)
+                        .all(|part| Timecode::parse(part).is_some()))
+        })
+    }
+
+    fn parse(&self, content: &str) -> Result<Edl> {
+        Ok(parse_edl(content))
+    }
+}
+
+/// 以 CMX3600 格式写出 EDL。
+///
+/// 没有源时间码的事件用录制时间码代替；`text` 与片段名不同时，
+/// 额外写一行 `* SUBTITLE:` 注释，方便人工核对。
+pub fn write_edl<W: Write>(mut out: W, edl: &Edl) -> io::Result<()> {
+    if let Some(title) = &edl.title {
+        writeln!(out, "TITLE: {}", title)?;
+    }
+    if let Some(fcm) = &edl.fcm {
+        writeln!(out, "FCM: {}", fcm)?;
+    }
+    writeln!(out)?;
+
+    for event in &edl.events {
+        writeln!(
+            out,
+            "{:<4} {:<8} {:<5} {:<8} {} {} {} {}",
+            event.number,
+            event.reel,
+            event.track,
+            event.transition,
+            event.source_in.unwrap_or(event.record_in),
+            event.source_out.unwrap_or(event.record_out),
+            event.record_in,
+            event.record_out
+        )?;
+        if let Some(motion) = &event.motion {
+            let sign = if motion.speed < 0.0 { "-" } else { "" };
+            writeln!(
+                out,
+                "M2   {:<8}       {}{:05.1}                {}",
+                motion.reel,
+                sign,
+                motion.speed.abs(),
+                motion.entry
+            )?;
+        }
+        if !event.audio_channels.is_empty() {
+            let channels: Vec<String> = event.audio_channels.iter().map(u32::to_string).collect();
+            writeln!(out, "AUD  {}", channels.join("    "))?;
+        }
+        if let Some(name) = &event.clip_name {
+            writeln!(out, "* FROM CLIP NAME: {}", name)?;
+        }
+        for comment in &event.comments {
+            writeln!(out, "* {}", comment)?;
+        }
+        if let Some(text) = &event.text
+            && event.clip_name.as_deref() != Some(text.as_str())
+        {
+            writeln!(out, "* SUBTITLE: {}", text)?;
+        }
+        writeln!(out)?;
+    }
+
+    out.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::ReadOptions;
+
+    #[test]
+    fn sniff_event_line_without_header() {
+        assert!(EdlReader.sniff("001 AX V C 00:00:00:00 00:00:01:00 00:00:00:00 00:00:01:00"));
+        assert!(!EdlReader.sniff("1\n00:00:00,000 --> 00:00:01,000\nhi\n"));
+    }
+
+    #[test]
+    fn read_decodes_and_parses() {
+        let text = "TITLE: t\n001 AX A C 00:00:00:00 00:00:01:00 00:00:00:00 00:00:01:00\n\
+                    * FROM CLIP NAME: a.wav\n";
+        let options = ReadOptions::default();
+        let (edl, decoded) = EdlReader.read(&mut text.as_bytes(), &options).unwrap();
+
+        assert_eq!(decoded.encoding_name, "UTF-8");
+        assert_eq!(edl.title.as_deref(), Some("t"));
+        assert_eq!(edl.events[0].clip_name.as_deref(), Some("a.wav"));
+    }
+
+    #[test]
+    fn write_then_parse_round_trip() {
+        let text = "TITLE: t\nFCM: NON-DROP FRAME\n\
+                    001 AX A C 00:00:01:00 00:00:02:00 01:00:00:00 01:00:01:00\n\
+                    M2   AX       -012.5                00:00:01:00\n\
+                    AUD  3\n\
+                    * FROM CLIP NAME: a.wav\n\
+                    * SOURCE FILE: /x/a.wav\n";
+        let edl = parse_edl(text);
+
+        let mut out = Vec::new();
+        write_edl(&mut out, &edl).unwrap();
+        let back = parse_edl(std::str::from_utf8(&out).unwrap());
+
+        assert_eq!(back.title, edl.title);
+        assert_eq!(back.fcm, edl.fcm);
+        assert_eq!(back.events.len(), 1);
+        let (a, b) = (&edl.events[0], &back.events[0]);
+        assert_eq!(
+            (&a.number, &a.reel, &a.track, &a.transition),
+            (&b.number, &b.reel, &b.track, &b.transition)
+        );
+        assert_eq!((a.source_in, a.record_out), (b.source_in, b.record_out));
+        assert_eq!(a.clip_name, b.clip_name);
+        assert_eq!(a.comments, b.comments);
+        assert_eq!(b.audio_channels, [3]);
+        assert_eq!(b.motion, a.motion);
+        assert_eq!(b.motion.as_ref().unwrap().speed, -12.5);
+    }
+}
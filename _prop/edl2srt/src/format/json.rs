@@ -0,0 +1,58 @@
+//! `--dump-json` 导出的 JSON 中间表示，作为时间线输入读回。
+
+use crate::dump::parse_dump_json;
+use crate::error::Result;
+use crate::format::TimelineReader;
+use crate::i18n::text;
+use crate::model::Edl;
+
+/// JSON 时间线读取器，格式见 [`crate::dump`]。
+pub struct JsonReader;
+
+impl TimelineReader for JsonReader {
+    fn name(&self) -> &'static str {
+        "json"
+    }
+
+    fn description(&self) -> &'static str {
+        text("format.json")
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["json"]
+    }
+
+    fn sniff(&self, sample: &str) -> bool {
+        sample.trim_start().starts_with('{') && sample.contains("\"events\"")
+    }
+
+    fn parse(&self, content: &str) -> Result<Edl> {
+        parse_dump_json(content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::dump::{EdlDump, write_dump_json};
+    use crate::format::edl::EdlReader;
+
+    #[test]
+    fn round_trip_through_edl_reader() {
+        let text = "TITLE: t\n001 AX A C 00:00:00:00 00:00:01:00 00:00:00:00 00:00:01:00\n\
+                    * FROM CLIP NAME: a.wav\n";
+        let edl = EdlReader.parse(text).unwrap();
+
+        let mut out = Vec::new();
+        write_dump_json(&mut out, &EdlDump::new(&edl, Some(25.0), None)).unwrap();
+        let json = String::from_utf8(out).unwrap();
+
+        assert!(JsonReader.sniff(&json));
+        assert!(!EdlReader.sniff(&json));
+
+        let back = JsonReader.parse(&json).unwrap();
+        assert_eq!(back.events[0].record_out, edl.events[0].record_out);
+        assert_eq!(back.events[0].text.as_deref(), Some("a"));
+        assert_eq!(back.events[0].source_lines, edl.events[0].source_lines);
+    }
+}
@@ -0,0 +1,209 @@
+//! LRC 歌词。
+//!
+//! 每行歌词前是 `[mm:ss.xx]` 开始时间（分钟可以超过 59，百分之一秒），
+//! 文件开头写 `[ti:标题]` 等标签，标题取自 EDL 的 TITLE 行。
+//!
+//! LRC 没有结束时间：字幕之间有空隙时补一行只有时间的空歌词，让播放器按时清屏。
+//! 多行字幕每行单独写一条、时间相同，多数播放器会把同一时间的第二行当作翻译显示。
+//!
+//! 增强版（`lrc-enhanced`）在每行文本前后加 `<mm:ss.xx>` 逐字时间标签，
+//! 写明这一行的开始和结束，支持的播放器可以据此做卡拉 OK 式的逐行高亮。
+
+use std::io::{self, Write};
+
+use crate::format::{SubtitleWriter, WriteOptions};
+use crate::i18n::text;
+use crate::model::Cue;
+use crate::t;
+
+/// 一小时，超过这个时间开始的歌词多半是忘了 --origin
+const HOUR_MS: u64 = 3_600_000;
+
+/// 普通 LRC 写出器。
+pub struct LrcFormat;
+
+/// 增强版 LRC 写出器。
+pub struct EnhancedLrcFormat;
+
+impl SubtitleWriter for LrcFormat {
+    fn name(&self) -> &'static str {
+        "lrc"
+    }
+
+    fn description(&self) -> &'static str {
+        text("format.lrc")
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["lrc"]
+    }
+
+    fn write(&self, out: &mut dyn Write, cues: &[Cue], options: &WriteOptions) -> io::Result<()> {
+        write_lrc(out, cues, options, false)
+    }
+
+    fn warnings(&self, cues: &[Cue], _options: &WriteOptions) -> Vec<String> {
+        lrc_warnings(cues)
+    }
+}
+
+impl SubtitleWriter for EnhancedLrcFormat {
+    fn name(&self) -> &'static str {
+        "lrc-enhanced"
+    }
+
+    fn description(&self) -> &'static str {
+        text("format.lrc_enhanced")
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["lrc"]
+    }
+
+    fn write(&self, out: &mut dyn Write, cues: &[Cue], options: &WriteOptions) -> io::Result<()> {
+        write_lrc(out, cues, options, true)
+    }
+
+    fn warnings(&self, cues: &[Cue], _options: &WriteOptions) -> Vec<String> {
+        lrc_warnings(cues)
+    }
+}
+
+fn write_lrc(
+    out: &mut dyn Write,
+    cues: &[Cue],
+    options: &WriteOptions,
+    enhanced: bool,
+) -> io::Result<()> {
+    if let Some(title) = &options.title {
+        writeln!(out, "[ti:{}]", escape_tag(title))?;
+    }
+    if let Some(last) = cues.iter().map(|cue| cue.end_ms).max() {
+        let seconds = last / 1000;
+        writeln!(out, "[length:{:02}:{:02}]", seconds / 60, seconds % 60)?;
+    }
+    writeln!(out, "[re:edl2srt]")?;
+    writeln!(out, "[ve:{}]", env!("CARGO_PKG_VERSION"))?;
+    writeln!(out)?;
+
+    for (i, cue) in cues.iter().enumerate() {
+        let start = format_lrc_timestamp(cue.start_ms);
+        let end = format_lrc_timestamp(cue.end_ms);
+        for line in cue.text.lines() {
+            if enhanced {
+                writeln!(out, "[{}]<{}>{}<{}>", start, start, line, end)?;
+            } else {
+                writeln!(out, "[{}]{}", start, line)?;
+            }
+        }
+
+        // 与下一句之间有空隙（或已是最后一句）时清屏
+        if cues
+            .get(i + 1)
+            .is_none_or(|next| next.start_ms > cue.end_ms)
+        {
+            writeln!(out, "[{}]", end)?;
+        }
+    }
+
+    out.flush()
+}
+
+fn lrc_warnings(cues: &[Cue]) -> Vec<String> {
+    match cues.first() {
+        Some(cue) if cue.start_ms >= HOUR_MS => {
+            vec![t!("lrc.late_start", format_lrc_timestamp(cue.start_ms))]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// 把毫秒格式化为 LRC 时间 `mm:ss.xx`，分钟不按小时进位。
+pub fn format_lrc_timestamp(total_ms: u64) -> String {
+    let minutes = total_ms / 60_000;
+    let seconds = total_ms / 1000 % 60;
+    let centis = total_ms % 1000 / 10;
+    format!("{:02}:{:02}.{:02}", minutes, seconds, centis)
+}
+
+/// 标签值中不能出现 `]` 和换行
+fn escape_tag(text: &str) -> String {
+    text.replace(']', ")").replace(['\r', '\n'], " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cues() -> Vec<Cue> {
+        vec![
+            Cue {
+                start_ms: 12_000,
+                end_ms: 15_300,
+                text: "第一句".to_string(),
+            },
+            Cue {
+                start_ms: 15_300,
+                end_ms: 18_000,
+                text: "第二句\nSecond line".to_string(),
+            },
+            Cue {
+                start_ms: 61_005,
+                end_ms: 63_000,
+                text: "最后".to_string(),
+            },
+        ]
+    }
+
+    fn write(writer: &dyn SubtitleWriter, title: Option<&str>) -> String {
+        let mut options = WriteOptions::new(25.0);
+        options.title = title.map(str::to_string);
+        let mut out = Vec::new();
+        writer.write(&mut out, &cues(), &options).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn timestamps_keep_counting_minutes() {
+        assert_eq!(format_lrc_timestamp(0), "00:00.00");
+        assert_eq!(format_lrc_timestamp(61_005), "01:01.00");
+        assert_eq!(format_lrc_timestamp(3_723_456), "62:03.45");
+    }
+
+    #[test]
+    fn writes_tags_lines_and_clears() {
+        let text = write(&LrcFormat, Some("歌名 [MV]"));
+        let expected = format!(
+            "[ti:歌名 [MV)]\n[length:01:03]\n[re:edl2srt]\n[ve:{}]\n\n\
+             [00:12.00]第一句\n\
+             [00:15.30]第二句\n[00:15.30]Second line\n[00:18.00]\n\
+             [01:01.00]最后\n[01:03.00]\n",
+            env!("CARGO_PKG_VERSION")
+        );
+        assert_eq!(text, expected);
+    }
+
+    #[test]
+    fn enhanced_marks_line_ends() {
+        let text = write(&EnhancedLrcFormat, None);
+        assert!(!text.contains("[ti:"));
+        assert!(text.contains("[00:12.00]<00:12.00>第一句<00:15.30>\n"));
+        assert!(text.contains("[00:15.30]<00:15.30>Second line<00:18.00>\n"));
+    }
+
+    #[test]
+    fn warns_about_record_timecodes() {
+        let late = [Cue {
+            start_ms: 3_600_000,
+            end_ms: 3_601_000,
+            text: "x".to_string(),
+        }];
+        let warnings = LrcFormat.warnings(&late, &WriteOptions::new(25.0));
+        assert!(warnings[0].contains("--origin"));
+        assert!(
+            LrcFormat
+                .warnings(&cues(), &WriteOptions::new(25.0))
+                .is_empty()
+        );
+    }
+}
@@ -0,0 +1,181 @@
+//! YouTube SubViewer (.sbv) 字幕。
+//!
+//! YouTube 字幕编辑器导入导出用的格式：时间行为 `H:MM:SS.mmm,H:MM:SS.mmm`，
+//! 下面是文本，字幕之间空一行。
+
+use std::io::{self, Write};
+
+use crate::error::{Error, Result};
+use crate::format::srt::split_millis;
+use crate::format::{SubtitleReader, SubtitleWriter, WriteOptions};
+use crate::i18n::text;
+use crate::model::Cue;
+use crate::t;
+
+/// SBV 读写器。
+pub struct SbvFormat;
+
+impl SubtitleWriter for SbvFormat {
+    fn name(&self) -> &'static str {
+        "sbv"
+    }
+
+    fn description(&self) -> &'static str {
+        text("format.sbv")
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["sbv"]
+    }
+
+    fn write(&self, out: &mut dyn Write, cues: &[Cue], options: &WriteOptions) -> io::Result<()> {
+        self.write_iter(out, &mut cues.iter().cloned(), options)
+    }
+
+    fn streams(&self) -> bool {
+        true
+    }
+
+    fn write_iter(
+        &self,
+        out: &mut dyn Write,
+        cues: &mut dyn Iterator<Item = Cue>,
+        _options: &WriteOptions,
+    ) -> io::Result<()> {
+        for cue in cues {
+            writeln!(
+                out,
+                "{},{}",
+                format_sbv_timestamp(cue.start_ms),
+                format_sbv_timestamp(cue.end_ms)
+            )?;
+            writeln!(out, "{}", cue.text)?;
+            writeln!(out)?;
+        }
+
+        out.flush()
+    }
+}
+
+impl SubtitleReader for SbvFormat {
+    fn name(&self) -> &'static str {
+        "sbv"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["sbv"]
+    }
+
+    fn sniff(&self, sample: &[u8]) -> bool {
+        let sample = sample.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(sample);
+        let text = String::from_utf8_lossy(sample);
+        text.lines()
+            .find(|line| !line.trim().is_empty())
+            .is_some_and(|line| parse_time_line(line).is_some())
+    }
+
+    fn read(&self, bytes: &[u8]) -> Result<Vec<Cue>> {
+        let text = String::from_utf8_lossy(bytes);
+        let text = text.trim_start_matches('\u{feff}');
+        let mut cues = Vec::new();
+        let mut lines = text.lines().enumerate().peekable();
+
+        while let Some((index, line)) = lines.next() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (start_ms, end_ms) = parse_time_line(line).ok_or_else(|| Error::Parse {
+                line: index + 1,
+                message: t!("sbv.bad_time_line", line.trim()),
+            })?;
+
+            let mut text_lines = Vec::new();
+            while let Some((_, line)) = lines.peek() {
+                if line.trim().is_empty() {
+                    break;
+                }
+                text_lines.push(line.trim_end());
+                lines.next();
+            }
+
+            cues.push(Cue {
+                start_ms,
+                end_ms,
+                text: text_lines.join("\n"),
+            });
+        }
+
+        Ok(cues)
+    }
+}
+
+/// 把毫秒格式化为 SBV 时间戳 `H:MM:SS.mmm`（小时不补零）。
+pub fn format_sbv_timestamp(total_ms: u64) -> String {
+    let (h, m, s, ms) = split_millis(total_ms);
+    format!("{}:{:02}:{:02}.{:03}", h, m, s, ms)
+}
+
+fn parse_time_line(line: &str) -> Option<(u64, u64)> {
+    let (start, end) = line.trim().split_once(',')?;
+    Some((parse_sbv_timestamp(start)?, parse_sbv_timestamp(end)?))
+}
+
+fn parse_sbv_timestamp(text: &str) -> Option<u64> {
+    let (hms, ms) = text.trim().split_once('.')?;
+    let mut parts = hms.split(':');
+    let h: u64 = parts.next()?.parse().ok()?;
+    let m: u64 = parts.next()?.parse().ok()?;
+    let s: u64 = parts.next()?.parse().ok()?;
+    if parts.next().is_some() || m >= 60 || s >= 60 || ms.len() != 3 {
+        return None;
+    }
+    let ms: u64 = ms.parse().ok()?;
+    Some(((h * 60 + m) * 60 + s) * 1000 + ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_comma_separated_times() {
+        let cues = [Cue {
+            start_ms: 3_723_004,
+            end_ms: 3_725_000,
+            text: "第一行\n第二行".to_string(),
+        }];
+        let mut out = Vec::new();
+        SbvFormat
+            .write(&mut out, &cues, &WriteOptions::new(25.0))
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "1:02:03.004,1:02:05.000\n第一行\n第二行\n\n"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let cues = vec![
+            Cue {
+                start_ms: 599,
+                end_ms: 4_160,
+                text: "hello".to_string(),
+            },
+            Cue {
+                start_ms: 36_000_000,
+                end_ms: 36_001_000,
+                text: "a\nb".to_string(),
+            },
+        ];
+        let mut out = Vec::new();
+        SbvFormat
+            .write(&mut out, &cues, &WriteOptions::new(25.0))
+            .unwrap();
+
+        assert!(SbvFormat.sniff(&out));
+        assert_eq!(SbvFormat.read(&out).unwrap(), cues);
+        assert!(!SbvFormat.sniff(b"1\n00:00:01,000 --> 00:00:02,000\n"));
+    }
+}
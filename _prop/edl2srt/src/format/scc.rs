@@ -0,0 +1,512 @@
+//! Scenarist SCC（CEA-608 隐藏字幕）。
+//!
+//! 每条字幕写成一组 pop-on 指令：
+//! - 先清空后台缓冲（ENM）、进入加载模式（RCL）
+//! - 每行一个前导码（PAC）定位到行和缩进，再写文字
+//! - 最后的 EOC 把后台缓冲翻到屏幕上，出点处用 EDM 清屏
+//!
+//! 控制码按惯例连发两次；每个字（两个字节）占一帧，
+//! 所以加载从入点往前推，保证 EOC 正好落在入点上。
+//! SCC 时间统一按 29.97 丢帧标注：先由字幕时间还原出录制时间码，按源帧率
+//! （丢帧时扣除跳过的帧号）数出实际帧数，再换算成 29.97 的帧，与录制时间码逐帧对齐。
+//!
+//! 每行最多 32 个字符、最多 4 行；608 字符集以外的字符写成 `?`。
+
+use std::io::{self, Write};
+
+use crate::format::{SubtitleWriter, WriteOptions};
+use crate::fps::nominal_rate;
+use crate::i18n::text;
+use crate::model::{Cue, Timecode};
+use crate::t;
+
+/// 每行最多字符数
+const MAX_COLUMNS: usize = 32;
+
+/// pop-on 字幕最多行数
+const MAX_ROWS: usize = 4;
+
+/// 最后一行所在的行号（1–15）
+const BOTTOM_ROW: usize = 15;
+
+// 频道 1 的控制码（未加校验位）
+const RCL: [u8; 2] = [0x14, 0x20];
+const EDM: [u8; 2] = [0x14, 0x2C];
+const ENM: [u8; 2] = [0x14, 0x2E];
+const EOC: [u8; 2] = [0x14, 0x2F];
+
+/// 空字节，用于把奇数个字符补成整字
+const PADDING: u8 = 0x00;
+
+/// 基本字符集中与 ASCII 不同的位置。
+const BASIC_OVERRIDES: &[(char, u8)] = &[
+    ('á', 0x2A),
+    ('é', 0x5C),
+    ('í', 0x5E),
+    ('ó', 0x5F),
+    ('ú', 0x60),
+    ('ç', 0x7B),
+    ('÷', 0x7C),
+    ('Ñ', 0x7D),
+    ('ñ', 0x7E),
+    ('█', 0x7F),
+];
+
+/// 特殊字符（0x11 0x30–0x3F）
+const SPECIAL: &[(char, u8)] = &[
+    ('®', 0x30),
+    ('°', 0x31),
+    ('½', 0x32),
+    ('¿', 0x33),
+    ('™', 0x34),
+    ('¢', 0x35),
+    ('£', 0x36),
+    ('♪', 0x37),
+    ('à', 0x38),
+    ('è', 0x3A),
+    ('â', 0x3B),
+    ('ê', 0x3C),
+    ('î', 0x3D),
+    ('ô', 0x3E),
+    ('û', 0x3F),
+];
+
+/// 扩展字符：(字符, 第一字节, 第二字节, 回退字符)。
+///
+/// 扩展字符会覆盖前一个字符，旧解码器则只显示前一个，
+/// 所以先写一个相近的基本字符作为回退。
+const EXTENDED: &[(char, u8, u8, char)] = &[
+    ('Á', 0x12, 0x20, 'A'),
+    ('É', 0x12, 0x21, 'E'),
+    ('Ó', 0x12, 0x22, 'O'),
+    ('Ú', 0x12, 0x23, 'U'),
+    ('Ü', 0x12, 0x24, 'U'),
+    ('ü', 0x12, 0x25, 'u'),
+    ('‘', 0x12, 0x26, '\''),
+    ('¡', 0x12, 0x27, '!'),
+    ('*', 0x12, 0x28, '.'),
+    ('—', 0x12, 0x2A, '-'),
+    ('©', 0x12, 0x2B, 'c'),
+    ('•', 0x12, 0x2D, '.'),
+    ('“', 0x12, 0x2E, '"'),
+    ('”', 0x12, 0x2F, '"'),
+    ('À', 0x12, 0x30, 'A'),
+    ('Â', 0x12, 0x31, 'A'),
+    ('Ç', 0x12, 0x32, 'C'),
+    ('È', 0x12, 0x33, 'E'),
+    ('Ê', 0x12, 0x34, 'E'),
+    ('Ë', 0x12, 0x35, 'E'),
+    ('ë', 0x12, 0x36, 'e'),
+    ('Î', 0x12, 0x37, 'I'),
+    ('Ï', 0x12, 0x38, 'I'),
+    ('ï', 0x12, 0x39, 'i'),
+    ('Ô', 0x12, 0x3A, 'O'),
+    ('Ù', 0x12, 0x3B, 'U'),
+    ('ù', 0x12, 0x3C, 'u'),
+    ('Û', 0x12, 0x3D, 'U'),
+    ('«', 0x12, 0x3E, '"'),
+    ('»', 0x12, 0x3F, '"'),
+    ('Ã', 0x13, 0x20, 'A'),
+    ('ã', 0x13, 0x21, 'a'),
+    ('Í', 0x13, 0x22, 'I'),
+    ('Ì', 0x13, 0x23, 'I'),
+    ('ì', 0x13, 0x24, 'i'),
+    ('Ò', 0x13, 0x25, 'O'),
+    ('ò', 0x13, 0x26, 'o'),
+    ('Õ', 0x13, 0x27, 'O'),
+    ('õ', 0x13, 0x28, 'o'),
+    ('{', 0x13, 0x29, '('),
+    ('}', 0x13, 0x2A, ')'),
+    ('\\', 0x13, 0x2B, '/'),
+    ('^', 0x13, 0x2C, '\''),
+    ('_', 0x13, 0x2D, '-'),
+    ('|', 0x13, 0x2E, '!'),
+    ('~', 0x13, 0x2F, '-'),
+    ('Ä', 0x13, 0x30, 'A'),
+    ('ä', 0x13, 0x31, 'a'),
+    ('Ö', 0x13, 0x32, 'O'),
+    ('ö', 0x13, 0x33, 'o'),
+    ('ß', 0x13, 0x34, 's'),
+    ('¥', 0x13, 0x35, 'Y'),
+    ('Å', 0x13, 0x38, 'A'),
+    ('å', 0x13, 0x39, 'a'),
+    ('Ø', 0x13, 0x3A, 'O'),
+    ('ø', 0x13, 0x3B, 'o'),
+];
+
+/// SCC 写出器。
+pub struct SccFormat;
+
+impl SubtitleWriter for SccFormat {
+    fn name(&self) -> &'static str {
+        "scc"
+    }
+
+    fn description(&self) -> &'static str {
+        text("format.scc")
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["scc"]
+    }
+
+    fn write(&self, out: &mut dyn Write, cues: &[Cue], options: &WriteOptions) -> io::Result<()> {
+        writeln!(out, "Scenarist_SCC V1.0")?;
+
+        let mut sorted: Vec<&Cue> = cues.iter().collect();
+        sorted.sort_by_key(|c| c.start_ms);
+        let captions: Vec<(u64, u64, Vec<[u8; 2]>)> = sorted
+            .iter()
+            .map(|c| {
+                (
+                    millis_to_frames(c.start_ms, options),
+                    millis_to_frames(c.end_ms, options),
+                    pop_on_words(&c.text),
+                )
+            })
+            .collect();
+
+        // 已被占用到哪一帧（不含）
+        let mut busy_until = 0;
+        for (i, (display, end, words)) in captions.iter().enumerate() {
+            let count = words.len() as u64;
+            let load = display.saturating_sub(count).max(busy_until);
+            write_line(out, load, words)?;
+            busy_until = load + count;
+
+            // 下一条紧接着加载时不必清屏，它的 EOC 会直接替换画面
+            let next_load = captions
+                .get(i + 1)
+                .map(|(display, _, words)| display.saturating_sub(words.len() as u64));
+            if *end >= busy_until && next_load.is_none_or(|next| end + 2 <= next) {
+                write_line(out, *end, &[EDM, EDM])?;
+                busy_until = end + 2;
+            }
+        }
+
+        out.flush()
+    }
+
+    fn warnings(&self, cues: &[Cue], _options: &WriteOptions) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        let mut unsupported: Vec<char> = cues
+            .iter()
+            .flat_map(|c| c.text.chars())
+            .filter(|&c| c != '\n' && encode_char(c).is_none())
+            .collect();
+        unsupported.sort_unstable();
+        unsupported.dedup();
+        if !unsupported.is_empty() {
+            let sample: String = unsupported.iter().take(20).collect();
+            warnings.push(t!("scc.unsupported_chars", unsupported.len(), sample));
+        }
+
+        for (index, cue) in cues.iter().enumerate() {
+            let lines: Vec<&str> = cue.text.lines().collect();
+            if lines.iter().any(|l| l.chars().count() > MAX_COLUMNS) {
+                warnings.push(t!("scc.line_too_long", index + 1, MAX_COLUMNS));
+            }
+            if lines.len() > MAX_ROWS {
+                warnings.push(t!("scc.too_many_rows", index + 1, lines.len(), MAX_ROWS));
+            }
+        }
+
+        warnings
+    }
+}
+
+/// 一条字幕的完整 pop-on 指令序列（校验位在写出时加上）。
+fn pop_on_words(text: &str) -> Vec<[u8; 2]> {
+    let mut words = vec![ENM, ENM, RCL, RCL];
+
+    let lines: Vec<&str> = text.lines().collect();
+    let lines = &lines[lines.len().saturating_sub(MAX_ROWS)..];
+    let first_row = BOTTOM_ROW + 1 - lines.len();
+
+    for (i, line) in lines.iter().enumerate() {
+        let chars: Vec<char> = line.chars().take(MAX_COLUMNS).collect();
+
+        // 居中：PAC 只能按 4 列缩进，余下的用 Tab Offset 补齐
+        let column = (MAX_COLUMNS - chars.len()) / 2;
+        let pac = preamble(first_row + i, column / 4 * 4);
+        words.extend([pac, pac]);
+        if !column.is_multiple_of(4) {
+            let tab = [0x17, 0x20 + (column % 4) as u8];
+            words.extend([tab, tab]);
+        }
+
+        let mut pending: Option<u8> = None;
+        for c in chars {
+            match encode_char(c).unwrap_or(Encoded::Basic(b'?')) {
+                Encoded::Basic(byte) => match pending.take() {
+                    Some(first) => words.push([first, byte]),
+                    None => pending = Some(byte),
+                },
+                Encoded::Control(word, fallback) => {
+                    if let Some(fallback) = fallback {
+                        match pending.take() {
+                            Some(first) => words.push([first, fallback]),
+                            None => words.push([fallback, PADDING]),
+                        }
+                    } else if let Some(first) = pending.take() {
+                        words.push([first, PADDING]);
+                    }
+                    words.extend([word, word]);
+                }
+            }
+        }
+        if let Some(first) = pending {
+            words.push([first, PADDING]);
+        }
+    }
+
+    words.extend([EOC, EOC]);
+    words
+}
+
+/// 频道 1 的前导码：行号 1–15，缩进为 4 的倍数（0–28），白色无下划线。
+fn preamble(row: usize, indent: usize) -> [u8; 2] {
+    const ROWS: [(u8, u8); 15] = [
+        (0x11, 0x40),
+        (0x11, 0x60),
+        (0x12, 0x40),
+        (0x12, 0x60),
+        (0x15, 0x40),
+        (0x15, 0x60),
+        (0x16, 0x40),
+        (0x16, 0x60),
+        (0x17, 0x40),
+        (0x17, 0x60),
+        (0x10, 0x40),
+        (0x13, 0x40),
+        (0x13, 0x60),
+        (0x14, 0x40),
+        (0x14, 0x60),
+    ];
+    let (first, second) = ROWS[row.clamp(1, 15) - 1];
+    [first, second | 0x10 | ((indent / 4) as u8) << 1]
+}
+
+enum Encoded {
+    /// 基本字符集中的单字节
+    Basic(u8),
+
+    /// 两字节的特殊 / 扩展字符，扩展字符带一个回退字节
+    Control([u8; 2], Option<u8>),
+}
+
+fn encode_char(c: char) -> Option<Encoded> {
+    if let Some(&(_, byte)) = BASIC_OVERRIDES.iter().find(|(ch, _)| *ch == c) {
+        return Some(Encoded::Basic(byte));
+    }
+    if let Some(&(_, second)) = SPECIAL.iter().find(|(ch, _)| *ch == c) {
+        return Some(Encoded::Control([0x11, second], None));
+    }
+    if let Some(&(_, first, second, fallback)) = EXTENDED.iter().find(|(ch, ..)| *ch == c) {
+        return Some(Encoded::Control([first, second], Some(fallback as u8)));
+    }
+    // 其余 ASCII 可打印字符与基本字符集一致
+    let replaced = BASIC_OVERRIDES.iter().any(|&(_, byte)| byte as char == c);
+    if (' '..='~').contains(&c) && !replaced {
+        return Some(Encoded::Basic(c as u8));
+    }
+    None
+}
+
+/// 加上奇校验位（最高位）。
+fn parity(byte: u8) -> u8 {
+    let byte = byte & 0x7F;
+    if byte.count_ones().is_multiple_of(2) {
+        byte | 0x80
+    } else {
+        byte
+    }
+}
+
+/// 字幕时间换算为 29.97 帧的实际帧数。
+///
+/// 字幕时间由录制时间码按 [`Timecode::to_millis`] 得来，先还原成时间码，
+/// 数出源帧率下的实际帧数，再按实际时长换算到 29.97。源为 29.97 时帧数不变。
+fn millis_to_frames(ms: u64, options: &WriteOptions) -> u64 {
+    let fps = options.fps;
+    let rate = nominal_rate(fps);
+    let label = Timecode::from_millis(ms, fps, options.drop_frame);
+    let frames = real_frames(&label, rate);
+
+    // 23.976、29.97、59.94 这类帧率实际是名义帧率的 1000/1001
+    let real_fps = if fps.fract() == 0.0 {
+        fps
+    } else {
+        rate as f64 * 1000.0 / 1001.0
+    };
+    (frames as f64 * (30_000.0 / 1001.0) / real_fps).round() as u64
+}
+
+/// 时间码对应的实际帧数：丢帧时间码每分钟跳过名义帧率的 1/15 个帧号，逢十分钟不跳。
+fn real_frames(tc: &Timecode, rate: u32) -> u64 {
+    let frames = tc.to_frames(rate);
+    if !tc.drop_frame || !(rate == 30 || rate == 60) {
+        return frames;
+    }
+
+    let minutes = tc.hours as u64 * 60 + tc.minutes as u64;
+    frames - (rate as u64 / 15) * (minutes - minutes / 10)
+}
+
+/// 实际帧数换算为 29.97 丢帧时间码：每分钟跳过帧号 00、01，逢十分钟不跳。
+pub fn drop_frame_timecode(frames: u64) -> Timecode {
+    const PER_TEN_MINUTES: u64 = 17_982;
+    const PER_MINUTE: u64 = 1_798;
+
+    let tens = frames / PER_TEN_MINUTES;
+    let rest = frames % PER_TEN_MINUTES;
+    let skipped = 18 * tens
+        + if rest < 2 {
+            0
+        } else {
+            2 * ((rest - 2) / PER_MINUTE)
+        };
+
+    Timecode::from_frames(frames + skipped, 30, true)
+}
+
+fn write_line(out: &mut dyn Write, frame: u64, words: &[[u8; 2]]) -> io::Result<()> {
+    let words: Vec<String> = words
+        .iter()
+        .map(|w| format!("{:02x}{:02x}", parity(w[0]), parity(w[1])))
+        .collect();
+    writeln!(out)?;
+    writeln!(out, "{}\t{}", drop_frame_timecode(frame), words.join(" "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(cues: &[Cue]) -> String {
+        let mut out = Vec::new();
+        SccFormat
+            .write(&mut out, cues, &WriteOptions::new(29.97))
+            .unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn odd_parity() {
+        assert_eq!(parity(0x14), 0x94);
+        assert_eq!(parity(0x2C), 0x2C);
+        assert_eq!(parity(0x2E), 0xAE);
+        assert_eq!(parity(b'A'), 0xC1);
+        assert_eq!(parity(PADDING), 0x80);
+    }
+
+    #[test]
+    fn drop_frame_labels() {
+        assert_eq!(drop_frame_timecode(0).to_string(), "00:00:00;00");
+        assert_eq!(drop_frame_timecode(1_800).to_string(), "00:01:00;02");
+        assert_eq!(drop_frame_timecode(17_982).to_string(), "00:10:00;00");
+        assert_eq!(drop_frame_timecode(107_892).to_string(), "01:00:00;00");
+    }
+
+    #[test]
+    fn times_follow_the_record_timecodes() {
+        use crate::fps::uses_drop_frame;
+        use crate::reader::parse_edl;
+        use crate::transform::{entries_to_cues, events_to_entries};
+
+        let scc = |text: &str| {
+            let edl = parse_edl(text);
+            let options = WriteOptions {
+                drop_frame: uses_drop_frame(&edl),
+                ..WriteOptions::new(29.97)
+            };
+            let cue = &entries_to_cues(&events_to_entries(&edl.events), 29.97)[0];
+            let display = millis_to_frames(cue.start_ms, &options);
+            let clear = millis_to_frames(cue.end_ms, &options);
+            let mut out = Vec::new();
+            SccFormat
+                .write(&mut out, std::slice::from_ref(cue), &options)
+                .unwrap();
+            (
+                drop_frame_timecode(display).to_string(),
+                drop_frame_timecode(clear).to_string(),
+                String::from_utf8(out).unwrap(),
+            )
+        };
+
+        // 丢帧 EDL：SCC 标签与录制时间码逐帧一致
+        let (display, clear, text) = scc("FCM: DROP FRAME\n\
+             001 AX V C 00:00:00;00 00:00:02;00 00:07:00;04 00:07:02;00\n\
+             * FROM CLIP NAME: Hi\n");
+        assert_eq!(
+            (display.as_str(), clear.as_str()),
+            ("00:07:00;04", "00:07:02;00")
+        );
+        assert!(text.ends_with("00:07:02;00\t942c 942c\n"), "{}", text);
+
+        // 29.97 不丢帧 EDL：01:00:00:00 是第 108000 帧，丢帧标签要晚 3 秒 18 帧
+        let (display, ..) = scc("FCM: NON-DROP FRAME\n\
+             001 AX V C 00:00:00:00 00:00:02:00 01:00:00:00 01:00:02:00\n\
+             * FROM CLIP NAME: Hi\n");
+        assert_eq!(display, "01:00:03;18");
+    }
+
+    #[test]
+    fn pop_on_caption() {
+        let cues = [Cue {
+            start_ms: 10_010,
+            end_ms: 12_012,
+            text: "Hi!\nMañana".to_string(),
+        }];
+        let text = write(&cues);
+
+        // 入点 300 帧，共 19 个字，从 281 帧开始加载
+        assert_eq!(
+            text,
+            "Scenarist_SCC V1.0\n\
+             \n\
+             00:00:09;11\t94ae 94ae 9420 9420 94d6 94d6 97a2 97a2 c8e9 a180 \
+             9476 9476 97a1 97a1 cd61 fe61 6e61 942f 942f\n\
+             \n\
+             00:00:12;00\t942c 942c\n"
+        );
+    }
+
+    #[test]
+    fn extended_characters_carry_a_fallback() {
+        let words = pop_on_words("Ö");
+        assert!(words.contains(&[b'O', PADDING]));
+        assert!(words.contains(&[0x13, 0x32]));
+    }
+
+    #[test]
+    fn back_to_back_captions_skip_the_clear() {
+        let cues = [
+            Cue {
+                start_ms: 1_000,
+                end_ms: 2_000,
+                text: "One".to_string(),
+            },
+            Cue {
+                start_ms: 2_000,
+                end_ms: 3_000,
+                text: "Two".to_string(),
+            },
+        ];
+        assert_eq!(write(&cues).matches("942c 942c").count(), 1);
+    }
+
+    #[test]
+    fn warns_about_long_lines_and_unsupported_characters() {
+        let cues = [Cue {
+            start_ms: 0,
+            end_ms: 1_000,
+            text: "这一行\nThis line is definitely longer than thirty-two".to_string(),
+        }];
+        let warnings = SccFormat.warnings(&cues, &WriteOptions::new(29.97));
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].contains("3 种字符"));
+        assert!(warnings[1].contains("32"));
+    }
+}
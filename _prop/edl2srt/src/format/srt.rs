@@ -0,0 +1,251 @@
+//! SubRip (.srt) 字幕。
+
+use std::io::{self, Write};
+
+use crate::error::{Error, Result};
+use crate::format::{SubtitleReader, SubtitleWriter, WriteOptions};
+use crate::i18n::text;
+use crate::model::Cue;
+use crate::t;
+
+/// SRT 读写器。
+pub struct SrtFormat;
+
+impl SubtitleWriter for SrtFormat {
+    fn name(&self) -> &'static str {
+        "srt"
+    }
+
+    fn description(&self) -> &'static str {
+        text("format.srt")
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["srt"]
+    }
+
+    fn write(&self, out: &mut dyn Write, cues: &[Cue], _options: &WriteOptions) -> io::Result<()> {
+        write_srt(out, cues)
+    }
+
+    fn streams(&self) -> bool {
+        true
+    }
+
+    fn write_iter(
+        &self,
+        mut out: &mut dyn Write,
+        cues: &mut dyn Iterator<Item = Cue>,
+        _options: &WriteOptions,
+    ) -> io::Result<()> {
+        for (index, cue) in cues.enumerate() {
+            write_srt_cue(&mut out, index + 1, &cue)?;
+        }
+        out.flush()
+    }
+}
+
+impl SubtitleReader for SrtFormat {
+    fn name(&self) -> &'static str {
+        "srt"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["srt"]
+    }
+
+    /// 第一个非空行是序号，下一行是 `-->` 时间行。
+    fn sniff(&self, sample: &[u8]) -> bool {
+        let text = String::from_utf8_lossy(sample);
+        let mut lines = text
+            .trim_start_matches('\u{feff}')
+            .lines()
+            .map(str::trim)
+            .skip_while(|l| l.is_empty());
+
+        let index_ok = lines
+            .next()
+            .is_some_and(|l| !l.is_empty() && l.bytes().all(|b| b.is_ascii_digit()));
+        index_ok && lines.next().is_some_and(|l| l.contains("-->"))
+    }
+
+    fn read(&self, bytes: &[u8]) -> Result<Vec<Cue>> {
+        let text = String::from_utf8_lossy(bytes);
+        parse_cue_blocks(text.trim_start_matches('\u{feff}'))
+    }
+}
+
+/// 写出 SRT。
+///
+/// 输出固定为 UTF-8 无 BOM。
+///
+/// Rust 的 String 本身就是 UTF-8，
+/// 只要我们不手动写入 BOM 字节，输出就是 UTF-8 无 BOM。
+pub fn write_srt<W: Write>(mut out: W, cues: &[Cue]) -> io::Result<()> {
+    for (index, cue) in cues.iter().enumerate() {
+        write_srt_cue(&mut out, index + 1, cue)?;
+    }
+
+    out.flush()
+}
+
+fn write_srt_cue<W: Write>(mut out: W, index: usize, cue: &Cue) -> io::Result<()> {
+    writeln!(out, "{}", index)?;
+    writeln!(
+        out,
+        "{} --> {}",
+        format_srt_timestamp(cue.start_ms),
+        format_srt_timestamp(cue.end_ms)
+    )?;
+    writeln!(out, "{}", cue.text)?;
+    writeln!(out)
+}
+
+/// 把毫秒格式化为 SRT 时间戳 `HH:MM:SS,mmm`。
+pub fn format_srt_timestamp(total_ms: u64) -> String {
+    let (h, m, s, ms) = split_millis(total_ms);
+    format!("{:02}:{:02}:{:02},{:03}", h, m, s, ms)
+}
+
+/// 把毫秒拆成 (时, 分, 秒, 毫秒)。
+pub(crate) fn split_millis(total_ms: u64) -> (u64, u64, u64, u64) {
+    let h = total_ms / 3_600_000;
+    let remainder = total_ms % 3_600_000;
+
+    let m = remainder / 60_000;
+    let remainder = remainder % 60_000;
+
+    let s = remainder / 1_000;
+    let ms = remainder % 1_000;
+
+    (h, m, s, ms)
+}
+
+/// 解析 `HH:MM:SS,mmm` 或 `HH:MM:SS.mmm`（WebVTT 还允许省略小时）。
+pub(crate) fn parse_timestamp(text: &str) -> Option<u64> {
+    let (clock, millis) = text.trim().split_once([',', '.'])?;
+    if millis.len() != 3 {
+        return None;
+    }
+
+    let mut fields: Vec<u64> = Vec::new();
+    for part in clock.split(':') {
+        fields.push(part.parse().ok()?);
+    }
+
+    let (h, m, s) = match fields.as_slice() {
+        [h, m, s] => (*h, *m, *s),
+        [m, s] => (0, *m, *s),
+        _ => return None,
+    };
+
+    Some(h * 3_600_000 + m * 60_000 + s * 1_000 + millis.parse::<u64>().ok()?)
+}
+
+/// 解析由空行分隔的字幕块（SRT / WebVTT 通用）。
+///
+/// 每块里第一行含 `-->` 的是时间行，它之前的行（序号或 cue id）忽略，
+/// 之后的行作为文本。没有时间行的块（例如 WebVTT 的 NOTE）直接跳过。
+pub(crate) fn parse_cue_blocks(text: &str) -> Result<Vec<Cue>> {
+    let mut cues = Vec::new();
+    let mut block: Vec<(usize, &str)> = Vec::new();
+
+    let mut lines = text.lines().enumerate().peekable();
+    while let Some((index, line)) = lines.next() {
+        if !line.trim().is_empty() {
+            block.push((index + 1, line));
+        }
+
+        let at_end = lines.peek().is_none();
+        if (line.trim().is_empty() || at_end) && !block.is_empty() {
+            if let Some(cue) = parse_block(&block)? {
+                cues.push(cue);
+            }
+            block.clear();
+        }
+    }
+
+    Ok(cues)
+}
+
+fn parse_block(block: &[(usize, &str)]) -> Result<Option<Cue>> {
+    let Some(pos) = block.iter().position(|(_, l)| l.contains("-->")) else {
+        return Ok(None);
+    };
+
+    let (line_number, time_line) = block[pos];
+    let (start, rest) = time_line.split_once("-->").unwrap_or_default();
+    // WebVTT 的时间行后面可能跟着 cue 设置，只取第一个字段
+    let end = rest.split_whitespace().next().unwrap_or("");
+
+    let (Some(start_ms), Some(end_ms)) = (parse_timestamp(start), parse_timestamp(end)) else {
+        return Err(Error::Parse {
+            line: line_number,
+            message: t!("srt.bad_time_line", time_line.trim()),
+        });
+    };
+
+    let text: Vec<&str> = block[pos + 1..].iter().map(|(_, l)| *l).collect();
+
+    Ok(Some(Cue {
+        start_ms,
+        end_ms,
+        text: text.join("\n"),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_cues() -> Vec<Cue> {
+        vec![
+            Cue {
+                start_ms: 0,
+                end_ms: 1_500,
+                text: "一".to_string(),
+            },
+            Cue {
+                start_ms: 3_661_001,
+                end_ms: 3_662_000,
+                text: "two\nlines".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn srt_timestamp_format() {
+        assert_eq!(format_srt_timestamp(0), "00:00:00,000");
+        assert_eq!(format_srt_timestamp(3_723_004), "01:02:03,004");
+    }
+
+    #[test]
+    fn write_srt_numbering_and_blank_lines() {
+        let mut out = Vec::new();
+        write_srt(&mut out, &sample_cues()[..1]).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "1\n00:00:00,000 --> 00:00:01,500\n一\n\n"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let cues = sample_cues();
+        let mut out = Vec::new();
+        SrtFormat
+            .write(&mut out, &cues, &WriteOptions::new(25.0))
+            .unwrap();
+
+        assert!(SrtFormat.sniff(&out));
+        assert_eq!(SrtFormat.read(&out).unwrap(), cues);
+    }
+
+    #[test]
+    fn bad_time_line_reports_line_number() {
+        let err = SrtFormat
+            .read(b"1\n00:00:00,000 --> 00:00:01,000\na\n\n2\nxx --> yy\nb\n")
+            .unwrap_err();
+        assert!(matches!(err, Error::Parse { line: 6, .. }));
+    }
+}
@@ -0,0 +1,834 @@
+//! EBU STL（EBU Tech 3264）二进制字幕。
+//!
+//! 文件由一个 1024 字节的 GSI 块（总体信息）和若干 128 字节的 TTI 块（每条字幕）组成：
+//! - 磁盘格式只有 `STL25.01` 和 `STL30.01` 两种，读写都按磁盘格式的 25 / 30 帧换算时间，
+//!   29.97 帧的字幕也按 30 帧计时
+//! - TTI 中的入出点是“时、分、秒、帧”四个字节，小时最大为 23
+//! - 文本使用拉丁字符表（ISO 6937，带重音的字母写成“重音符 + 字母”两个字节），
+//!   每行前加双倍高度和开框控制码，行与行之间用 0x8A 分隔
+//! - 单条字幕文本超过 112 字节时拆成多个扩展块
+//!
+//! 拉丁字符表无法表示的字符（例如中文）写成 `?`，写出前会给出提示。
+
+use std::io::{self, Write};
+
+use crate::error::{Error, Result};
+use crate::format::{SubtitleReader, SubtitleWriter, WriteOptions};
+use crate::fps::nominal_rate;
+use crate::i18n::text;
+use crate::model::{Cue, Timecode};
+use crate::t;
+
+const GSI_SIZE: usize = 1024;
+const TTI_SIZE: usize = 128;
+const TEXT_FIELD_SIZE: usize = 112;
+
+/// 文本字段中的换行
+const NEWLINE: u8 = 0x8A;
+
+/// 文本字段末尾的填充字节
+const FILLER: u8 = 0x8F;
+
+/// 每行开头的控制码：双倍高度、开框（两次）
+const LINE_PREFIX: [u8; 3] = [0x0D, 0x0B, 0x0B];
+
+/// 最后一个（或唯一一个）扩展块的 EBN
+const LAST_BLOCK: u8 = 0xFF;
+
+/// 每行最多显示的字符数（GSI 中的 MNC）
+const MAX_CHARS_PER_ROW: usize = 40;
+
+/// 字幕编号 SN 是 16 位，最多 65536 条
+const MAX_SUBTITLES: usize = u16::MAX as usize + 1;
+
+/// GSI 中 TNB（TTI 块数）只有 5 位数字
+const MAX_BLOCKS: usize = 99_999;
+
+/// ISO 6937 的组合重音符，后面跟基本字母。
+const GRAVE: u8 = 0xC1;
+const ACUTE: u8 = 0xC2;
+const CIRCUMFLEX: u8 = 0xC3;
+const TILDE: u8 = 0xC4;
+const MACRON: u8 = 0xC5;
+const BREVE: u8 = 0xC6;
+const DOT: u8 = 0xC7;
+const DIAERESIS: u8 = 0xC8;
+const RING: u8 = 0xCA;
+const CEDILLA: u8 = 0xCB;
+const DOUBLE_ACUTE: u8 = 0xCD;
+const OGONEK: u8 = 0xCE;
+const CARON: u8 = 0xCF;
+
+/// 带重音的字母：(字符, 重音符, 基本字母)
+const COMPOSED: &[(char, u8, u8)] = &[
+    ('À', GRAVE, b'A'),
+    ('Á', ACUTE, b'A'),
+    ('Â', CIRCUMFLEX, b'A'),
+    ('Ã', TILDE, b'A'),
+    ('Ä', DIAERESIS, b'A'),
+    ('Å', RING, b'A'),
+    ('Ç', CEDILLA, b'C'),
+    ('È', GRAVE, b'E'),
+    ('É', ACUTE, b'E'),
+    ('Ê', CIRCUMFLEX, b'E'),
+    ('Ë', DIAERESIS, b'E'),
+    ('Ì', GRAVE, b'I'),
+    ('Í', ACUTE, b'I'),
+    ('Î', CIRCUMFLEX, b'I'),
+    ('Ï', DIAERESIS, b'I'),
+    ('Ñ', TILDE, b'N'),
+    ('Ò', GRAVE, b'O'),
+    ('Ó', ACUTE, b'O'),
+    ('Ô', CIRCUMFLEX, b'O'),
+    ('Õ', TILDE, b'O'),
+    ('Ö', DIAERESIS, b'O'),
+    ('Ù', GRAVE, b'U'),
+    ('Ú', ACUTE, b'U'),
+    ('Û', CIRCUMFLEX, b'U'),
+    ('Ü', DIAERESIS, b'U'),
+    ('Ý', ACUTE, b'Y'),
+    ('à', GRAVE, b'a'),
+    ('á', ACUTE, b'a'),
+    ('â', CIRCUMFLEX, b'a'),
+    ('ã', TILDE, b'a'),
+    ('ä', DIAERESIS, b'a'),
+    ('å', RING, b'a'),
+    ('ç', CEDILLA, b'c'),
+    ('è', GRAVE, b'e'),
+    ('é', ACUTE, b'e'),
+    ('ê', CIRCUMFLEX, b'e'),
+    ('ë', DIAERESIS, b'e'),
+    ('ì', GRAVE, b'i'),
+    ('í', ACUTE, b'i'),
+    ('î', CIRCUMFLEX, b'i'),
+    ('ï', DIAERESIS, b'i'),
+    ('ñ', TILDE, b'n'),
+    ('ò', GRAVE, b'o'),
+    ('ó', ACUTE, b'o'),
+    ('ô', CIRCUMFLEX, b'o'),
+    ('õ', TILDE, b'o'),
+    ('ö', DIAERESIS, b'o'),
+    ('ù', GRAVE, b'u'),
+    ('ú', ACUTE, b'u'),
+    ('û', CIRCUMFLEX, b'u'),
+    ('ü', DIAERESIS, b'u'),
+    ('ý', ACUTE, b'y'),
+    ('ÿ', DIAERESIS, b'y'),
+    ('Ā', MACRON, b'A'),
+    ('ā', MACRON, b'a'),
+    ('Ă', BREVE, b'A'),
+    ('ă', BREVE, b'a'),
+    ('Ą', OGONEK, b'A'),
+    ('ą', OGONEK, b'a'),
+    ('Ć', ACUTE, b'C'),
+    ('ć', ACUTE, b'c'),
+    ('Č', CARON, b'C'),
+    ('č', CARON, b'c'),
+    ('Ď', CARON, b'D'),
+    ('ď', CARON, b'd'),
+    ('Ē', MACRON, b'E'),
+    ('ē', MACRON, b'e'),
+    ('Ė', DOT, b'E'),
+    ('ė', DOT, b'e'),
+    ('Ę', OGONEK, b'E'),
+    ('ę', OGONEK, b'e'),
+    ('Ě', CARON, b'E'),
+    ('ě', CARON, b'e'),
+    ('Ğ', BREVE, b'G'),
+    ('ğ', BREVE, b'g'),
+    ('Ī', MACRON, b'I'),
+    ('ī', MACRON, b'i'),
+    ('Į', OGONEK, b'I'),
+    ('į', OGONEK, b'i'),
+    ('İ', DOT, b'I'),
+    ('Ĺ', ACUTE, b'L'),
+    ('ĺ', ACUTE, b'l'),
+    ('Ľ', CARON, b'L'),
+    ('ľ', CARON, b'l'),
+    ('Ń', ACUTE, b'N'),
+    ('ń', ACUTE, b'n'),
+    ('Ņ', CEDILLA, b'N'),
+    ('ņ', CEDILLA, b'n'),
+    ('Ň', CARON, b'N'),
+    ('ň', CARON, b'n'),
+    ('Ő', DOUBLE_ACUTE, b'O'),
+    ('ő', DOUBLE_ACUTE, b'o'),
+    ('Ŕ', ACUTE, b'R'),
+    ('ŕ', ACUTE, b'r'),
+    ('Ř', CARON, b'R'),
+    ('ř', CARON, b'r'),
+    ('Ś', ACUTE, b'S'),
+    ('ś', ACUTE, b's'),
+    ('Ş', CEDILLA, b'S'),
+    ('ş', CEDILLA, b's'),
+    ('Š', CARON, b'S'),
+    ('š', CARON, b's'),
+    ('Ţ', CEDILLA, b'T'),
+    ('ţ', CEDILLA, b't'),
+    ('Ť', CARON, b'T'),
+    ('ť', CARON, b't'),
+    ('Ū', MACRON, b'U'),
+    ('ū', MACRON, b'u'),
+    ('Ů', RING, b'U'),
+    ('ů', RING, b'u'),
+    ('Ű', DOUBLE_ACUTE, b'U'),
+    ('ű', DOUBLE_ACUTE, b'u'),
+    ('Ų', OGONEK, b'U'),
+    ('ų', OGONEK, b'u'),
+    ('Ź', ACUTE, b'Z'),
+    ('ź', ACUTE, b'z'),
+    ('Ż', DOT, b'Z'),
+    ('ż', DOT, b'z'),
+    ('Ž', CARON, b'Z'),
+    ('ž', CARON, b'z'),
+];
+
+/// 单字节的特殊字符
+const SPECIAL: &[(char, u8)] = &[
+    ('$', 0xA4),
+    ('¡', 0xA1),
+    ('¢', 0xA2),
+    ('£', 0xA3),
+    ('¥', 0xA5),
+    ('§', 0xA7),
+    ('‘', 0xA9),
+    ('“', 0xAA),
+    ('«', 0xAB),
+    ('°', 0xB0),
+    ('±', 0xB1),
+    ('²', 0xB2),
+    ('³', 0xB3),
+    ('×', 0xB4),
+    ('µ', 0xB5),
+    ('¶', 0xB6),
+    ('·', 0xB7),
+    ('÷', 0xB8),
+    ('’', 0xB9),
+    ('”', 0xBA),
+    ('»', 0xBB),
+    ('¼', 0xBC),
+    ('½', 0xBD),
+    ('¾', 0xBE),
+    ('¿', 0xBF),
+    ('—', 0xD0),
+    ('Æ', 0xE1),
+    ('Đ', 0xE2),
+    ('Ħ', 0xE4),
+    ('Ł', 0xE8),
+    ('Ø', 0xE9),
+    ('Œ', 0xEA),
+    ('Þ', 0xEC),
+    ('Ŋ', 0xEE),
+    ('æ', 0xF1),
+    ('đ', 0xF2),
+    ('ð', 0xF3),
+    ('ħ', 0xF4),
+    ('ı', 0xF5),
+    ('ł', 0xF8),
+    ('ø', 0xF9),
+    ('œ', 0xFA),
+    ('ß', 0xFB),
+    ('þ', 0xFC),
+    ('ŋ', 0xFE),
+];
+
+/// EBU STL 读写器。
+pub struct StlFormat;
+
+impl SubtitleWriter for StlFormat {
+    fn name(&self) -> &'static str {
+        "stl"
+    }
+
+    fn description(&self) -> &'static str {
+        text("format.stl")
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["stl"]
+    }
+
+    fn write(&self, out: &mut dyn Write, cues: &[Cue], options: &WriteOptions) -> io::Result<()> {
+        let disk_format = disk_format(options.fps)
+            .and_then(|disk_format| check_hours(cues, disk_format).map(|_| disk_format))
+            .and_then(|disk_format| check_counts(cues).map(|_| disk_format))
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err.to_string()))?;
+        let disk_fps = disk_fps(disk_format);
+
+        let mut blocks = Vec::new();
+        for (index, cue) in cues.iter().enumerate() {
+            blocks.extend(tti_blocks(index as u16, cue, disk_fps));
+        }
+
+        let first_in = cues
+            .first()
+            .map(|c| Timecode::from_millis(c.start_ms, disk_fps, false));
+        out.write_all(&gsi_block(
+            disk_format,
+            options.title.as_deref(),
+            blocks.len(),
+            cues.len(),
+            first_in,
+        ))?;
+        for block in &blocks {
+            out.write_all(block)?;
+        }
+
+        out.flush()
+    }
+
+    fn warnings(&self, cues: &[Cue], options: &WriteOptions) -> Vec<String> {
+        let mut warnings = Vec::new();
+
+        if let Err(err) =
+            disk_format(options.fps).and_then(|disk_format| check_hours(cues, disk_format))
+        {
+            warnings.push(err.to_string());
+        }
+        if let Err(err) = check_counts(cues) {
+            warnings.push(err.to_string());
+        }
+
+        let mut unsupported: Vec<char> = cues
+            .iter()
+            .flat_map(|c| c.text.chars())
+            .filter(|&c| c != '\n' && encode_char(c).is_none())
+            .collect();
+        unsupported.sort_unstable();
+        unsupported.dedup();
+        if !unsupported.is_empty() {
+            let sample: String = unsupported.iter().take(20).collect();
+            warnings.push(t!("stl.unsupported_chars", unsupported.len(), sample));
+        }
+
+        for (index, cue) in cues.iter().enumerate() {
+            if cue
+                .text
+                .lines()
+                .any(|l| l.chars().count() > MAX_CHARS_PER_ROW)
+            {
+                warnings.push(t!("stl.line_too_long", index + 1, MAX_CHARS_PER_ROW));
+            }
+        }
+
+        warnings
+    }
+}
+
+impl SubtitleReader for StlFormat {
+    fn name(&self) -> &'static str {
+        "stl"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["stl"]
+    }
+
+    fn sniff(&self, sample: &[u8]) -> bool {
+        matches!(sample.get(3..11), Some(b"STL25.01") | Some(b"STL30.01"))
+    }
+
+    /// 时间按磁盘格式的帧率换算，与写出时一致（STL30.01 按 30 帧）。
+    fn read(&self, bytes: &[u8]) -> Result<Vec<Cue>> {
+        let document = parse_stl(bytes)?;
+        Ok(document.cues(disk_fps(&document.disk_format)))
+    }
+}
+
+/// 解析后的 STL 文件。
+#[derive(Debug, Clone, PartialEq)]
+pub struct StlDocument {
+    /// `STL25.01` 或 `STL30.01`
+    pub disk_format: String,
+
+    /// GSI 中的原始节目名（OPT）
+    pub title: String,
+
+    pub subtitles: Vec<StlSubtitle>,
+}
+
+/// 一条字幕（已合并扩展块）。
+#[derive(Debug, Clone, PartialEq)]
+pub struct StlSubtitle {
+    pub number: u16,
+    pub start: Timecode,
+    pub end: Timecode,
+    pub text: String,
+}
+
+impl StlDocument {
+    /// 按帧率换算为 [`Cue`]。
+    pub fn cues(&self, fps: f64) -> Vec<Cue> {
+        self.subtitles
+            .iter()
+            .map(|s| Cue {
+                start_ms: s.start.to_millis(fps),
+                end_ms: s.end.to_millis(fps),
+                text: s.text.clone(),
+            })
+            .collect()
+    }
+}
+
+/// 解析 STL 文件，保留时间码标签，便于逐帧核对。
+pub fn parse_stl(bytes: &[u8]) -> Result<StlDocument> {
+    if bytes.len() < GSI_SIZE || !StlFormat.sniff(bytes) {
+        return Err(Error::Parse {
+            line: 0,
+            message: t!("stl.not_stl"),
+        });
+    }
+
+    let disk_format = String::from_utf8_lossy(&bytes[3..11]).to_string();
+    let title = decode_text(&bytes[16..48]).trim_end().to_string();
+
+    let body = &bytes[GSI_SIZE..];
+    if !body.len().is_multiple_of(TTI_SIZE) {
+        return Err(Error::Parse {
+            line: 0,
+            message: t!("stl.bad_tti_length", body.len()),
+        });
+    }
+
+    let mut subtitles: Vec<StlSubtitle> = Vec::new();
+    let mut pending: Option<(u16, Timecode, Timecode, Vec<u8>)> = None;
+
+    for block in body.chunks(TTI_SIZE) {
+        let number = u16::from_le_bytes([block[1], block[2]]);
+        let extension = block[3];
+        let comment = block[15] != 0;
+
+        // 用户数据块和注释块不是字幕
+        if extension == 0xFE || comment {
+            continue;
+        }
+
+        let timecode = |b: &[u8]| Timecode::new(b[0] as u32, b[1] as u32, b[2] as u32, b[3] as u32);
+        let text = &block[16..16 + TEXT_FIELD_SIZE];
+
+        match &mut pending {
+            Some((n, _, _, bytes)) if *n == number => bytes.extend_from_slice(text),
+            _ => {
+                pending = Some((
+                    number,
+                    timecode(&block[5..9]),
+                    timecode(&block[9..13]),
+                    text.to_vec(),
+                ))
+            }
+        }
+
+        if extension == LAST_BLOCK
+            && let Some((number, start, end, bytes)) = pending.take()
+        {
+            subtitles.push(StlSubtitle {
+                number,
+                start,
+                end,
+                text: decode_text(&bytes),
+            });
+        }
+    }
+
+    Ok(StlDocument {
+        disk_format,
+        title,
+        subtitles,
+    })
+}
+
+/// 按帧率选择磁盘格式（DFC）。
+///
+/// 50 / 59.94 / 60 帧按一半的帧率写出。
+fn disk_format(fps: f64) -> Result<&'static str> {
+    let rate = match nominal_rate(fps) {
+        50 | 60 => nominal_rate(fps) / 2,
+        rate => rate,
+    };
+
+    match rate {
+        25 => Ok("STL25.01"),
+        30 => Ok("STL30.01"),
+        _ => Err(Error::Invalid(t!("stl.unsupported_fps", fps))),
+    }
+}
+
+/// 磁盘格式的时间码帧率，读写共用。
+fn disk_fps(disk_format: &str) -> f64 {
+    if disk_format == "STL25.01" {
+        25.0
+    } else {
+        30.0
+    }
+}
+
+/// TTI 的小时只有 0~23，第一条超出的字幕报错。
+fn check_hours(cues: &[Cue], disk_format: &str) -> Result<()> {
+    let fps = disk_fps(disk_format);
+    match cues
+        .iter()
+        .map(|c| Timecode::from_millis(c.end_ms.max(c.start_ms), fps, false))
+        .enumerate()
+        .find(|(_, tc)| tc.hours > 23)
+    {
+        Some((index, tc)) => Err(Error::Invalid(t!("stl.past_24_hours", index + 1, tc))),
+        None => Ok(()),
+    }
+}
+
+/// 字幕条数和 TTI 块数不能超出 SN 与 TNB / TNS 字段能表示的范围。
+fn check_counts(cues: &[Cue]) -> Result<()> {
+    if cues.len() > MAX_SUBTITLES {
+        return Err(Error::Invalid(t!(
+            "stl.too_many_subtitles",
+            cues.len(),
+            MAX_SUBTITLES
+        )));
+    }
+
+    let blocks: usize = cues.iter().map(|c| block_count(&tti_text(c))).sum();
+    if blocks > MAX_BLOCKS {
+        return Err(Error::Invalid(t!(
+            "stl.too_many_blocks",
+            blocks,
+            MAX_BLOCKS
+        )));
+    }
+    Ok(())
+}
+
+fn gsi_block(
+    disk_format: &str,
+    title: Option<&str>,
+    block_count: usize,
+    subtitle_count: usize,
+    first_in: Option<Timecode>,
+) -> Vec<u8> {
+    let mut gsi = vec![b' '; GSI_SIZE];
+    let mut put = |offset: usize, len: usize, bytes: &[u8]| {
+        let n = bytes.len().min(len);
+        gsi[offset..offset + n].copy_from_slice(&bytes[..n]);
+    };
+
+    let today = chrono::Local::now().format("%y%m%d").to_string();
+    let first_in = first_in.unwrap_or(Timecode::new(0, 0, 0, 0));
+    let hhmmssff = |tc: Timecode| {
+        format!(
+            "{:02}{:02}{:02}{:02}",
+            tc.hours, tc.minutes, tc.seconds, tc.frames
+        )
+    };
+
+    put(0, 3, b"850"); // CPN 代码页
+    put(3, 8, disk_format.as_bytes()); // DFC
+    put(11, 1, b"1"); // DSC 图文电视 level 1
+    put(12, 2, b"00"); // CCT 拉丁字符表
+    put(14, 2, b"00"); // LC 语言未指定
+    if let Some(title) = title {
+        put(16, 32, &encode_text(title)); // OPT
+    }
+    put(224, 6, today.as_bytes()); // CD
+    put(230, 6, today.as_bytes()); // RD
+    put(236, 2, b"00"); // RN
+    put(238, 5, format!("{:05}", block_count).as_bytes()); // TNB
+    put(243, 5, format!("{:05}", subtitle_count).as_bytes()); // TNS
+    put(248, 3, b"001"); // TNG
+    put(251, 2, format!("{:02}", MAX_CHARS_PER_ROW).as_bytes()); // MNC
+    put(253, 2, b"23"); // MNR
+    put(255, 1, b"1"); // TCS
+    // TCP 节目起点取第一条字幕所在的整点
+    put(
+        256,
+        8,
+        hhmmssff(Timecode::new(first_in.hours, 0, 0, 0)).as_bytes(),
+    );
+    put(264, 8, hhmmssff(first_in).as_bytes()); // TCF
+    put(272, 1, b"1"); // TND
+    put(273, 1, b"1"); // DSN
+
+    gsi
+}
+
+/// 一条字幕在 TTI 文本字段中的字节：每行加双倍高度前缀，行间用换行码。
+fn tti_text(cue: &Cue) -> Vec<u8> {
+    let mut text = Vec::new();
+    for (i, line) in cue.text.lines().enumerate() {
+        if i > 0 {
+            text.push(NEWLINE);
+        }
+        text.extend_from_slice(&LINE_PREFIX);
+        text.extend(encode_text(line));
+    }
+    text
+}
+
+/// 文本要占用的 TTI 块数，空文本也占一块。
+fn block_count(text: &[u8]) -> usize {
+    text.len().div_ceil(TEXT_FIELD_SIZE).max(1)
+}
+
+/// 一条字幕的 TTI 块，文本过长时拆成多个扩展块。
+fn tti_blocks(number: u16, cue: &Cue, fps: f64) -> Vec<[u8; TTI_SIZE]> {
+    let lines: Vec<&str> = cue.text.lines().collect();
+    let text = tti_text(cue);
+
+    let start = Timecode::from_millis(cue.start_ms, fps, false);
+    let end = Timecode::from_millis(cue.end_ms, fps, false);
+    let chunks: Vec<&[u8]> = if text.is_empty() {
+        vec![&[]]
+    } else {
+        text.chunks(TEXT_FIELD_SIZE).collect()
+    };
+
+    // 双倍高度每行占两行，最后一行落在第 22 行
+    let rows = lines.len().max(1) as u8;
+    let vertical_position = 22u8.saturating_sub(2 * (rows - 1)).max(1);
+
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(i, chunk)| {
+            let mut block = [FILLER; TTI_SIZE];
+            block[0] = 0; // SGN
+            block[1..3].copy_from_slice(&number.to_le_bytes()); // SN
+            block[3] = if i + 1 == chunks.len() {
+                LAST_BLOCK
+            } else {
+                i as u8
+            }; // EBN
+            block[4] = 0; // CS
+            block[5..9].copy_from_slice(&timecode_bytes(start));
+            block[9..13].copy_from_slice(&timecode_bytes(end));
+            block[13] = vertical_position; // VP
+            block[14] = 2; // JC 居中
+            block[15] = 0; // CF
+            block[16..16 + chunk.len()].copy_from_slice(chunk);
+            block
+        })
+        .collect()
+}
+
+fn timecode_bytes(tc: Timecode) -> [u8; 4] {
+    [
+        tc.hours as u8,
+        tc.minutes as u8,
+        tc.seconds as u8,
+        tc.frames as u8,
+    ]
+}
+
+/// 编码一个字符，无法表示时返回 None。
+fn encode_char(c: char) -> Option<Vec<u8>> {
+    if let Some(&(_, byte)) = SPECIAL.iter().find(|(ch, _)| *ch == c) {
+        return Some(vec![byte]);
+    }
+    if (' '..='~').contains(&c) {
+        return Some(vec![c as u8]);
+    }
+    COMPOSED
+        .iter()
+        .find(|(ch, _, _)| *ch == c)
+        .map(|&(_, accent, base)| vec![accent, base])
+}
+
+fn encode_text(text: &str) -> Vec<u8> {
+    text.chars()
+        .flat_map(|c| encode_char(c).unwrap_or_else(|| vec![b'?']))
+        .collect()
+}
+
+/// 解码文本字段：跳过控制码和填充字节，0x8A 视为换行。
+fn decode_text(bytes: &[u8]) -> String {
+    let mut text = String::new();
+    let mut iter = bytes.iter().copied().peekable();
+
+    while let Some(byte) = iter.next() {
+        match byte {
+            NEWLINE => text.push('\n'),
+            0x20..=0x7E if byte != b'$' => text.push(byte as char),
+            0xC1..=0xCF => {
+                if let Some(base) = iter.next()
+                    && let Some(&(c, _, _)) = COMPOSED
+                        .iter()
+                        .find(|(_, accent, b)| *accent == byte && *b == base)
+                {
+                    text.push(c);
+                }
+            }
+            _ => {
+                if let Some(&(c, _)) = SPECIAL.iter().find(|(_, b)| *b == byte) {
+                    text.push(c);
+                }
+            }
+        }
+    }
+
+    text.trim_end_matches('\n').to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cues() -> Vec<Cue> {
+        vec![
+            Cue {
+                start_ms: 36_000_000,
+                end_ms: 36_002_480,
+                text: "Café à Zürich\n“Łódź” £5".to_string(),
+            },
+            Cue {
+                start_ms: 36_003_000,
+                end_ms: 36_004_000,
+                text: "A long line that needs more than one hundred and twelve bytes \
+                       so that it spills into an extension block\nof its own, ok?"
+                    .to_string(),
+            },
+        ]
+    }
+
+    fn write(cues: &[Cue], fps: f64) -> Vec<u8> {
+        let mut options = WriteOptions::new(fps);
+        options.title = Some("Épisode 1".to_string());
+        let mut out = Vec::new();
+        StlFormat.write(&mut out, cues, &options).unwrap();
+        out
+    }
+
+    #[test]
+    fn gsi_and_tti_layout() {
+        let bytes = write(&cues(), 25.0);
+
+        // GSI + 1 + 2 个 TTI 块
+        assert_eq!(bytes.len(), GSI_SIZE + 3 * TTI_SIZE);
+        assert_eq!(&bytes[0..3], b"850");
+        assert_eq!(&bytes[3..11], b"STL25.01");
+        assert_eq!(&bytes[16..19], &[ACUTE, b'E', b'p']);
+        assert_eq!(&bytes[238..243], b"00003");
+        assert_eq!(&bytes[243..248], b"00002");
+        assert_eq!(&bytes[256..264], b"10000000");
+        assert_eq!(&bytes[264..272], b"10000000");
+
+        let first = &bytes[GSI_SIZE..GSI_SIZE + TTI_SIZE];
+        assert_eq!(first[3], LAST_BLOCK);
+        assert_eq!(&first[5..9], &[10, 0, 0, 0]);
+        assert_eq!(&first[9..13], &[10, 0, 2, 12]);
+        assert_eq!(first[13], 20);
+
+        let second = &bytes[GSI_SIZE + TTI_SIZE..];
+        assert_eq!((second[3], second[TTI_SIZE + 3]), (0, LAST_BLOCK));
+        assert_eq!(u16::from_le_bytes([second[1], second[2]]), 1);
+    }
+
+    #[test]
+    fn round_trip() {
+        let bytes = write(&cues(), 25.0);
+        assert!(StlFormat.sniff(&bytes));
+
+        let document = parse_stl(&bytes).unwrap();
+        assert_eq!(document.title, "Épisode 1");
+        assert_eq!(document.subtitles[0].end, Timecode::new(10, 0, 2, 12));
+        assert_eq!(StlFormat.read(&bytes).unwrap(), cues());
+    }
+
+    #[test]
+    fn frame_rates() {
+        assert_eq!(&write(&cues(), 29.97)[3..11], b"STL30.01");
+        assert_eq!(&write(&cues(), 50.0)[3..11], b"STL25.01");
+
+        let mut out = Vec::new();
+        assert!(
+            StlFormat
+                .write(&mut out, &cues(), &WriteOptions::new(24.0))
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn stl30_reads_back_what_it_writes() {
+        // 29.97 与 30 帧都写成 STL30.01，读回时按同一帧率换算
+        let cues = [Cue {
+            start_ms: 3_600_967,
+            end_ms: 3_601_500,
+            text: "ok".to_string(),
+        }];
+        for fps in [29.97, 30.0, 59.94] {
+            let bytes = write(&cues, fps);
+            let document = parse_stl(&bytes).unwrap();
+            assert_eq!(document.subtitles[0].start, Timecode::new(1, 0, 0, 29));
+            assert_eq!(document.subtitles[0].end, Timecode::new(1, 0, 1, 15));
+            assert_eq!(StlFormat.read(&bytes).unwrap(), cues);
+        }
+    }
+
+    #[test]
+    fn rejects_more_subtitles_than_the_format_can_number() {
+        let cue = Cue {
+            start_ms: 0,
+            end_ms: 1_000,
+            text: "x".to_string(),
+        };
+        let options = WriteOptions::new(25.0);
+
+        let cues = vec![cue.clone(); MAX_SUBTITLES];
+        assert!(check_counts(&cues).is_ok());
+
+        let cues = vec![cue; MAX_SUBTITLES + 1];
+        let warnings = StlFormat.warnings(&cues, &options);
+        assert!(
+            warnings.iter().any(|w| w.contains("65537")),
+            "{:?}",
+            warnings
+        );
+
+        let mut out = Vec::new();
+        let err = StlFormat.write(&mut out, &cues, &options).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(out.is_empty());
+
+        // 长文本拆成多个扩展块，一起计入 TNB
+        assert_eq!(block_count(&[]), 1);
+        assert_eq!(block_count(&[0; TEXT_FIELD_SIZE + 1]), 2);
+    }
+
+    #[test]
+    fn rejects_timecodes_past_24_hours() {
+        let cues = [
+            Cue {
+                start_ms: 0,
+                end_ms: 1_000,
+                text: "ok".to_string(),
+            },
+            Cue {
+                start_ms: 86_399_000,
+                end_ms: 86_401_000,
+                text: "late".to_string(),
+            },
+        ];
+        let options = WriteOptions::new(25.0);
+
+        let warnings = StlFormat.warnings(&cues, &options);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("第 2 条") && warnings[0].contains("24:00:01:00"));
+
+        let mut out = Vec::new();
+        let err = StlFormat.write(&mut out, &cues, &options).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn warns_about_unsupported_characters() {
+        let cues = [Cue {
+            start_ms: 0,
+            end_ms: 1_000,
+            text: "你好 hi".to_string(),
+        }];
+        let warnings = StlFormat.warnings(&cues, &WriteOptions::new(25.0));
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].contains("2 种字符"));
+
+        let document = parse_stl(&write(&cues, 25.0)).unwrap();
+        assert_eq!(document.subtitles[0].text, "?? hi");
+    }
+}
@@ -0,0 +1,146 @@
+//! WebVTT (.vtt) 字幕。
+
+use std::io::{self, Write};
+
+use crate::error::Result;
+use crate::format::srt::{parse_cue_blocks, split_millis};
+use crate::format::{SubtitleReader, SubtitleWriter, WriteOptions};
+use crate::i18n::text;
+use crate::model::Cue;
+
+/// WebVTT 读写器。
+pub struct VttFormat;
+
+impl SubtitleWriter for VttFormat {
+    fn name(&self) -> &'static str {
+        "vtt"
+    }
+
+    fn description(&self) -> &'static str {
+        text("format.vtt")
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["vtt"]
+    }
+
+    fn write(&self, out: &mut dyn Write, cues: &[Cue], options: &WriteOptions) -> io::Result<()> {
+        self.write_iter(out, &mut cues.iter().cloned(), options)
+    }
+
+    fn streams(&self) -> bool {
+        true
+    }
+
+    fn write_iter(
+        &self,
+        out: &mut dyn Write,
+        cues: &mut dyn Iterator<Item = Cue>,
+        _options: &WriteOptions,
+    ) -> io::Result<()> {
+        writeln!(out, "WEBVTT")?;
+        writeln!(out)?;
+
+        for cue in cues {
+            writeln!(
+                out,
+                "{} --> {}",
+                format_vtt_timestamp(cue.start_ms),
+                format_vtt_timestamp(cue.end_ms)
+            )?;
+            writeln!(out, "{}", cue.text)?;
+            writeln!(out)?;
+        }
+
+        out.flush()
+    }
+}
+
+impl SubtitleReader for VttFormat {
+    fn name(&self) -> &'static str {
+        "vtt"
+    }
+
+    fn extensions(&self) -> &'static [&'static str] {
+        &["vtt"]
+    }
+
+    fn sniff(&self, sample: &[u8]) -> bool {
+        let sample = sample.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(sample);
+        sample.starts_with(b"WEBVTT")
+    }
+
+    fn read(&self, bytes: &[u8]) -> Result<Vec<Cue>> {
+        let text = String::from_utf8_lossy(bytes);
+        let body = text.trim_start_matches('\u{feff}');
+        // 跳过 WEBVTT 头部块
+        let body = body.split_once("\n\n").map(|(_, b)| b).unwrap_or("");
+        parse_cue_blocks(body)
+    }
+}
+
+/// 把毫秒格式化为 WebVTT 时间戳 `HH:MM:SS.mmm`。
+pub fn format_vtt_timestamp(total_ms: u64) -> String {
+    let (h, m, s, ms) = split_millis(total_ms);
+    format!("{:02}:{:02}:{:02}.{:03}", h, m, s, ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_header_and_dot_timestamps() {
+        let cues = [Cue {
+            start_ms: 1_001,
+            end_ms: 2_000,
+            text: "hi".to_string(),
+        }];
+        let mut out = Vec::new();
+        VttFormat
+            .write(&mut out, &cues, &WriteOptions::new(25.0))
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "WEBVTT\n\n00:00:01.001 --> 00:00:02.000\nhi\n\n"
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let cues = vec![
+            Cue {
+                start_ms: 0,
+                end_ms: 500,
+                text: "第一行\n第二行".to_string(),
+            },
+            Cue {
+                start_ms: 7_200_000,
+                end_ms: 7_201_234,
+                text: "end".to_string(),
+            },
+        ];
+        let mut out = Vec::new();
+        VttFormat
+            .write(&mut out, &cues, &WriteOptions::new(25.0))
+            .unwrap();
+
+        assert!(VttFormat.sniff(&out));
+        assert_eq!(VttFormat.read(&out).unwrap(), cues);
+    }
+
+    #[test]
+    fn reads_short_timestamps_and_skips_notes() {
+        let text = "WEBVTT\n\nNOTE 备注\n\nid-1\n00:01.000 --> 00:02.500 align:start\nx\n";
+        let cues = VttFormat.read(text.as_bytes()).unwrap();
+        assert_eq!(
+            cues,
+            vec![Cue {
+                start_ms: 1_000,
+                end_ms: 2_500,
+                text: "x".to_string(),
+            }]
+        );
+    }
+}
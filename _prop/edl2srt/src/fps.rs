@@ -0,0 +1,410 @@
+//! 帧率推断与校验。
+//!
+//! EDL 本身通常不写帧率，但可以从内容里找到线索：
+//! - 录制时间码中帧字段的最大值（帧字段总是小于名义帧率）
+//! - `FCM: DROP FRAME` 或 `;` 分隔符（丢帧只存在于 29.97 / 59.94）
+//! - 标题里的 "25fps" 之类的提示，以及注释中明确的 `FRAME RATE:` / `FPS:` 键
+//! - JSON 输入里记录的 `fps`
+//!
+//! 源时间码可能是素材自己的帧率，不参与推断；片段名、源文件路径里的 "60fps"
+//! 说的也是素材，同样不算提示。
+//!
+//! 帧字段只能排除帧率，不能证明帧率：最大帧为 24 时 25、30、50 都说得通。
+//! 因此没有提示、也没有丢帧迹象时不会擅自选择，而是把候选列出来交给用户决定。
+
+use crate::error::{Error, Result};
+use crate::model::{Edl, EdlEvent, Timecode, Warning};
+use crate::t;
+
+/// 常见帧率，按从小到大排列。
+pub const STANDARD_FRAME_RATES: [f64; 8] = [23.976, 24.0, 25.0, 29.97, 30.0, 50.0, 59.94, 60.0];
+
+/// 推断过程中收集到的线索。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FpsEvidence {
+    /// 录制时间码中帧字段的最大值
+    pub max_frame: Option<u32>,
+
+    /// 参与统计的时间码数量
+    pub timecode_count: usize,
+
+    /// 是否有丢帧迹象（FCM 或 `;` 分隔符）
+    pub drop_frame: bool,
+
+    /// 标题、注释中的帧率键或 JSON 中明确给出的帧率
+    pub hints: Vec<f64>,
+}
+
+/// 推断结果。
+#[derive(Debug, Clone, PartialEq)]
+pub struct FpsInference {
+    pub evidence: FpsEvidence,
+
+    /// 与线索相符的候选帧率
+    pub candidates: Vec<f64>,
+
+    /// 唯一能确定的帧率；有歧义时为 None
+    pub chosen: Option<f64>,
+}
+
+impl FpsInference {
+    /// 取出确定的帧率，有歧义时返回列出候选的错误。
+    pub fn resolve(&self) -> Result<f64> {
+        if let Some(fps) = self.chosen {
+            return Ok(fps);
+        }
+
+        let reason = match (self.evidence.max_frame, self.candidates.is_empty()) {
+            (None, _) => t!("fps.no_timecodes"),
+            (Some(max), true) => t!("fps.no_candidate", max),
+            (Some(max), false) => t!("fps.ambiguous", max),
+        };
+
+        Err(Error::FrameRate {
+            reason,
+            candidates: self.candidates.clone(),
+        })
+    }
+}
+
+/// 帧率是否可用：有限且大于 0。
+pub fn is_valid_fps(fps: f64) -> bool {
+    fps.is_finite() && fps > 0.0
+}
+
+/// 名义帧率，即帧字段的取值上限，例如 29.97 -> 30。
+pub fn nominal_rate(fps: f64) -> u32 {
+    fps.round() as u32
+}
+
+/// 从解析结果推断帧率。
+pub fn infer_fps(edl: &Edl) -> FpsInference {
+    let evidence = collect_evidence(edl);
+
+    // 明确的提示优先，只要它和帧字段不矛盾
+    let hinted: Vec<f64> = evidence
+        .hints
+        .iter()
+        .copied()
+        .filter(|&fps| fits(fps, &evidence))
+        .collect();
+    if !hinted.is_empty() {
+        let chosen = (hinted.iter().all(|&f| f == hinted[0])).then_some(hinted[0]);
+        return FpsInference {
+            evidence,
+            candidates: dedup(hinted),
+            chosen,
+        };
+    }
+
+    let Some(max_frame) = evidence.max_frame else {
+        return FpsInference {
+            evidence,
+            candidates: Vec::new(),
+            chosen: None,
+        };
+    };
+
+    let candidates: Vec<f64> = STANDARD_FRAME_RATES
+        .iter()
+        .copied()
+        .filter(|&fps| fits(fps, &evidence))
+        .collect();
+
+    // 有丢帧迹象时只剩 29.97 / 59.94，帧字段达到 29 就能确定是 29.97。
+    // 没有丢帧迹象时帧字段只能排除帧率，剩下的候选都说得通，不做选择。
+    let tight: Vec<f64> = candidates
+        .iter()
+        .copied()
+        .filter(|&fps| evidence.drop_frame && nominal_rate(fps) == max_frame + 1)
+        .collect();
+
+    let candidates = if tight.is_empty() { candidates } else { tight };
+    let chosen = match candidates.as_slice() {
+        [only] => Some(*only),
+        _ => None,
+    };
+
+    FpsInference {
+        evidence,
+        candidates,
+        chosen,
+    }
+}
+
+/// 录制时间码是否为丢帧格式（`FCM: DROP FRAME` 或 `;` 分隔符）。
+pub fn uses_drop_frame(edl: &Edl) -> bool {
+    collect_evidence(edl).drop_frame
+}
+
+/// 检查时间码的帧字段是否超出了给定帧率。
+///
+/// 每个超出的事件生成一条警告。
+pub fn check_frames_against_fps(edl: &Edl, fps: f64) -> Vec<Warning> {
+    let limit = nominal_rate(fps);
+
+    edl.events
+        .iter()
+        .filter_map(|event| {
+            let max = event_timecodes(event).map(|tc| tc.frames).max()?;
+            (max >= limit).then(|| Warning {
+                line: event.line,
+                message: t!("fps.frame_out_of_range", max, fps),
+            })
+        })
+        .collect()
+}
+
+fn collect_evidence(edl: &Edl) -> FpsEvidence {
+    let mut evidence = FpsEvidence::default();
+
+    // 源时间码可能是素材的帧率，只看录制时间码
+    for tc in edl.events.iter().flat_map(|e| [e.record_in, e.record_out]) {
+        evidence.timecode_count += 1;
+        evidence.max_frame = evidence.max_frame.max(Some(tc.frames));
+        evidence.drop_frame |= tc.drop_frame;
+    }
+
+    if let Some(fcm) = &edl.fcm {
+        let fcm = fcm.to_ascii_uppercase();
+        if fcm.contains("DROP") && !fcm.contains("NON") {
+            evidence.drop_frame = true;
+        }
+    }
+
+    if let Some(fps) = edl.frame_rate {
+        evidence.hints.push(fps);
+    }
+
+    if let Some(title) = &edl.title {
+        evidence.hints.extend(find_rate_hints(title));
+    }
+
+    // 注释里只认明确的帧率键，SOURCE FILE 等素材信息中的 "60fps" 不算
+    let comments = edl.events.iter().flat_map(|e| e.comments.iter());
+    evidence
+        .hints
+        .extend(comments.filter_map(|c| keyed_rate_hint(c)));
+    evidence.hints = dedup(std::mem::take(&mut evidence.hints));
+
+    evidence
+}
+
+fn event_timecodes(event: &EdlEvent) -> impl Iterator<Item = Timecode> + '_ {
+    [
+        event.source_in,
+        event.source_out,
+        Some(event.record_in),
+        Some(event.record_out),
+    ]
+    .into_iter()
+    .flatten()
+}
+
+/// 帧率是否与线索相符。
+fn fits(fps: f64, evidence: &FpsEvidence) -> bool {
+    if let Some(max) = evidence.max_frame
+        && max >= nominal_rate(fps)
+    {
+        return false;
+    }
+
+    if evidence.drop_frame {
+        return is_drop_frame_rate(fps);
+    }
+
+    true
+}
+
+fn is_drop_frame_rate(fps: f64) -> bool {
+    (fps - 29.97).abs() < 0.01 || (fps - 59.94).abs() < 0.01
+}
+
+/// 在一段文本中查找帧率提示。
+///
+/// 识别 "25fps"、"29.97 FPS"、"FRAME RATE: 23.976"、"帧率: 24" 等写法。
+pub fn find_rate_hints(text: &str) -> Vec<f64> {
+    let upper = text.to_uppercase();
+    let mut hints = Vec::new();
+
+    for keyword in ["FRAME RATE", "FRAMERATE", "帧率", "FPS"] {
+        let mut search_from = 0;
+        while let Some(pos) = upper[search_from..].find(keyword) {
+            let start = search_from + pos;
+            let end = start + keyword.len();
+
+            let number = if keyword == "FPS" {
+                number_before(&upper[..start])
+            } else {
+                None
+            }
+            .or_else(|| number_after(&upper[end..]));
+
+            if let Some(fps) = number
+                && fps > 0.0
+                && fps <= 120.0
+            {
+                hints.push(fps);
+            }
+
+            search_from = end;
+        }
+    }
+
+    dedup(hints)
+}
+
+/// 注释中的帧率键，例如 "FRAME RATE: 23.976"、"FPS = 25"、"帧率：24"。
+fn keyed_rate_hint(comment: &str) -> Option<f64> {
+    let upper = comment.trim().to_uppercase();
+    let rest = ["FRAME RATE", "FRAMERATE", "FPS", "帧率"]
+        .iter()
+        .find_map(|key| upper.strip_prefix(key))?;
+    if !rest.trim_start().starts_with([':', '：', '=']) {
+        return None;
+    }
+
+    number_after(rest).filter(|&fps| fps > 0.0 && fps <= 120.0)
+}
+
+fn number_before(text: &str) -> Option<f64> {
+    let trimmed = text.trim_end();
+    let start = trimmed
+        .rfind(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .map(|i| i + trimmed[i..].chars().next().map_or(1, char::len_utf8))
+        .unwrap_or(0);
+    trimmed[start..].parse().ok()
+}
+
+fn number_after(text: &str) -> Option<f64> {
+    let trimmed =
+        text.trim_start_matches(|c: char| c.is_whitespace() || c == ':' || c == '：' || c == '=');
+    let end = trimmed
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(trimmed.len());
+    trimmed[..end].parse().ok()
+}
+
+fn dedup(mut rates: Vec<f64>) -> Vec<f64> {
+    rates.sort_by(f64::total_cmp);
+    rates.dedup();
+    rates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::parse_edl;
+
+    fn edl_with(header: &str, frames: &[&str]) -> Edl {
+        let mut text = header.to_string();
+        for (i, tc) in frames.iter().enumerate() {
+            text.push_str(&format!(
+                "{:03} AX V C 00:00:00:00 00:00:01:00 {} {}\n",
+                i + 1,
+                tc,
+                tc
+            ));
+        }
+        parse_edl(&text)
+    }
+
+    #[test]
+    fn max_frame_24_is_not_enough_for_25fps() {
+        // 25、30、50 等都与帧字段相符，没有旁证时列出候选
+        let edl = edl_with("", &["00:00:00:10", "00:00:01:24"]);
+        let inference = infer_fps(&edl);
+        assert_eq!(inference.chosen, None);
+        assert_eq!(
+            inference.candidates,
+            vec![25.0, 29.97, 30.0, 50.0, 59.94, 60.0]
+        );
+        assert!(inference.resolve().is_err());
+
+        let edl = edl_with("TITLE: PAL 25fps\n", &["00:00:01:24"]);
+        assert_eq!(infer_fps(&edl).chosen, Some(25.0));
+    }
+
+    #[test]
+    fn source_timecodes_and_clip_metadata_are_ignored() {
+        // 24fps 的时间线引用 60fps 的航拍素材：源时间码和路径都是素材的帧率
+        let text = "\
+TITLE: Timeline 1
+001 AX V C 00:00:00:45 00:00:02:00 01:00:00:00 01:00:02:23
+* FROM CLIP NAME: drone_60fps.mov
+* SOURCE FILE: /media/drone_60fps.mov
+* FRAME RATE: 24
+";
+        let inference = infer_fps(&parse_edl(text));
+        assert_eq!(inference.evidence.max_frame, Some(23));
+        assert_eq!(inference.evidence.hints, vec![24.0]);
+        assert_eq!(inference.chosen, Some(24.0));
+
+        // 没有帧率键时不会被路径带偏成 60，也不会因为路径而报歧义以外的结果
+        let edl = parse_edl(&text.replace("* FRAME RATE: 24\n", ""));
+        let inference = infer_fps(&edl);
+        assert!(inference.evidence.hints.is_empty());
+        assert_eq!(inference.chosen, None);
+        assert_eq!(inference.candidates, STANDARD_FRAME_RATES);
+    }
+
+    #[test]
+    fn max_frame_23_is_ambiguous() {
+        let edl = edl_with("", &["00:00:00:23"]);
+        let inference = infer_fps(&edl);
+        assert_eq!(inference.chosen, None);
+        assert_eq!(inference.candidates, STANDARD_FRAME_RATES);
+
+        let err = inference.resolve().unwrap_err().to_string();
+        assert!(err.contains("23.976") && err.contains("24"), "{}", err);
+    }
+
+    #[test]
+    fn drop_frame_separator_means_2997() {
+        let edl = edl_with("", &["00:00:00;29"]);
+        assert_eq!(infer_fps(&edl).chosen, Some(29.97));
+    }
+
+    #[test]
+    fn fcm_drop_frame_is_used() {
+        let edl = edl_with("FCM: DROP FRAME\n", &["00:00:00:05"]);
+        assert_eq!(infer_fps(&edl).candidates, vec![29.97, 59.94]);
+    }
+
+    #[test]
+    fn title_hint_wins_when_consistent() {
+        let edl = edl_with("TITLE: Reel 1 23.976fps\n", &["00:00:00:05"]);
+        assert_eq!(infer_fps(&edl).chosen, Some(23.976));
+
+        // 提示与帧字段矛盾时忽略
+        let edl = edl_with("TITLE: 24 FPS\n", &["00:00:00:24"]);
+        assert_eq!(infer_fps(&edl).chosen, None);
+    }
+
+    #[test]
+    fn no_timecodes_cannot_be_resolved() {
+        assert!(infer_fps(&Edl::default()).resolve().is_err());
+    }
+
+    #[test]
+    fn hint_parsing() {
+        assert_eq!(find_rate_hints("FRAME RATE: 29.97"), vec![29.97]);
+        assert_eq!(find_rate_hints("seq_25fps_v2"), vec![25.0]);
+        assert_eq!(find_rate_hints("帧率：24"), vec![24.0]);
+        assert!(find_rate_hints("FPS check").is_empty());
+
+        assert_eq!(keyed_rate_hint("FRAME RATE: 29.97"), Some(29.97));
+        assert_eq!(keyed_rate_hint("fps = 25"), Some(25.0));
+        assert_eq!(keyed_rate_hint("SOURCE FILE: drone_60fps.mov"), None);
+        assert_eq!(keyed_rate_hint("FPS check"), None);
+    }
+
+    #[test]
+    fn frames_exceeding_rate_are_reported() {
+        let edl = edl_with("", &["00:00:00:10", "00:00:00:27"]);
+        let warnings = check_frames_against_fps(&edl, 25.0);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].line, 2);
+        assert!(check_frames_against_fps(&edl, 29.97).is_empty());
+    }
+}
@@ -0,0 +1,282 @@
+//! 界面语言与消息目录。
+//!
+//! 帮助、编码提示、警告和错误都按键从目录中取，目前有中文（默认）和英文两套。
+//! 命令行用 `--lang` 选择，没给出时看环境变量 `LC_ALL` / `LC_MESSAGES` / `LANG`。
+//!
+//! 消息中的 `{}` 依次替换为参数，`{0}`、`{1}` 按序号替换，`{{` / `}}` 表示花括号本身：
+//!
+//! ```
+//! use edl2srt::i18n::{Lang, tr_in};
+//!
+//! assert_eq!(tr_in(Lang::Zh, "error.unsupported_encoding", &[&"x"]), "不支持的编码: x");
+//! assert_eq!(tr_in(Lang::En, "error.unsupported_encoding", &[&"x"]), "Unsupported encoding: x");
+//! ```
+//!
+//! 新增消息时在 `i18n/zh.rs` 和 `i18n/en.rs` 中各加一条同名的键，
+//! 单元测试会检查两种语言的键和参数个数一致、代码里用到的键都存在。
+
+mod en;
+mod zh;
+
+use std::fmt::{Display, Write};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 界面语言。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    #[default]
+    Zh,
+    En,
+}
+
+impl Lang {
+    pub const ALL: [Lang; 2] = [Lang::Zh, Lang::En];
+
+    /// 解析 `zh` / `en` 或 `zh_CN.UTF-8` 之类的区域名，其它语言返回 None。
+    pub fn parse(name: &str) -> Option<Lang> {
+        let name = name.trim().to_ascii_lowercase();
+        let language = name.split(['_', '-', '.', '@']).next().unwrap_or("");
+        match language {
+            "zh" => Some(Lang::Zh),
+            "en" => Some(Lang::En),
+            _ => None,
+        }
+    }
+
+    /// 按 `LC_ALL`、`LC_MESSAGES`、`LANG` 的顺序取第一个非空的值。
+    ///
+    /// 中文区域用中文；`C` / `POSIX` 或没有设置时保持默认的中文；其它区域用英文。
+    pub fn from_env() -> Lang {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .map_or(Lang::default(), |value| Lang::from_locale(&value))
+    }
+
+    fn from_locale(value: &str) -> Lang {
+        match Lang::parse(value) {
+            Some(lang) => lang,
+            None if value == "C" || value.starts_with("C.") || value == "POSIX" => Lang::default(),
+            None => Lang::En,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Lang::Zh => "zh",
+            Lang::En => "en",
+        }
+    }
+
+    fn messages(&self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Lang::Zh => zh::MESSAGES,
+            Lang::En => en::MESSAGES,
+        }
+    }
+}
+
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// 设置当前界面语言，整个进程生效。
+pub fn set_lang(lang: Lang) {
+    CURRENT.store(lang as u8, Ordering::Relaxed);
+}
+
+/// 当前界面语言
+pub fn lang() -> Lang {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Lang::En,
+        _ => Lang::Zh,
+    }
+}
+
+/// 查找消息原文，不做参数替换。
+pub fn lookup(lang: Lang, key: &str) -> Option<&'static str> {
+    lang.messages()
+        .iter()
+        .find(|(k, _)| *k == key)
+        .map(|(_, message)| *message)
+}
+
+/// 当前语言下没有参数、也没有花括号的消息，例如格式描述。
+///
+/// 键不存在时返回键本身。
+pub fn text(key: &'static str) -> &'static str {
+    lookup(lang(), key).unwrap_or(key)
+}
+
+/// 当前语言下的消息，参数按占位符替换。见 [`t!`](crate::t)。
+pub fn tr(key: &str, args: &[&dyn Display]) -> String {
+    tr_in(lang(), key, args)
+}
+
+/// 指定语言下的消息。键不存在时返回键本身。
+pub fn tr_in(lang: Lang, key: &str, args: &[&dyn Display]) -> String {
+    match lookup(lang, key) {
+        Some(message) => format_message(message, args),
+        None => key.to_string(),
+    }
+}
+
+/// 按 `{}` / `{N}` 替换参数，`{{` / `}}` 转义；缺少的参数留空。
+fn format_message(message: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(message.len());
+    let mut next = 0;
+    let mut chars = message.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                out.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                out.push('}');
+            }
+            '{' => {
+                let mut index = String::new();
+                for c in chars.by_ref() {
+                    if c == '}' {
+                        break;
+                    }
+                    index.push(c);
+                }
+                let index = match index.parse::<usize>() {
+                    Ok(index) => index,
+                    Err(_) => {
+                        next += 1;
+                        next - 1
+                    }
+                };
+                if let Some(arg) = args.get(index) {
+                    let _ = write!(out, "{}", arg);
+                }
+            }
+            c => out.push(c),
+        }
+    }
+
+    out
+}
+
+/// 占位符个数：`{}` 按出现次数，`{N}` 按最大序号。
+#[cfg(test)]
+fn placeholder_count(message: &str) -> usize {
+    let mut sequential = 0;
+    let mut max_indexed = 0;
+    let mut rest = message.replace("{{", "").replace("}}", "");
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..].find('}').map_or(rest.len(), |i| start + i);
+        match rest[start + 1..end].parse::<usize>() {
+            Ok(index) => max_indexed = max_indexed.max(index + 1),
+            Err(_) => sequential += 1,
+        }
+        rest = rest[(end + 1).min(rest.len())..].to_string();
+    }
+    sequential.max(max_indexed)
+}
+
+/// 取当前语言的消息：`t!("键")` 或 `t!("键", 参数...)`，结果是 `String`。
+#[macro_export]
+macro_rules! t {
+    ($key:literal) => {
+        $crate::i18n::tr($key, &[])
+    };
+    ($key:literal, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::tr($key, &[$(&$arg as &dyn ::std::fmt::Display),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::path::Path;
+
+    #[test]
+    fn every_key_exists_in_every_language() {
+        let zh = Lang::Zh.messages();
+        for lang in Lang::ALL {
+            let messages = lang.messages();
+            let keys: BTreeSet<_> = messages.iter().map(|(k, _)| *k).collect();
+            assert_eq!(keys.len(), messages.len(), "{} 中有重复的键", lang.name());
+
+            for (key, message) in zh {
+                let other =
+                    lookup(lang, key).unwrap_or_else(|| panic!("{} 缺少消息 {}", lang.name(), key));
+                assert_eq!(
+                    placeholder_count(other),
+                    placeholder_count(message),
+                    "{} 的消息 {} 参数个数与中文不同",
+                    lang.name(),
+                    key
+                );
+            }
+            assert_eq!(messages.len(), zh.len(), "{} 有中文没有的键", lang.name());
+        }
+    }
+
+    /// 代码里 `t!("…")`、`text("…")` 等用到的键都要在目录里
+    #[test]
+    fn keys_used_in_source_exist() {
+        fn visit(dir: &Path, used: &mut BTreeSet<String>) {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                // 目录本身和这个文件里的都是例子，不算
+                if path.ends_with("i18n") || path.ends_with("i18n.rs") {
+                    continue;
+                }
+                if path.is_dir() {
+                    visit(&path, used);
+                } else if path.extension().is_some_and(|ext| ext == "rs") {
+                    let source = std::fs::read_to_string(&path).unwrap();
+                    for marker in ["t!(\"", "text(\"", "tr(\"", "tr_in(Lang::Zh, \""] {
+                        for (i, _) in source.match_indices(marker) {
+                            // push_str(" 之类不算
+                            let before = source[..i].chars().next_back();
+                            if before.is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                                continue;
+                            }
+                            let rest = &source[i + marker.len()..];
+                            let key = &rest[..rest.find('"').unwrap()];
+                            used.insert(key.to_string());
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut used = BTreeSet::new();
+        visit(
+            &Path::new(env!("CARGO_MANIFEST_DIR")).join("src"),
+            &mut used,
+        );
+        assert!(used.len() > 100);
+        for key in used {
+            assert!(lookup(Lang::Zh, &key).is_some(), "目录中没有消息 {}", key);
+        }
+    }
+
+    #[test]
+    fn formats_placeholders() {
+        assert_eq!(format_message("{} 和 {}", &[&1, &"b"]), "1 和 b");
+        assert_eq!(format_message("{0} {1} {0}", &[&"a", &2]), "a 2 a");
+        assert_eq!(format_message("{{ {} }}", &[&3]), "{ 3 }");
+        assert_eq!(format_message("缺 {}", &[]), "缺 ");
+        assert_eq!(placeholder_count("{{ {0} {1} {0} }}"), 2);
+        assert_eq!(placeholder_count("{} {}"), 2);
+    }
+
+    #[test]
+    fn parses_locales() {
+        assert_eq!(Lang::parse("zh_CN.UTF-8"), Some(Lang::Zh));
+        assert_eq!(Lang::parse("EN"), Some(Lang::En));
+        assert_eq!(Lang::parse("ja_JP"), None);
+        assert_eq!(Lang::from_locale("C.UTF-8"), Lang::Zh);
+        assert_eq!(Lang::from_locale("fr_FR.UTF-8"), Lang::En);
+        assert_eq!(Lang::from_locale("zh_TW"), Lang::Zh);
+    }
+}
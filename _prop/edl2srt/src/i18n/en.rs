@@ -0,0 +1,969 @@
+//! 英文消息，键与 [`super::zh`] 一一对应。
+
+pub(super) const MESSAGES: &[(&str, &str)] = &[
+    // 错误
+    ("error.unsupported_encoding", "Unsupported encoding: {}"),
+    ("error.unknown_format", "Unsupported format: {}"),
+    ("error.parse", "Line {}: {}"),
+    ("error.json", "Malformed JSON: {}"),
+    ("error.invalid", "Invalid argument: {}"),
+    ("error.transcribe", "Transcription failed: {}"),
+    ("error.config", "Invalid configuration file: {}"),
+    ("error.config_file", "Invalid configuration file {}: {}"),
+    ("error.frame_rate", "Cannot infer the frame rate: {}"),
+    ("error.frame_rate_candidates", ". Candidate frame rates: {}"),
+    (
+        "error.frame_rate_hint",
+        ". Please specify the frame rate on the command line",
+    ),
+    // 时间码与警告
+    ("model.invalid_timecode", "Invalid timecode: {}"),
+    ("model.warning", "Line {}: {}"),
+    // 质检
+    ("qc.issue", "Subtitle {}: {}"),
+    ("qc.retimed_event", "Line {} event {}: "),
+    ("qc.freeze", "freeze frame"),
+    ("qc.reverse", "reverse {}%"),
+    ("qc.speed", "speed {}%"),
+    (
+        "qc.no_m2",
+        " (source and record durations differ, no M2 line)",
+    ),
+    (
+        "qc.line_too_long",
+        "a line has {} characters, over the limit of {}",
+    ),
+    ("qc.too_many_lines", "{} lines, over the limit of {}"),
+    ("qc.too_short", "shown for {} ms, under the minimum of {}"),
+    ("qc.too_long", "shown for {} ms, over the maximum of {}"),
+    (
+        "qc.too_fast",
+        "reading speed {} chars/s, over the limit of {}",
+    ),
+    // 帧率
+    ("fps.no_timecodes", "no timecodes found"),
+    (
+        "fps.no_candidate",
+        "the largest frame field {} does not fit any common frame rate",
+    ),
+    (
+        "fps.ambiguous",
+        "the largest frame field is {}, which fits several frame rates",
+    ),
+    (
+        "fps.frame_out_of_range",
+        "frame field {} is out of range for {} fps",
+    ),
+    // EDL 解析
+    (
+        "reader.record_out_before_in",
+        "record out is earlier than record in",
+    ),
+    ("reader.bad_event_line", "unrecognised event line, ignored"),
+    (
+        "reader.m2_without_event",
+        "M2 line before any event, ignored",
+    ),
+    (
+        "reader.duplicate_m2",
+        "several M2 lines for one event, using the first",
+    ),
+    ("reader.bad_m2", "unrecognised M2 line, ignored"),
+    (
+        "reader.aud_without_event",
+        "AUD line before any event, ignored",
+    ),
+    ("reader.bad_aud", "unrecognised AUD line, ignored"),
+    (
+        "reader.note_without_event",
+        "comment line before any event, ignored",
+    ),
+    (
+        "reader.duplicate_clip_name",
+        "several clip names for one event, using the first",
+    ),
+    (
+        "reader.bad_locator",
+        "unrecognised locator (LOC) line, kept as a comment",
+    ),
+    // 双语
+    (
+        "bilingual.empty_translation",
+        "the translation for clip {} is empty",
+    ),
+    (
+        "bilingual.missing_translation",
+        "clip {} has no translation in the table",
+    ),
+    ("bilingual.empty_track", "track {} has no subtitle events"),
+    (
+        "bilingual.time_mismatch",
+        "primary {}-{} and secondary {}-{} (line {}) do not line up; using the primary timing",
+    ),
+    (
+        "bilingual.unmatched_primary",
+        "primary subtitle {} has no match on secondary track {}",
+    ),
+    (
+        "bilingual.unmatched_secondary",
+        "secondary subtitle {} has no match on primary track {}",
+    ),
+    (
+        "bilingual.row_too_short",
+        "each row of the translation table needs a clip name and a translation",
+    ),
+    ("bilingual.unclosed_quote", "unclosed quote"),
+    (
+        "bilingual.json_shape",
+        "a JSON translation table must be an object or an array of objects",
+    ),
+    (
+        "bilingual.json_not_string",
+        "the translation for clip {} is not a string",
+    ),
+    (
+        "bilingual.json_missing_field",
+        "item {} of the JSON translation table has no clip_name or text",
+    ),
+    // 配置文件
+    ("list.separator", ", "),
+    (
+        "config.no_presets",
+        "no preset named {} (the file defines no presets)",
+    ),
+    ("config.unknown_preset", "no preset named {}; available: {}"),
+    // EDL 比较
+    ("diff.added", "added"),
+    ("diff.removed", "removed"),
+    ("diff.retimed", "retimed"),
+    ("diff.retexted", "retexted"),
+    ("diff.retimed_and_retexted", "retimed+retexted"),
+    (
+        "diff.summary",
+        "{} added, {} removed, {} retimed, {} retexted, {} retimed+retexted, {} unchanged",
+    ),
+    ("diff.column_change", "Change"),
+    ("diff.column_event", "Event"),
+    ("diff.column_old_time", "Old time"),
+    ("diff.column_new_time", "New time"),
+    ("diff.column_in_delta", "In Δ"),
+    ("diff.column_out_delta", "Out Δ"),
+    ("diff.column_text", "Text"),
+    // JSON 导出
+    ("dump.unsupported_version", "unsupported JSON version: {}"),
+    (
+        "dump.bad_fps",
+        "the frame rate in the JSON must be a finite number greater than 0, got {}",
+    ),
+    // 字幕编辑
+    ("editor.overlap", "overlaps subtitle {}"),
+    (
+        "editor.out_not_after_in",
+        "out point is not after the in point",
+    ),
+    // 编码诊断
+    ("encoding.guess", "auto-detect"),
+    ("encoding.hinted_guess", "hint {}"),
+    // 格式
+    ("format.input_formats", "Input formats:"),
+    ("format.output_formats", "Output formats:"),
+    ("format.ass", "Advanced SubStation Alpha subtitles"),
+    (
+        "format.ffmetadata",
+        "FFmpeg metadata chapters (FFMETADATA1)",
+    ),
+    ("format.mkvchapters", "Matroska chapter XML (mkvmerge)"),
+    (
+        "format.youtube",
+        "YouTube description chapter list (0:00 Title)",
+    ),
+    ("format.youtube_start", "Start"),
+    (
+        "format.edl",
+        "EDL exported by CMX3600 / Premiere / Resolve / Avid",
+    ),
+    ("format.json", "event list exported by edl2srt --dump-json"),
+    ("format.lrc", "LRC lyrics ([mm:ss.xx] time tags)"),
+    (
+        "format.lrc_enhanced",
+        "enhanced LRC lyrics (extra <mm:ss.xx> line start and end times)",
+    ),
+    ("format.sbv", "YouTube SubViewer subtitles"),
+    (
+        "format.scc",
+        "Scenarist SCC (CEA-608 pop-on, 29.97 drop frame)",
+    ),
+    ("format.srt", "SubRip subtitles (UTF-8 without BOM)"),
+    ("format.stl", "EBU STL (Tech 3264, 25 / 30 fps)"),
+    ("format.vtt", "WebVTT web subtitles"),
+    (
+        "lrc.late_start",
+        "the first lyric starts at {}; LRC counts from the start of the song, so --origin may be needed",
+    ),
+    (
+        "chapters.late_start",
+        "the first chapter starts at {}; chapter times count from the start of the video, so --origin may be needed",
+    ),
+    (
+        "chapters.youtube_padded",
+        r#"the first chapter is not at 0:00, so a 0:00 chapter "{}" was added at the start"#,
+    ),
+    ("sbv.bad_time_line", "unrecognised SBV time line: {}"),
+    ("srt.bad_time_line", "unrecognised time line: {}"),
+    (
+        "scc.unsupported_chars",
+        "the CEA-608 character set cannot represent {} characters (e.g. “{}”); they will be written as ?",
+    ),
+    (
+        "scc.line_too_long",
+        "subtitle {} has a line longer than {} characters; the rest will be cut off",
+    ),
+    (
+        "scc.too_many_rows",
+        "subtitle {} has {} lines; pop-on captions keep only the last {}",
+    ),
+    (
+        "stl.unsupported_chars",
+        "the EBU STL Latin character table cannot represent {} characters (e.g. “{}”); they will be written as ?",
+    ),
+    (
+        "stl.line_too_long",
+        "subtitle {} has a line longer than {} characters; some playout devices will truncate it",
+    ),
+    (
+        "stl.not_stl",
+        "not an EBU STL file (missing GSI block, or the disk format is not STL25.01 / STL30.01)",
+    ),
+    (
+        "stl.bad_tti_length",
+        "the TTI section length {} is not a multiple of 128",
+    ),
+    (
+        "stl.unsupported_fps",
+        "EBU STL only supports 25 / 29.97 / 30 fps (and multiples); the frame rate is {}",
+    ),
+    (
+        "stl.past_24_hours",
+        "subtitle {} ends at {}, past the 23:59:59:xx that STL can hold; use --origin to move the start earlier",
+    ),
+    (
+        "stl.too_many_subtitles",
+        "there are {} subtitles, more than the {} that EBU STL subtitle numbers can hold; split them into several files",
+    ),
+    (
+        "stl.too_many_blocks",
+        "{} TTI blocks are needed, more than the EBU STL limit of {}; split them into several files",
+    ),
+    // 卷、预览、说话人、转写、网页版
+    (
+        "reels.overlap",
+        "{}: subtitle starts at {}, before the last subtitle of reel {} ends at {} (reels overlap)",
+    ),
+    (
+        "reels.starts_count",
+        "{} reel starts given, but there are {} reels",
+    ),
+    (
+        "reels.lengths_count",
+        "{} reel lengths given, expected {} (one for every reel but the last)",
+    ),
+    ("render.bad_font", "cannot read font: {}"),
+    ("render.sheet_title", "edl2srt subtitle preview"),
+    ("render.sheet_summary", "{} subtitles at {} fps"),
+    ("render.still_alt", "Subtitle {}"),
+    ("speaker.bad_regex", "invalid speaker regex: {}"),
+    (
+        "transcribe.http_only",
+        "the transcription URL must use http://, got: {}",
+    ),
+    (
+        "transcribe.bad_port",
+        "invalid port in transcription URL: {}",
+    ),
+    (
+        "transcribe.missing_host",
+        "transcription URL has no host: {}",
+    ),
+    (
+        "transcribe.source_not_found",
+        "source file for clip {} not found",
+    ),
+    ("transcribe.empty_result", "transcription of {} is empty"),
+    (
+        "transcribe.incomplete_response",
+        "incomplete response from the transcription service",
+    ),
+    (
+        "transcribe.not_http",
+        "the transcription service did not answer with HTTP",
+    ),
+    ("transcribe.bad_chunked", "malformed chunked response"),
+    ("transcribe.no_text", "no text field in the response"),
+    (
+        "web.unsupported_format",
+        "{} (not supported in the web version)",
+    ),
+    (
+        "web.decode_errors",
+        "invalid bytes while decoding as {}; replaced with substitution characters",
+    ),
+    (
+        "web.bad_fps",
+        "frame rate must be a finite number greater than 0, got {}",
+    ),
+    // 终端编辑界面
+    (
+        "tui.help",
+        "↑↓ select  Enter edit text  d delete  m merge with next  [ ] in ±1 frame  { } out ±1 frame  < > whole ±1 frame  u undo  w write  q quit",
+    ),
+    ("tui.deleted", "deleted subtitle {}"),
+    ("tui.merged", "merged subtitles {} and {}"),
+    (
+        "tui.nothing_to_merge",
+        "the last subtitle has nothing to merge with",
+    ),
+    ("tui.at_boundary", "reached a boundary, cannot move further"),
+    ("tui.undone", "undone"),
+    ("tui.nothing_to_undo", "nothing to undo"),
+    ("tui.title", " edl2srt --edit · {} subtitles · {}{} "),
+    ("tui.column_in", "In"),
+    ("tui.column_out", "Out"),
+    ("tui.column_duration", "Duration"),
+    ("tui.column_text", "Text"),
+    ("tui.column_warnings", "Warnings"),
+    ("tui.warning", "warning: {}"),
+    ("tui.warning_separator", "; "),
+    ("tui.no_cues", "no subtitles"),
+    ("tui.current", " Current subtitle "),
+    ("tui.edit_prefix", "Text: "),
+    (
+        "tui.edit_help",
+        r#"Enter confirm  Esc cancel  {} starts a new line, \| types a literal |"#,
+    ),
+    (
+        "tui.confirm_quit",
+        "there are unwritten changes; press q again to discard, any other key to go back",
+    ),
+    // 命令行
+    ("cli.error", "error: {}"),
+    (
+        "cli.top_bottom_ass_only",
+        "error: the top-bottom bilingual layout is only supported for ASS output.",
+    ),
+    (
+        "cli.speaker_style_format",
+        "error: speaker style {} only supports {} output.",
+    ),
+    (
+        "cli.fps_inferred",
+        "no frame rate given; inferred from the EDL: {}",
+    ),
+    ("cli.warning", "warning: {}"),
+    ("cli.reel_merge_failed", "failed to merge reels: {}"),
+    ("cli.reel_start", "reel {} starts at: {}"),
+    ("cli.transcribing", "transcribing audio clips: {}:{}{}"),
+    (
+        "cli.transcribe_done",
+        "transcription done: {} new, {} from cache, {} failed (cache directory: {})",
+    ),
+    ("cli.json_dumped", "JSON written: {}"),
+    ("cli.json_dump_failed", "error writing JSON: {}"),
+    (
+        "cli.no_speakers",
+        "warning: no speakers recognised; check the speaker rules",
+    ),
+    (
+        "cli.no_locators",
+        "warning: no usable locators found (* LOC: lines)",
+    ),
+    (
+        "cli.before_origin",
+        "warning: {} subtitles start before origin {} and were skipped",
+    ),
+    ("cli.qc_passed", "QC passed"),
+    ("cli.edit_discarded", "editing discarded; no file written."),
+    ("cli.tui_failed", "cannot open the terminal interface: {}"),
+    ("cli.output_renamed", "output file exists; renamed to: {}"),
+    ("cli.converted", "conversion succeeded! output file: {}"),
+    ("cli.write_failed", "error writing output file: {}"),
+    ("cli.preview_written", "preview written: {}"),
+    ("cli.preview_failed", "error generating preview: {}"),
+    ("cli.read_input_failed", "cannot read input file {}: {}"),
+    ("cli.decode_failed", "failed to decode input file: {}"),
+    ("cli.encoding_given", "using the given encoding: {}"),
+    (
+        "cli.encoding_detected_sample",
+        "no encoding given; detected from the start of the file: {}",
+    ),
+    (
+        "cli.fps_inferred_sample",
+        "no frame rate given; inferred from the start of the file: {}",
+    ),
+    (
+        "cli.stream_buffered",
+        "warning: the {} format collects all subtitles before writing; memory grows with the number of subtitles",
+    ),
+    ("cli.stream_no_qc", "warning: streaming conversion skips QC"),
+    (
+        "cli.stream_decode_errors",
+        "warning: invalid bytes found while decoding as {}; replaced with substitution characters.",
+    ),
+    (
+        "cli.stream_done",
+        "streaming conversion done: {} events, {} subtitles",
+    ),
+    (
+        "cli.font_missing_glyphs",
+        "warning: font {} lacks {} characters (e.g. {}); they show as missing glyphs in the preview",
+    ),
+    (
+        "cli.large_input",
+        "note: the input file is {} MiB and will be read into memory; for a plain conversion add --stream to read and write as you go",
+    ),
+    (
+        "cli.encoding_given_errors",
+        "using the given encoding: {}, but invalid bytes were found and replaced with substitution characters.",
+    ),
+    (
+        "cli.encoding_detected_errors",
+        "no encoding given; detected: {}, but invalid bytes were found and replaced with substitution characters.",
+    ),
+    ("cli.encoding_detected", "no encoding given; detected: {}"),
+    ("cli.parse_failed", "failed to parse input file: {}"),
+    (
+        "cli.read_subtitle_failed",
+        "cannot read subtitle file {}: {}",
+    ),
+    (
+        "cli.unknown_subtitle_format",
+        "unrecognised subtitle file format: {}",
+    ),
+    (
+        "cli.parse_subtitle_failed",
+        "failed to parse subtitle file: {}",
+    ),
+    (
+        "cli.conform_done",
+        "conform done: {} kept ({} moved), {} cut, {} unmatched, {} new clips",
+    ),
+    ("cli.conform_cut", "  cut: {} {}"),
+    ("cli.conform_unmatched", "  not found in the old EDL: {} {}"),
+    (
+        "cli.conform_needs_translation",
+        "  needs translation: event {} {} {}",
+    ),
+    (
+        "cli.conform_nothing_matched",
+        "None of the translated subtitles match the old EDL; check that the frame rate matches the old EDL, and if the subtitles were exported with --origin, pass the same --origin here",
+    ),
+    (
+        "cli.bad_encoding_hint",
+        "Invalid region hint '{}'; choose from: jp, cn, tw, kr",
+    ),
+    ("cli.bad_lines", "Invalid line count '{}'."),
+    ("cli.read_failed", "failed to read input file: {}"),
+    ("cli.detect_file", "file: {} ({} bytes)"),
+    ("cli.detect_no_bom", "BOM: none"),
+    ("cli.detect_confident", "confident"),
+    ("cli.detect_not_confident", "not confident"),
+    ("cli.detect_certain", "certain"),
+    ("cli.detect_replacements", "yes ({} replacement characters)"),
+    ("cli.detect_no_errors", "none"),
+    (
+        "cli.detect_candidate",
+        "[{}] {}  source: {}  guess: {}  decode errors: {}",
+    ),
+    (
+        "cli.detect_recommend",
+        "suggestion: --input-encoding {} (check against the preview before using it)",
+    ),
+    (
+        "cli.diff_print_failed",
+        "error printing the change table: {}",
+    ),
+    ("cli.diff_report_written", "change report written: {}"),
+    (
+        "cli.diff_report_failed",
+        "error writing the change report: {}",
+    ),
+    ("cli.change_list_written", "change list EDL written: {}"),
+    (
+        "cli.change_list_failed",
+        "error writing the change list EDL: {}",
+    ),
+    (
+        "cli.read_translations_failed",
+        "cannot read translation table {}: {}",
+    ),
+    (
+        "cli.decode_translations_failed",
+        "failed to decode translation table: {}",
+    ),
+    (
+        "cli.parse_translations_failed",
+        "failed to parse translation table: {}",
+    ),
+    (
+        "cli.unused_translation",
+        "warning: {} in the translation table matches no clip",
+    ),
+    (
+        "cli.bilingual_done",
+        "bilingual subtitles: {} in total, {} without a secondary language",
+    ),
+    ("cli.reel_warning", "warning: {}: {}"),
+    ("cli.too_few_arguments", "not enough arguments."),
+    (
+        "cli.bad_fps",
+        "invalid frame rate '{}'. Enter a number (e.g. 24 or 23.976).",
+    ),
+    (
+        "cli.fps_not_positive",
+        "frame rate must be a finite number greater than 0.",
+    ),
+    ("cli.extra_argument", "unexpected argument: {}"),
+    (
+        "cli.fps_without_paths",
+        "'{}' was read as the frame rate, but the input or output file is missing after it.",
+    ),
+    (
+        "cli.bad_speaker_style",
+        "invalid speaker style '{}'; choose from: prefix, dash, ass-styles, vtt-voice",
+    ),
+    (
+        "cli.bad_speaker_reel",
+        "option --speaker-reel should be reel=speaker, got: {}",
+    ),
+    (
+        "cli.bad_text_source",
+        "invalid text source '{}'; choose from: clip-name, transcribe, locators",
+    ),
+    ("cli.missing_value", "option {} needs a value."),
+    ("cli.unknown_option", "unrecognised option: {}"),
+    (
+        "cli.tracks_and_translations",
+        "--tracks and --translations cannot be used together.",
+    ),
+    (
+        "cli.config_fps_not_positive",
+        "the frame rate in the config file must be a finite number greater than 0.",
+    ),
+    (
+        "cli.config_bad_origin",
+        "invalid origin timecode in the config file: {}",
+    ),
+    (
+        "cli.tracks_and_translations_config",
+        "--tracks and --translations cannot be used together (check the config file).",
+    ),
+    (
+        "cli.conform_and_bilingual",
+        "--conform cannot be combined with bilingual options.",
+    ),
+    (
+        "cli.layout_needs_bilingual",
+        "--bilingual-layout requires --tracks or --translations.",
+    ),
+    (
+        "cli.locators_conflict",
+        "locator subtitles (--text-source locators) cannot be combined with conform or bilingual options.",
+    ),
+    (
+        "cli.edit_top_bottom",
+        "--edit does not support the top-bottom bilingual layout.",
+    ),
+    (
+        "cli.speakers_need_rules",
+        "speaker labels need --speaker-pattern or --speaker-reel.",
+    ),
+    (
+        "cli.speakers_conflict",
+        "speaker labels cannot be combined with conform, bilingual options or locator subtitles.",
+    ),
+    (
+        "cli.edit_ass_styles",
+        "--edit does not support per-speaker styles (ass-styles).",
+    ),
+    (
+        "cli.render_needs_font",
+        "--render needs a font file given with --render-font (TTF / OTF).",
+    ),
+    (
+        "cli.render_options_alone",
+        "--render-font and --render-size require --render.",
+    ),
+    ("cli.feature_bilingual", "bilingual options"),
+    ("cli.feature_transcribe", "transcription"),
+    ("cli.feature_locators", "locator subtitles"),
+    ("cli.feature_speakers", "speaker labels"),
+    (
+        "cli.stream_conflict",
+        "--stream cannot be combined with {}.",
+    ),
+    (
+        "cli.config_not_found",
+        "config file {} not found (neither in the current directory nor the user config directory); presets are unavailable.",
+    ),
+    (
+        "cli.bad_tracks",
+        r#"option --tracks should be "primary,secondary", e.g. A1,A2, got: {}"#,
+    ),
+    (
+        "cli.bad_render_size",
+        "invalid resolution '{}'; expected WIDTHxHEIGHT, e.g. 1920x1080",
+    ),
+    (
+        "cli.bad_layout",
+        "invalid layout '{}'; choose from: stacked, top-bottom",
+    ),
+    (
+        "cli.bad_lang",
+        "invalid interface language '{}'; choose from: zh, en",
+    ),
+    ("cli.see_help", "See '{}' for help."),
+    (
+        "cli.unknown_option_suggest",
+        "Unrecognized argument: {}; did you mean {}?",
+    ),
+    ("cli.unknown_subcommand", "Unknown subcommand: {}"),
+    ("cli.invalid_value", "Invalid value for {}: {}"),
+    ("cli.invalid_value_reason", "{}: {}"),
+    (
+        "cli.invalid_value_choices",
+        "Invalid value '{1}' for {0}; possible values: {2}",
+    ),
+    ("cli.missing_arguments", "Missing required arguments: {}"),
+    ("cli.used_twice", "{} cannot be used more than once"),
+    ("cli.argument_conflict", "{} cannot be used with {}"),
+    ("cli.wrong_number_of_values", "{} takes {} values"),
+    ("cli.bad_timecode", "Invalid timecode: {}"),
+    ("cli.check_passed", "Check passed: {} events, {} subtitles."),
+    (
+        "cli.check_failed",
+        "{} events, {} subtitles, {} problems found.",
+    ),
+    // 命令行选项
+    ("cli.heading.usage", "Usage:"),
+    ("cli.heading.options", "Options"),
+    ("cli.heading.arguments", "Arguments"),
+    ("cli.heading.commands", "Commands"),
+    (
+        "about.main",
+        r#"EDL to subtitle converter
+
+Converts EDL files into SRT and other subtitle files. A known input encoding is used
+when given, otherwise it is detected automatically; SRT output is always UTF-8 without BOM."#,
+    ),
+    (
+        "about.convert",
+        "Convert an EDL into a subtitle file (the default when no subcommand is given)",
+    ),
+    (
+        "about.check",
+        "Check an EDL and its subtitles, reporting problems without writing files",
+    ),
+    (
+        "about.diff",
+        "Compare two versions of an EDL and list subtitle changes",
+    ),
+    (
+        "about.detect_encoding",
+        "Diagnose the encoding of an input file when the output is garbled",
+    ),
+    ("about.completions", "Generate a shell completion script"),
+    (
+        "usage.main",
+        r#"{0} <COMMAND> [OPTIONS]
+  {0} [FPS] <INPUT> <OUTPUT> [OPTIONS]"#,
+    ),
+    (
+        "usage.convert",
+        r#"{0} convert [FPS] <INPUT> <OUTPUT> [OPTIONS]
+  {0} convert --input <INPUT> --output <OUTPUT> [--fps <FPS>] [OPTIONS]
+  {0} [FPS] <INPUT> <OUTPUT> [OPTIONS]"#,
+    ),
+    ("usage.check", "{0} check [FPS] <INPUT> [OPTIONS]"),
+    ("usage.diff", "{0} diff <OLD> <NEW> [OPTIONS]"),
+    (
+        "usage.detect_encoding",
+        "{0} detect-encoding <INPUT> [OPTIONS]",
+    ),
+    ("usage.completions", "{0} completions <SHELL>"),
+    ("arg.help", "Show help"),
+    ("arg.version", "Show the version"),
+    (
+        "arg.list_formats",
+        "List all supported input / output formats",
+    ),
+    (
+        "arg.lang",
+        "Interface language: zh (Chinese) or en (English); taken from LC_ALL / LC_MESSAGES / LANG by default",
+    ),
+    (
+        "arg.convert_positionals",
+        r#"[FPS] <INPUT> <OUTPUT>, the same as --fps / --input / --output;
+anything already given as an option is left out"#,
+    ),
+    (
+        "arg.check_positionals",
+        "[FPS] <INPUT>, the same as --fps / --input",
+    ),
+    (
+        "arg.fps",
+        r#"Frame rate, integer or decimal, e.g. 24, 25, 29.97, 23.976;
+when omitted it is inferred from the EDL (largest frame field, FCM, drop-frame separators,
+title hints); if ambiguous the candidates are listed and the program exits"#,
+    ),
+    (
+        "arg.input",
+        "Source EDL, or JSON exported with --dump-json and edited",
+    ),
+    (
+        "arg.output",
+        "Subtitle file to create; an existing file is never overwritten, a timestamp is appended instead",
+    ),
+    (
+        "arg.input_encoding",
+        "Input encoding, used in preference to detection",
+    ),
+    (
+        "arg.format",
+        "Output format name (see --list-formats); defaults to the output extension, otherwise srt",
+    ),
+    (
+        "arg.check_format",
+        "Also check what this output format cannot represent (see --list-formats)",
+    ),
+    (
+        "arg.dump_json",
+        r#"Also export the parsed event list (JSON); "-" means standard output;
+the exported JSON can be edited and used as an input file"#,
+    ),
+    (
+        "arg.reel",
+        r#"Add another EDL reel, repeatable; all reels are merged into one continuous subtitle file
+numbered across reels, overlaps are reported"#,
+    ),
+    (
+        "arg.reel_starts",
+        "Comma-separated start timecode of each reel (one per reel)",
+    ),
+    (
+        "arg.reel_lengths",
+        r#"Comma-separated length timecode of each reel (all but the last, one fewer than the reels);
+without either, each reel follows the last record out of the previous one"#,
+    ),
+    (
+        "arg.conform",
+        r#"Conform: move subtitles translated against the old EDL onto the new EDL.
+Events are matched by clip name + source timecode and subtitles move to the new position;
+subtitles of cut clips are dropped and new clips are listed for translation"#,
+    ),
+    (
+        "arg.tracks",
+        r#"Bilingual: primary and secondary tracks, comma-separated, e.g. A1,A2;
+secondary events are matched to the primary by record in, and the primary timing wins"#,
+    ),
+    (
+        "arg.translations",
+        r#"Bilingual: translation table keyed by clip name (.csv / .tsv / .json);
+CSV / TSV: first column clip name, second column translation;
+JSON: {"clip": "translation"} or [{"clip_name": ..., "text": ...}]"#,
+    ),
+    (
+        "arg.bilingual_layout",
+        r#"Bilingual layout: stacked (default, two lines per subtitle) or
+top-bottom (primary at the bottom, secondary at the top, ASS only)"#,
+    ),
+    (
+        "arg.transcribe",
+        r#"Send the source file of every audio event (track starting with A) to a local transcription
+service and use the result as subtitle text; failed events keep the clip name and are reported"#,
+    ),
+    (
+        "arg.transcribe_url",
+        r#"Transcription endpoint (WhisperLiveKit / OpenAI compatible),
+default http://localhost:8000/v1/audio/transcriptions; implies --transcribe"#,
+    ),
+    (
+        "arg.media_path",
+        r#"Directory (searched recursively) for source files, repeatable; paths in "* SOURCE FILE:"
+comments are tried first, then file names here; implies --transcribe"#,
+    ),
+    (
+        "arg.transcribe_cache",
+        r#"Transcription cache directory, keyed by the SHA-256 of file contents,
+default .edl2srt-cache next to the input EDL; implies --transcribe"#,
+    ),
+    (
+        "arg.transcribe_language",
+        "Recognition language, e.g. zh, en; detected by the service by default; implies --transcribe",
+    ),
+    (
+        "arg.origin",
+        r#"Start timecode of the finished file, e.g. 01:00:00:00; it is subtracted from all output times
+and earlier subtitles are dropped. Usually needed for chapter output
+(ffmetadata / mkvchapters / youtube) and LRC lyrics"#,
+    ),
+    (
+        "arg.text_source",
+        r#"Subtitle text source: clip-name (default), transcribe (same as --transcribe)
+or locators (Avid "* LOC:" locators, each lasting until the next locator or the clip out)"#,
+    ),
+    (
+        "arg.locator_color",
+        "Only use locators of this colour, e.g. YELLOW, repeatable (implies --text-source locators)",
+    ),
+    (
+        "arg.speaker_pattern",
+        r#"Regex extracting the speaker from the clip name, using the capture group named speaker
+(or the first one), e.g. "^[A-Z]_(?P<speaker>[^_]+)_" turns A_Ming_line03 into Ming / line03"#,
+    ),
+    (
+        "arg.speaker_reel",
+        "reel=speaker, used when the regex does not match, repeatable",
+    ),
+    (
+        "arg.speaker_style",
+        r#"Speaker labels: prefix (default, Ming: line), dash (a leading - per line in dialogue),
+ass-styles (one ASS style per speaker) or vtt-voice (WebVTT <v Ming>);
+clips overlapping in record time become one subtitle with one line per speaker"#,
+    ),
+    (
+        "arg.render",
+        r#"Also render a burn-in preview into this directory: one PNG still per subtitle (white text
+with black outline, bottom centre, laid out like the ASS output) plus an index.html with timecodes"#,
+    ),
+    (
+        "arg.render_font",
+        "Font file for the preview (TTF / OTF), required with --render",
+    ),
+    ("arg.render_size", "Preview resolution, default 1920x1080"),
+    (
+        "arg.preset",
+        "Use a named preset from the config file ([preset.<name>])",
+    ),
+    (
+        "arg.config",
+        r#"Config file to use; by default edl2srt.toml is looked up in the current directory,
+then in the user config directory"#,
+    ),
+    (
+        "arg.edit",
+        r#"Open a terminal interface before writing: a table of number, in / out, duration,
+reading speed, text and warnings; edit text (| starts a new line), delete (d),
+merge with the next (m), nudge in ([ ]), out ({ }) or the whole subtitle (< >) by one frame,
+undo (u); w writes in the chosen format, q quits"#,
+    ),
+    (
+        "arg.stream",
+        r#"Stream very large EDLs or logs: decode, parse and write as the file is read, with memory use
+independent of file size. Encoding detection and fps inference only look at the first 64 KiB;
+srt / vtt / sbv are written cue by cue, other formats still collect all subtitles. No QC;
+cannot be combined with reels, conform, bilingual options, transcription, locators,
+speakers, --edit, --render or --dump-json"#,
+    ),
+    ("arg.diff_old", "The old version of the EDL"),
+    ("arg.diff_new", "The new version of the EDL"),
+    (
+        "arg.diff_fps",
+        "Frame rate; inferred from both EDLs when omitted (nominal rate only)",
+    ),
+    ("arg.diff_json", "Also export the change report as JSON"),
+    (
+        "arg.diff_change_list",
+        "Also export an EDL with only the added and changed events",
+    ),
+    ("arg.detect_input", "File to diagnose"),
+    (
+        "arg.encoding_hint",
+        "Region hint jp / cn / tw / kr, repeatable; all four are tried by default",
+    ),
+    (
+        "arg.lines",
+        "Preview lines per candidate encoding, default 5",
+    ),
+    ("arg.shell", "bash, zsh, fish, elvish or powershell"),
+    // 帮助
+    (
+        "help.main",
+        r#"Examples:
+  {0} 25 input.edl output.srt
+  {0} input.edl output.srt
+  {0} convert --fps 25 -i input.edl -o output.vtt
+  {0} check input.edl --preset netflix-zh
+  {0} diff v3.edl v4.edl --json changes.json --change-list changes.edl
+  {0} detect-encoding input.edl --encoding-hint jp
+  {0} completions bash > /etc/bash_completion.d/{0}
+
+Notes:
+  - without a subcommand convert is assumed; see {0} <COMMAND> --help for its options
+  - usage errors exit with code 2, failed conversions or checks with 1"#,
+    ),
+    (
+        "help.convert",
+        r#"Config file:
+  Values under [default] in edl2srt.toml always apply, the preset chosen with --preset
+  overrides them, and command-line options override the config file. Keys match the
+  command-line options, plus three sub-tables: cleanup (text cleanup), qc (QC limits) and
+  speakers (speaker rules):
+    [preset.netflix-zh]
+    fps = 23.976
+    format = "srt"
+    text-source = "transcribe"
+    [preset.netflix-zh.cleanup]
+    replace = [["_", " "]]
+    collapse-spaces = true
+    [preset.netflix-zh.qc]
+    max-chars-per-line = 16
+    min-duration-ms = 833
+    [preset.netflix-zh.speakers]
+    pattern = "^[A-Z]_(?P<speaker>[^_]+)_"
+    reels = {{ R01 = "Ming" }}
+    style = "dash"
+
+Examples:
+  {0} 30 input.edl output.srt
+  {0} input.edl output.srt
+  {0} convert --fps 30 --input input.edl --output output.srt
+  {0} 30 input.edl output.srt --input-encoding shift_jis
+  {0} 25 input.edl output.srt --input-encoding utf-16le
+  {0} 29.97 project/main.edl subtitles/final_sub.srt
+  {0} 25 input.edl output.vtt
+  {0} 25 input.edl output.stl
+  {0} 29.97 input.edl output.scc
+  {0} 25 input.edl output.srt --dump-json events.json
+  {0} 24 r1.edl feature.srt --reel r2.edl --reel r3.edl
+  {0} 24 r1.edl feature.srt --reel r2.edl --reel-lengths 00:19:58:12
+  {0} 25 v4.edl v4_en.srt --conform v3.edl v3_en.srt
+  {0} 25 input.edl output.srt --tracks A1,A2
+  {0} 25 input.edl output.ass --translations en.csv --bilingual-layout top-bottom
+  {0} 25 input.edl output.srt --transcribe --media-path D:\VO --transcribe-language zh
+  {0} 25 chapters.edl chapters.ffmeta --origin 01:00:00:00
+  {0} 25 chapters.edl youtube.txt --format youtube --origin 01:00:00:00
+  {0} 25 mv.edl mv.sbv --origin 01:00:00:00
+  {0} 25 mv.edl mv.lrc --format lrc-enhanced --origin 01:00:00:00
+  {0} input.edl output.srt --preset netflix-zh
+  {0} 25 avid.edl output.srt --locator-color YELLOW
+  {0} 25 input.edl output.vtt --speaker-pattern "^[A-Z]_(?P<speaker>[^_]+)_" --speaker-style vtt-voice
+  {0} 25 input.edl output.srt --edit
+  {0} 25 huge.edl huge.srt --stream --origin 01:00:00:00
+  {0} 25 input.edl output.srt --render preview --render-font NotoSansSC-Regular.otf
+  {0} --lang en 25 input.edl output.srt
+
+Notes:
+  - automatic detection relies on chardetng and suits most common text encodings
+  - when the input encoding is known, pass --input-encoding for more reliable results
+  - relative and absolute paths are supported
+  - an existing output file is never overwritten; the new file is renamed instead
+  - make sure the output directory is writable"#,
+    ),
+    (
+        "help.completions",
+        r#"Examples:
+  {0} completions bash > /etc/bash_completion.d/{0}
+  {0} completions zsh > ~/.zfunc/_{0}
+  {0} completions fish > ~/.config/fish/completions/{0}.fish
+  {0} completions powershell >> $PROFILE"#,
+    ),
+];
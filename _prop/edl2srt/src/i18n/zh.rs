@@ -0,0 +1,809 @@
+//! 中文消息，也是默认语言。
+
+pub(super) const MESSAGES: &[(&str, &str)] = &[
+    // 错误
+    ("error.unsupported_encoding", "不支持的编码: {}"),
+    ("error.unknown_format", "不支持的格式: {}"),
+    ("error.parse", "第 {} 行: {}"),
+    ("error.json", "JSON 格式错误: {}"),
+    ("error.invalid", "参数无效: {}"),
+    ("error.transcribe", "转写失败: {}"),
+    ("error.config", "配置文件有误: {}"),
+    ("error.config_file", "配置文件 {} 有误: {}"),
+    ("error.frame_rate", "无法推断帧率：{}"),
+    ("error.frame_rate_candidates", "。候选帧率: {}"),
+    ("error.frame_rate_hint", "。请在命令行中明确指定帧率"),
+    // 时间码与警告
+    ("model.invalid_timecode", "无效的时间码: {}"),
+    ("model.warning", "第 {} 行: {}"),
+    // 质检
+    ("qc.issue", "第 {} 条字幕: {}"),
+    ("qc.retimed_event", "第 {} 行事件 {}: "),
+    ("qc.freeze", "定格"),
+    ("qc.reverse", "倒放 {}%"),
+    ("qc.speed", "变速 {}%"),
+    ("qc.no_m2", "（源时长与录制时长不同，没有 M2 行）"),
+    ("qc.line_too_long", "单行 {} 个字符，超过上限 {}"),
+    ("qc.too_many_lines", "共 {} 行，超过上限 {}"),
+    ("qc.too_short", "显示 {} 毫秒，短于下限 {}"),
+    ("qc.too_long", "显示 {} 毫秒，长于上限 {}"),
+    ("qc.too_fast", "阅读速度 {} 字/秒，超过上限 {}"),
+    // 帧率
+    ("fps.no_timecodes", "没有找到任何时间码"),
+    ("fps.no_candidate", "帧字段最大值 {} 不符合任何常见帧率"),
+    ("fps.ambiguous", "帧字段最大值为 {}，有多个帧率都符合"),
+    ("fps.frame_out_of_range", "帧字段 {} 超出了帧率 {} 的范围"),
+    // EDL 解析
+    ("reader.record_out_before_in", "录制出点早于入点"),
+    ("reader.bad_event_line", "无法识别的事件行，已忽略"),
+    ("reader.m2_without_event", "M2 行之前没有事件，已忽略"),
+    (
+        "reader.duplicate_m2",
+        "同一事件出现多个 M2 行，只使用第一个",
+    ),
+    ("reader.bad_m2", "无法识别的 M2 行，已忽略"),
+    ("reader.aud_without_event", "AUD 行之前没有事件，已忽略"),
+    ("reader.bad_aud", "无法识别的 AUD 行，已忽略"),
+    ("reader.note_without_event", "注释行之前没有事件，已忽略"),
+    (
+        "reader.duplicate_clip_name",
+        "同一事件出现多个片段名，只使用第一个",
+    ),
+    (
+        "reader.bad_locator",
+        "无法识别的定位点（LOC）行，只作为注释保留",
+    ),
+    // 双语
+    ("bilingual.empty_translation", "片段 {} 的译文为空"),
+    (
+        "bilingual.missing_translation",
+        "片段 {} 在翻译表中没有译文",
+    ),
+    ("bilingual.empty_track", "轨道 {} 上没有字幕事件"),
+    (
+        "bilingual.time_mismatch",
+        "主轨 {}-{} 与副轨 {}-{}（第 {} 行）时间不一致，以主轨为准",
+    ),
+    (
+        "bilingual.unmatched_primary",
+        "主轨字幕 {} 在副轨 {} 上没有对应",
+    ),
+    (
+        "bilingual.unmatched_secondary",
+        "副轨字幕 {} 在主轨 {} 上没有对应",
+    ),
+    (
+        "bilingual.row_too_short",
+        "翻译表每行至少需要片段名和译文两列",
+    ),
+    ("bilingual.unclosed_quote", "引号没有闭合"),
+    ("bilingual.json_shape", "JSON 翻译表应为对象或对象数组"),
+    ("bilingual.json_not_string", "片段 {} 的译文不是字符串"),
+    (
+        "bilingual.json_missing_field",
+        "JSON 翻译表第 {} 项缺少 clip_name 或 text",
+    ),
+    // 配置文件
+    ("list.separator", "、"),
+    (
+        "config.no_presets",
+        "没有名为 {} 的预设（文件中没有定义任何预设）",
+    ),
+    ("config.unknown_preset", "没有名为 {} 的预设，可选: {}"),
+    // EDL 比较
+    ("diff.added", "新增"),
+    ("diff.removed", "删除"),
+    ("diff.retimed", "改时间"),
+    ("diff.retexted", "改文本"),
+    ("diff.retimed_and_retexted", "改时间+文本"),
+    (
+        "diff.summary",
+        "新增 {}，删除 {}，改时间 {}，改文本 {}，改时间+文本 {}，未变 {}",
+    ),
+    ("diff.column_change", "变更"),
+    ("diff.column_event", "事件"),
+    ("diff.column_old_time", "旧时间"),
+    ("diff.column_new_time", "新时间"),
+    ("diff.column_in_delta", "入点差"),
+    ("diff.column_out_delta", "出点差"),
+    ("diff.column_text", "文本"),
+    // JSON 导出
+    ("dump.unsupported_version", "不支持的 JSON 版本: {}"),
+    (
+        "dump.bad_fps",
+        "JSON 中的帧率必须是大于 0 的有限数字，实际为 {}",
+    ),
+    // 字幕编辑
+    ("editor.overlap", "与第 {} 条重叠"),
+    ("editor.out_not_after_in", "出点不晚于入点"),
+    // 编码诊断
+    ("encoding.guess", "自动检测"),
+    ("encoding.hinted_guess", "提示 {}"),
+    // 格式
+    ("format.input_formats", "输入格式:"),
+    ("format.output_formats", "输出格式:"),
+    ("format.ass", "Advanced SubStation Alpha 字幕"),
+    ("format.ffmetadata", "FFmpeg 元数据章节（FFMETADATA1）"),
+    ("format.mkvchapters", "Matroska 章节 XML（mkvmerge）"),
+    ("format.youtube", "YouTube 简介章节列表（0:00 标题）"),
+    ("format.youtube_start", "开始"),
+    (
+        "format.edl",
+        "CMX3600 / Premiere / Resolve / Avid 导出的 EDL",
+    ),
+    ("format.json", "edl2srt --dump-json 导出的事件列表"),
+    ("format.lrc", "LRC 歌词（[mm:ss.xx] 时间标签）"),
+    (
+        "format.lrc_enhanced",
+        "增强版 LRC 歌词（另加 <mm:ss.xx> 行首行尾时间）",
+    ),
+    ("format.sbv", "YouTube SubViewer 字幕"),
+    ("format.scc", "Scenarist SCC（CEA-608 pop-on，29.97 丢帧）"),
+    ("format.srt", "SubRip 字幕（UTF-8 无 BOM）"),
+    ("format.stl", "EBU STL（Tech 3264，25 / 30 帧）"),
+    ("format.vtt", "WebVTT 网页字幕"),
+    (
+        "lrc.late_start",
+        "第一句歌词从 {} 开始，LRC 以歌曲开头为 0，可能需要用 --origin 指定起点",
+    ),
+    (
+        "chapters.late_start",
+        "第一章从 {} 开始，章节时间以成片开头为 0，可能需要用 --origin 指定起点",
+    ),
+    (
+        "chapters.youtube_padded",
+        "第一章不在 0:00，已在开头补一个 0:00 章节“{}”",
+    ),
+    ("sbv.bad_time_line", "无法识别的 SBV 时间行: {}"),
+    ("srt.bad_time_line", "无法识别的时间行: {}"),
+    (
+        "scc.unsupported_chars",
+        "CEA-608 字符集无法表示 {} 种字符（例如 “{}”），将写成 ?",
+    ),
+    (
+        "scc.line_too_long",
+        "第 {} 条字幕有超过 {} 个字符的行，超出部分会被截掉",
+    ),
+    (
+        "scc.too_many_rows",
+        "第 {} 条字幕有 {} 行，pop-on 字幕只保留最后 {} 行",
+    ),
+    (
+        "stl.unsupported_chars",
+        "EBU STL 拉丁字符表无法表示 {} 种字符（例如 “{}”），将写成 ?",
+    ),
+    (
+        "stl.line_too_long",
+        "第 {} 条字幕有超过 {} 个字符的行，部分播出设备会截断",
+    ),
+    (
+        "stl.not_stl",
+        "不是 EBU STL 文件（GSI 块缺失或磁盘格式不是 STL25.01 / STL30.01）",
+    ),
+    ("stl.bad_tti_length", "TTI 部分长度 {} 不是 128 的整数倍"),
+    (
+        "stl.unsupported_fps",
+        "EBU STL 只支持 25 / 29.97 / 30 帧（及其倍数），当前帧率为 {}",
+    ),
+    (
+        "stl.past_24_hours",
+        "第 {} 条字幕的时间码 {} 超过了 STL 能表示的 23:59:59:xx，请用 --origin 把起点前移",
+    ),
+    (
+        "stl.too_many_subtitles",
+        "共有 {} 条字幕，超过了 EBU STL 字幕编号能表示的 {} 条，请拆成多个文件",
+    ),
+    (
+        "stl.too_many_blocks",
+        "需要 {} 个 TTI 块，超过了 EBU STL 的上限 {}，请拆成多个文件",
+    ),
+    // 卷、预览、说话人、转写、网页版
+    (
+        "reels.overlap",
+        "{}: 字幕开始于 {}，早于上一卷 {} 的字幕结束时间 {}（跨卷重叠）",
+    ),
+    ("reels.starts_count", "给出了 {} 个卷起点，但有 {} 卷"),
+    (
+        "reels.lengths_count",
+        "给出了 {} 个卷长度，应为 {} 个（最后一卷不需要长度）",
+    ),
+    ("render.bad_font", "无法读取字体: {}"),
+    ("render.sheet_title", "edl2srt 字幕预览"),
+    ("render.sheet_summary", "共 {} 条字幕，帧率 {}"),
+    ("render.still_alt", "第 {} 条"),
+    ("speaker.bad_regex", "说话人正则无效: {}"),
+    ("transcribe.http_only", "转写地址只支持 http://，实际为: {}"),
+    ("transcribe.bad_port", "转写地址的端口无效: {}"),
+    ("transcribe.missing_host", "转写地址缺少主机名: {}"),
+    ("transcribe.source_not_found", "找不到片段 {} 的源文件"),
+    ("transcribe.empty_result", "{} 的转写结果为空"),
+    ("transcribe.incomplete_response", "转写服务的响应不完整"),
+    ("transcribe.not_http", "转写服务的响应不是 HTTP"),
+    ("transcribe.bad_chunked", "分块响应格式错误"),
+    ("transcribe.no_text", "响应中没有 text 字段"),
+    ("web.unsupported_format", "{}（网页版不支持）"),
+    (
+        "web.decode_errors",
+        "按 {} 解码时遇到非法字节，已用替代字符继续处理",
+    ),
+    ("web.bad_fps", "帧率必须是大于 0 的有限数字，实际为 {}"),
+    // 终端编辑界面
+    (
+        "tui.help",
+        "↑↓ 选择  Enter 改文本  d 删除  m 与下一条合并  [ ] 入点±1帧  { } 出点±1帧  < > 整条±1帧  u 撤销  w 写出  q 放弃",
+    ),
+    ("tui.deleted", "已删除第 {} 条"),
+    ("tui.merged", "已合并第 {} 条和第 {} 条"),
+    ("tui.nothing_to_merge", "最后一条没有可合并的下一条"),
+    ("tui.at_boundary", "已到边界，无法继续平移"),
+    ("tui.undone", "已撤销"),
+    ("tui.nothing_to_undo", "没有可撤销的修改"),
+    ("tui.title", " edl2srt --edit · {} 条 · {}{} "),
+    ("tui.column_in", "入点"),
+    ("tui.column_out", "出点"),
+    ("tui.column_duration", "时长"),
+    ("tui.column_text", "文本"),
+    ("tui.column_warnings", "警告"),
+    ("tui.warning", "警告: {}"),
+    ("tui.warning_separator", "；"),
+    ("tui.no_cues", "没有字幕"),
+    ("tui.current", " 当前字幕 "),
+    ("tui.edit_prefix", "文本: "),
+    (
+        "tui.edit_help",
+        r#"Enter 确认  Esc 取消  用 {} 表示换行，\| 表示字符 |"#,
+    ),
+    (
+        "tui.confirm_quit",
+        "有未写出的修改，再按 q 放弃，按其它键返回",
+    ),
+    // 命令行
+    ("cli.error", "错误: {}"),
+    (
+        "cli.top_bottom_ass_only",
+        "错误: 双语上下排布（top-bottom）只支持 ASS 输出。",
+    ),
+    (
+        "cli.speaker_style_format",
+        "错误: 说话人标注方式 {} 只支持 {} 输出。",
+    ),
+    ("cli.fps_inferred", "未指定帧率，根据 EDL 内容推断为: {}"),
+    ("cli.warning", "警告: {}"),
+    ("cli.reel_merge_failed", "合并多卷失败: {}"),
+    ("cli.reel_start", "卷 {} 起点: {}"),
+    ("cli.transcribing", "正在转写音频片段: {}:{}{}"),
+    (
+        "cli.transcribe_done",
+        "转写完成: 新转写 {} 个，使用缓存 {} 个，失败 {} 个（缓存目录: {}）",
+    ),
+    ("cli.json_dumped", "已导出 JSON: {}"),
+    ("cli.json_dump_failed", "导出 JSON 时出错: {}"),
+    (
+        "cli.no_speakers",
+        "警告: 没有识别出任何说话人，请检查说话人规则",
+    ),
+    ("cli.no_locators", "警告: 没有找到可用的定位点（* LOC: 行）"),
+    ("cli.before_origin", "警告: {} 条字幕早于起点 {}，已忽略"),
+    ("cli.qc_passed", "QC 通过"),
+    ("cli.edit_discarded", "已放弃编辑，未写出文件。"),
+    ("cli.tui_failed", "无法打开终端界面: {}"),
+    ("cli.output_renamed", "输出文件已存在，已自动改名为: {}"),
+    ("cli.converted", "转换成功! 输出文件: {}"),
+    ("cli.write_failed", "写入输出文件时出错: {}"),
+    ("cli.preview_written", "已生成预览: {}"),
+    ("cli.preview_failed", "生成预览时出错: {}"),
+    ("cli.read_input_failed", "无法读取输入文件 {}: {}"),
+    ("cli.decode_failed", "解码输入文件失败: {}"),
+    ("cli.encoding_given", "已使用指定编码: {}"),
+    (
+        "cli.encoding_detected_sample",
+        "未指定编码，根据文件开头自动检测结果: {}",
+    ),
+    (
+        "cli.fps_inferred_sample",
+        "未指定帧率，根据文件开头推断为: {}",
+    ),
+    (
+        "cli.stream_buffered",
+        "警告: {} 格式需要先收集全部字幕才能写出，内存占用与字幕条数成正比",
+    ),
+    ("cli.stream_no_qc", "警告: 流式转换不做质检"),
+    (
+        "cli.stream_decode_errors",
+        "警告: 按 {} 解码时检测到部分非法字节，已使用替代字符继续处理。",
+    ),
+    ("cli.stream_done", "流式转换完成: 事件 {} 个，字幕 {} 条"),
+    (
+        "cli.font_missing_glyphs",
+        "警告: 字体 {} 中缺少 {} 个字符（例如 {}），预览中显示为缺字符号",
+    ),
+    (
+        "cli.large_input",
+        "提示: 输入文件有 {} MiB，会整个读进内存；只需普通转换时可以加 --stream 边读边写",
+    ),
+    (
+        "cli.encoding_given_errors",
+        "已使用指定编码: {}，但检测到部分非法字节，已使用替代字符继续处理。",
+    ),
+    (
+        "cli.encoding_detected_errors",
+        "未指定编码，自动检测结果: {}，但检测到部分非法字节，已使用替代字符继续处理。",
+    ),
+    ("cli.encoding_detected", "未指定编码，自动检测结果: {}"),
+    ("cli.parse_failed", "解析输入文件失败: {}"),
+    ("cli.read_subtitle_failed", "无法读取字幕文件 {}: {}"),
+    ("cli.unknown_subtitle_format", "无法识别字幕文件格式: {}"),
+    ("cli.parse_subtitle_failed", "解析字幕文件失败: {}"),
+    (
+        "cli.conform_done",
+        "套底完成: 保留 {} 条（其中移动 {} 条），剪掉 {} 条，无法对应 {} 条，新片段 {} 个",
+    ),
+    ("cli.conform_cut", "  已剪掉: {} {}"),
+    ("cli.conform_unmatched", "  无法对应到旧 EDL: {} {}"),
+    ("cli.conform_needs_translation", "  需要翻译: 事件 {} {} {}"),
+    (
+        "cli.conform_nothing_matched",
+        "译文字幕中没有一条能与旧 EDL 对应；请确认帧率与旧 EDL 一致，字幕导出时用了 --origin 的，这里也要给出同一个 --origin",
+    ),
+    (
+        "cli.bad_encoding_hint",
+        "无效的地区提示 '{}'，可选: jp、cn、tw、kr",
+    ),
+    ("cli.bad_lines", "无效的行数 '{}'。"),
+    ("cli.read_failed", "读取输入文件失败: {}"),
+    ("cli.detect_file", "文件: {}（{} 字节）"),
+    ("cli.detect_no_bom", "BOM: 无"),
+    ("cli.detect_confident", "可信"),
+    ("cli.detect_not_confident", "不可信"),
+    ("cli.detect_certain", "确定"),
+    ("cli.detect_replacements", "有（{} 个替换字符）"),
+    ("cli.detect_no_errors", "无"),
+    (
+        "cli.detect_candidate",
+        "[{}] {}  来源: {}  猜测: {}  解码错误: {}",
+    ),
+    (
+        "cli.detect_recommend",
+        "建议: --input-encoding {}（与预览对照确认后使用）",
+    ),
+    ("cli.diff_print_failed", "输出变更表时出错: {}"),
+    ("cli.diff_report_written", "已导出变更报告: {}"),
+    ("cli.diff_report_failed", "导出变更报告时出错: {}"),
+    ("cli.change_list_written", "已导出变更 EDL: {}"),
+    ("cli.change_list_failed", "导出变更 EDL 时出错: {}"),
+    ("cli.read_translations_failed", "无法读取翻译表 {}: {}"),
+    ("cli.decode_translations_failed", "解码翻译表失败: {}"),
+    ("cli.parse_translations_failed", "解析翻译表失败: {}"),
+    (
+        "cli.unused_translation",
+        "警告: 翻译表中的 {} 没有对应的片段",
+    ),
+    (
+        "cli.bilingual_done",
+        "双语字幕: 共 {} 条，其中 {} 条没有副语言",
+    ),
+    ("cli.reel_warning", "警告: {}: {}"),
+    ("cli.too_few_arguments", "参数不足。"),
+    (
+        "cli.bad_fps",
+        "无效的帧率格式 '{}'。请输入数字（例如 24 或 23.976）。",
+    ),
+    ("cli.fps_not_positive", "帧率必须是大于 0 的有限数字。"),
+    ("cli.extra_argument", "多余的参数: {}"),
+    (
+        "cli.fps_without_paths",
+        "'{}' 被当作帧率，后面缺少输入或输出文件。",
+    ),
+    (
+        "cli.bad_speaker_style",
+        "无效的说话人标注方式 '{}'，可选: prefix、dash、ass-styles、vtt-voice",
+    ),
+    (
+        "cli.bad_speaker_reel",
+        "参数 --speaker-reel 应为 卷名=说话人，实际为: {}",
+    ),
+    (
+        "cli.bad_text_source",
+        "无效的文本来源 '{}'，可选: clip-name、transcribe、locators",
+    ),
+    ("cli.missing_value", "参数 {} 缺少取值。"),
+    ("cli.unknown_option", "无法识别的参数: {}"),
+    (
+        "cli.tracks_and_translations",
+        "--tracks 和 --translations 不能同时使用。",
+    ),
+    (
+        "cli.config_fps_not_positive",
+        "配置文件中的帧率必须是大于 0 的有限数字。",
+    ),
+    (
+        "cli.config_bad_origin",
+        "配置文件中的 origin 时间码无效: {}",
+    ),
+    (
+        "cli.tracks_and_translations_config",
+        "--tracks 和 --translations 不能同时使用（请检查配置文件）。",
+    ),
+    (
+        "cli.conform_and_bilingual",
+        "--conform 不能与双语选项同时使用。",
+    ),
+    (
+        "cli.layout_needs_bilingual",
+        "--bilingual-layout 需要配合 --tracks 或 --translations 使用。",
+    ),
+    (
+        "cli.locators_conflict",
+        "定位点字幕（--text-source locators）不能与套底或双语选项同时使用。",
+    ),
+    (
+        "cli.edit_top_bottom",
+        "--edit 不支持双语上下排布（top-bottom）。",
+    ),
+    (
+        "cli.speakers_need_rules",
+        "说话人标注需要 --speaker-pattern 或 --speaker-reel。",
+    ),
+    (
+        "cli.speakers_conflict",
+        "说话人标注不能与套底、双语或定位点字幕同时使用。",
+    ),
+    (
+        "cli.edit_ass_styles",
+        "--edit 不支持按说话人分样式（ass-styles）。",
+    ),
+    (
+        "cli.render_needs_font",
+        "--render 需要用 --render-font 指定字体文件（TTF / OTF）。",
+    ),
+    (
+        "cli.render_options_alone",
+        "--render-font 和 --render-size 需要配合 --render 使用。",
+    ),
+    ("cli.feature_bilingual", "双语选项"),
+    ("cli.feature_transcribe", "转写"),
+    ("cli.feature_locators", "定位点字幕"),
+    ("cli.feature_speakers", "说话人标注"),
+    ("cli.stream_conflict", "--stream 不能与 {} 同时使用。"),
+    (
+        "cli.config_not_found",
+        "找不到配置文件 {}（当前目录和用户配置目录中都没有），无法使用预设。",
+    ),
+    (
+        "cli.bad_tracks",
+        r#"参数 --tracks 应为 "主轨,副轨"，例如 A1,A2，实际为: {}"#,
+    ),
+    (
+        "cli.bad_render_size",
+        "无效的分辨率 '{}'，应为 宽x高，例如 1920x1080",
+    ),
+    (
+        "cli.bad_layout",
+        "无效的排布方式 '{}'，可选: stacked、top-bottom",
+    ),
+    ("cli.bad_lang", "无效的界面语言 '{}'，可选: zh、en"),
+    ("cli.see_help", "用 {} 查看帮助。"),
+    (
+        "cli.unknown_option_suggest",
+        "无法识别的参数: {}，是否想用 {}？",
+    ),
+    ("cli.unknown_subcommand", "未知的子命令: {}"),
+    ("cli.invalid_value", "参数 {} 的取值无效: {}"),
+    ("cli.invalid_value_reason", "参数 {}: {}"),
+    (
+        "cli.invalid_value_choices",
+        "参数 {} 的取值 '{}' 无效，可选: {}",
+    ),
+    ("cli.missing_arguments", "缺少必需的参数: {}"),
+    ("cli.used_twice", "参数 {} 只能给出一次"),
+    ("cli.argument_conflict", "参数 {} 不能与 {} 同时使用"),
+    ("cli.wrong_number_of_values", "参数 {} 需要 {} 个取值"),
+    ("cli.bad_timecode", "无效的时间码: {}"),
+    ("cli.check_passed", "检查通过：{} 个事件，{} 条字幕。"),
+    ("cli.check_failed", "{} 个事件，{} 条字幕，发现 {} 个问题。"),
+    // 命令行选项
+    ("cli.heading.usage", "用法:"),
+    ("cli.heading.options", "选项"),
+    ("cli.heading.arguments", "参数"),
+    ("cli.heading.commands", "子命令"),
+    (
+        "about.main",
+        r#"EDL 转字幕工具
+
+将 EDL 文件转换为 SRT 等字幕文件。输入编码已知时优先使用，否则自动识别；
+输出 SRT 固定为 UTF-8 无 BOM。"#,
+    ),
+    (
+        "about.convert",
+        "把 EDL 转换为字幕文件（省略子命令时的默认操作）",
+    ),
+    (
+        "about.check",
+        "检查 EDL 和生成的字幕，只报告问题，不写出文件",
+    ),
+    ("about.diff", "比较两个版本的 EDL，列出字幕的变化"),
+    (
+        "about.detect_encoding",
+        "诊断输入文件的编码，用于解码出现乱码时",
+    ),
+    ("about.completions", "生成 shell 补全脚本"),
+    (
+        "usage.main",
+        r#"{0} <子命令> [选项]
+  {0} [FPS] <INPUT> <OUTPUT> [选项]"#,
+    ),
+    (
+        "usage.convert",
+        r#"{0} convert [FPS] <INPUT> <OUTPUT> [选项]
+  {0} convert --input <INPUT> --output <OUTPUT> [--fps <FPS>] [选项]
+  {0} [FPS] <INPUT> <OUTPUT> [选项]"#,
+    ),
+    ("usage.check", "{0} check [FPS] <INPUT> [选项]"),
+    ("usage.diff", "{0} diff <OLD> <NEW> [选项]"),
+    (
+        "usage.detect_encoding",
+        "{0} detect-encoding <INPUT> [选项]",
+    ),
+    ("usage.completions", "{0} completions <SHELL>"),
+    ("arg.help", "显示帮助"),
+    ("arg.version", "显示版本号"),
+    ("arg.list_formats", "列出所有支持的输入 / 输出格式"),
+    (
+        "arg.lang",
+        "界面语言：zh（中文）或 en（英文）；默认按 LC_ALL / LC_MESSAGES / LANG 判断",
+    ),
+    (
+        "arg.convert_positionals",
+        r#"[FPS] <INPUT> <OUTPUT>，与 --fps / --input / --output 等价，
+已用选项给出的不再重复"#,
+    ),
+    (
+        "arg.check_positionals",
+        "[FPS] <INPUT>，与 --fps / --input 等价",
+    ),
+    (
+        "arg.fps",
+        r#"帧率，支持整数或小数，例如 24、25、29.97、23.976；
+省略时根据 EDL 内容（帧字段最大值、FCM、丢帧分隔符、标题提示）推断，
+无法唯一确定时会列出候选帧率并退出"#,
+    ),
+    (
+        "arg.input",
+        "源 EDL 文件，或 --dump-json 导出后修改过的 JSON",
+    ),
+    (
+        "arg.output",
+        "生成的字幕文件；已存在时自动追加时间戳改名，不会覆盖",
+    ),
+    (
+        "arg.input_encoding",
+        "已知输入编码时优先使用；未提供时自动检测",
+    ),
+    (
+        "arg.format",
+        "输出格式名（见 --list-formats）；未提供时按输出扩展名判断，默认 srt",
+    ),
+    (
+        "arg.check_format",
+        "按这种输出格式检查它表示不了的内容（见 --list-formats）",
+    ),
+    (
+        "arg.dump_json",
+        r#"额外导出解析后的事件列表（JSON），"-" 表示输出到标准输出；
+导出的 JSON 可修改后再作为输入文件使用"#,
+    ),
+    (
+        "arg.reel",
+        r#"追加一卷 EDL，可重复使用；所有卷合并为一个连续的字幕文件，
+字幕跨卷统一编号，跨卷重叠会给出警告"#,
+    ),
+    (
+        "arg.reel_starts",
+        "逗号分隔的每卷起点时间码（数量与卷数一致）",
+    ),
+    (
+        "arg.reel_lengths",
+        r#"逗号分隔的每卷长度时间码（最后一卷除外，个数为卷数减一）；
+两者都不给时，每卷紧接在上一卷最后一个录制出点之后"#,
+    ),
+    (
+        "arg.conform",
+        r#"套底：把按旧 EDL 做好的译文字幕迁移到新 EDL 上。
+按片段名 + 源时间码对应事件，字幕移动到新位置；
+片段被剪掉的字幕会丢弃，新增片段会列出以便补翻译"#,
+    ),
+    (
+        "arg.tracks",
+        r#"双语：主语言轨道和副语言轨道，逗号分隔，例如 A1,A2；
+副轨事件按录制入点对应到主轨，时间以主轨为准"#,
+    ),
+    (
+        "arg.translations",
+        r#"双语：按片段名索引的翻译表（.csv / .tsv / .json），
+CSV / TSV 第一列为片段名、第二列为译文；
+JSON 为 {"片段名": "译文"} 或 [{"clip_name": ..., "text": ...}]"#,
+    ),
+    (
+        "arg.bilingual_layout",
+        r#"双语排布：stacked（默认，每条字幕两行）或
+top-bottom（主语言在底部、副语言在顶部，仅 ASS）"#,
+    ),
+    (
+        "arg.transcribe",
+        r#"把每个音频事件（轨道以 A 开头）的源文件发给本地转写服务，
+用转写结果作为字幕文本；失败的事件保留片段名并给出警告"#,
+    ),
+    (
+        "arg.transcribe_url",
+        r#"转写接口地址（兼容 WhisperLiveKit / OpenAI 转写接口），
+默认 http://localhost:8000/v1/audio/transcriptions；隐含 --transcribe"#,
+    ),
+    (
+        "arg.media_path",
+        r#"查找源文件的目录（含子目录），可重复使用；优先使用 "* SOURCE FILE:" 注释中的路径，
+找不到时按文件名在这里查找；隐含 --transcribe"#,
+    ),
+    (
+        "arg.transcribe_cache",
+        r#"转写结果缓存目录，按文件内容的 SHA-256 缓存，
+默认为输入 EDL 所在目录下的 .edl2srt-cache；隐含 --transcribe"#,
+    ),
+    (
+        "arg.transcribe_language",
+        "识别语言，例如 zh、en；默认由服务自动判断；隐含 --transcribe",
+    ),
+    (
+        "arg.origin",
+        r#"成片文件的起点时间码，例如 01:00:00:00；输出时间都减去它，早于起点的字幕会被忽略。
+章节输出（ffmetadata / mkvchapters / youtube）和 LRC 歌词通常需要配合使用"#,
+    ),
+    (
+        "arg.text_source",
+        r#"字幕文本来源：clip-name（默认，片段名）、transcribe（同 --transcribe）
+或 locators（Avid 的 "* LOC:" 定位点，每条到下一个定位点或片段出点为止）"#,
+    ),
+    (
+        "arg.locator_color",
+        "只使用这种颜色的定位点，例如 YELLOW，可重复使用（隐含 --text-source locators）",
+    ),
+    (
+        "arg.speaker_pattern",
+        r#"从片段名提取说话人的正则，取名为 speaker 的捕获组（没有时取第一个），
+例如 "^[A-Z]_(?P<speaker>[^_]+)_" 把 A_小明_台词03 识别为 小明 / 台词03"#,
+    ),
+    (
+        "arg.speaker_reel",
+        "卷名=说话人，正则没有匹配时按卷名查找，可重复使用",
+    ),
+    (
+        "arg.speaker_style",
+        r#"说话人标注方式：prefix（默认，小明：台词）、dash（多人对话时每行加 -）、
+ass-styles（每个说话人一个 ASS 样式）或 vtt-voice（WebVTT 的 <v 小明>）；
+录制时间重叠的片段合成一条字幕，每人一行"#,
+    ),
+    (
+        "arg.render",
+        r#"额外生成烧录预览到这个目录：每条字幕一张 PNG 静帧（白字黑边、底部居中，
+版式同 ASS 输出），加一个带时间码的 index.html 索引页"#,
+    ),
+    (
+        "arg.render_font",
+        "预览使用的字体文件（TTF / OTF），--render 时必需",
+    ),
+    ("arg.render_size", "预览分辨率，默认 1920x1080"),
+    ("arg.preset", "使用配置文件中的命名预设（[preset.<名字>]）"),
+    (
+        "arg.config",
+        "指定配置文件；默认依次查找当前目录和用户配置目录下的 edl2srt.toml",
+    ),
+    (
+        "arg.edit",
+        r#"写出前打开终端界面：表格显示序号、入出点、时长、阅读速度、文本和警告，
+可修改文本（| 表示换行）、删除（d）、与下一条合并（m）、
+按帧平移入点（[ ]）、出点（{ }）或整条（< >）、撤销（u），
+按 w 用所选格式写出，按 q 放弃"#,
+    ),
+    (
+        "arg.stream",
+        r#"流式转换超大 EDL 或日志：边读边解码、边解析边写出，内存占用与文件大小无关。
+编码检测和帧率推断只看文件开头 64 KiB；srt / vtt / sbv 逐条写出，
+其它格式仍需收集全部字幕。不做质检，不能与多卷、套底、双语、转写、
+定位点、说话人、--edit、--render、--dump-json 同时使用"#,
+    ),
+    ("arg.diff_old", "旧版本的 EDL"),
+    ("arg.diff_new", "新版本的 EDL"),
+    (
+        "arg.diff_fps",
+        "帧率；省略时从两份 EDL 推断（只需名义帧率）",
+    ),
+    ("arg.diff_json", "额外导出 JSON 格式的变更报告"),
+    ("arg.diff_change_list", "额外导出只包含新增和修改事件的 EDL"),
+    ("arg.detect_input", "要诊断的文件"),
+    (
+        "arg.encoding_hint",
+        "地区提示 jp / cn / tw / kr，可重复使用；省略时四个都试",
+    ),
+    ("arg.lines", "每个候选编码预览的行数，默认 5"),
+    ("arg.shell", "bash、zsh、fish、elvish 或 powershell"),
+    // 帮助
+    (
+        "help.main",
+        r#"示例:
+  {0} 25 input.edl output.srt
+  {0} input.edl output.srt
+  {0} convert --fps 25 -i input.edl -o output.vtt
+  {0} check input.edl --preset netflix-zh
+  {0} diff v3.edl v4.edl --json changes.json --change-list changes.edl
+  {0} detect-encoding input.edl --encoding-hint jp
+  {0} completions bash > /etc/bash_completion.d/{0}
+
+说明:
+  - 省略子命令时按 convert 处理，{0} <子命令> --help 查看各子命令的选项
+  - 用法错误的退出码为 2，转换或检查失败为 1"#,
+    ),
+    (
+        "help.convert",
+        r#"配置文件:
+  edl2srt.toml 中 [default] 的值总是生效，--preset 选中的预设覆盖它，
+  命令行参数又覆盖配置文件。键名与命令行参数相同，另有 cleanup（文本清理）、
+  qc（质检限制）和 speakers（说话人规则）三个子表：
+    [preset.netflix-zh]
+    fps = 23.976
+    format = "srt"
+    text-source = "transcribe"
+    [preset.netflix-zh.cleanup]
+    replace = [["_", " "]]
+    collapse-spaces = true
+    [preset.netflix-zh.qc]
+    max-chars-per-line = 16
+    min-duration-ms = 833
+    [preset.netflix-zh.speakers]
+    pattern = "^[A-Z]_(?P<speaker>[^_]+)_"
+    reels = {{ R01 = "小明" }}
+    style = "dash"
+
+示例:
+  {0} 30 input.edl output.srt
+  {0} input.edl output.srt
+  {0} convert --fps 30 --input input.edl --output output.srt
+  {0} 30 input.edl output.srt --input-encoding shift_jis
+  {0} 25 input.edl output.srt --input-encoding utf-16le
+  {0} 29.97 project/main.edl subtitles/final_sub.srt
+  {0} 25 input.edl output.vtt
+  {0} 25 input.edl output.stl
+  {0} 29.97 input.edl output.scc
+  {0} 25 input.edl output.srt --dump-json events.json
+  {0} 24 r1.edl feature.srt --reel r2.edl --reel r3.edl
+  {0} 24 r1.edl feature.srt --reel r2.edl --reel-lengths 00:19:58:12
+  {0} 25 v4.edl v4_en.srt --conform v3.edl v3_en.srt
+  {0} 25 input.edl output.srt --tracks A1,A2
+  {0} 25 input.edl output.ass --translations en.csv --bilingual-layout top-bottom
+  {0} 25 input.edl output.srt --transcribe --media-path D:\VO --transcribe-language zh
+  {0} 25 chapters.edl chapters.ffmeta --origin 01:00:00:00
+  {0} 25 chapters.edl youtube.txt --format youtube --origin 01:00:00:00
+  {0} 25 mv.edl mv.sbv --origin 01:00:00:00
+  {0} 25 mv.edl mv.lrc --format lrc-enhanced --origin 01:00:00:00
+  {0} input.edl output.srt --preset netflix-zh
+  {0} 25 avid.edl output.srt --locator-color YELLOW
+  {0} 25 input.edl output.vtt --speaker-pattern "^[A-Z]_(?P<speaker>[^_]+)_" --speaker-style vtt-voice
+  {0} 25 input.edl output.srt --edit
+  {0} 25 huge.edl huge.srt --stream --origin 01:00:00:00
+  {0} 25 input.edl output.srt --render preview --render-font NotoSansSC-Regular.otf
+  {0} --lang en 25 input.edl output.srt
+
+说明:
+  - 自动识别依赖 chardetng，适合大多数常见文本编码
+  - 已知输入编码时，建议显式传入 --input-encoding 以获得更稳定结果
+  - 支持相对路径和绝对路径
+  - 如果输出文件已存在，程序会自动改名，而不是覆盖旧文件
+  - 请确保输出目录具有写入权限"#,
+    ),
+    (
+        "help.completions",
+        r#"示例:
+  {0} completions bash > /etc/bash_completion.d/{0}
+  {0} completions zsh > ~/.zfunc/_{0}
+  {0} completions fish > ~/.config/fish/completions/{0}.fish
+  {0} completions powershell >> $PROFILE"#,
+    ),
+];
@@ -0,0 +1,103 @@
+//! edl2srt：把 EDL（剪辑决策表）转换为字幕文件。
+//!
+//! 库按处理流程拆成几个模块：
+//! - [`reader`]：字节解码（指定编码 / BOM / 自动检测）与 EDL 文本解析
+//! - [`encoding`]：编码诊断，对比 BOM 和不同地区提示下的猜测结果
+//! - [`i18n`]：界面语言（中文 / 英文）与消息目录
+//! - [`model`]：时间码、EDL 事件、字幕条目等数据结构
+//! - [`transform`]：事件到字幕条目、时间码到毫秒的换算
+//! - [`writer`]：写出 SRT 以及输出路径处理
+//! - [`format`]：可插拔的读取器 / 写出器 trait 与格式注册表
+//! - [`dump`]：JSON 中间表示的导出与导入
+//! - [`fps`]：从 EDL 内容推断帧率，以及帧字段校验
+//! - [`reels`]：把多卷 EDL 合并成一条连续的时间线
+//! - [`conform`]：把按旧 EDL 做好的译文字幕迁移到改剪后的新 EDL
+//! - [`bilingual`]：两条轨道或翻译表配成双语字幕
+//! - [`diff`]：比较两个版本的 EDL，列出字幕的增删改
+//! - [`transcribe`]：调用本地转写服务，用音频内容作为字幕文本
+//! - [`config`]：项目配置文件 `edl2srt.toml` 与命名预设
+//! - [`qc`]：按交付规范检查行长、时长和阅读速度，列出变速片段
+//! - [`speaker`]：从片段名或卷名识别说话人，加前缀、对话破折号、ASS 样式或 VTT 声音标签
+//! - [`stream`]：超大 EDL 的流式转换，边读边解码、边解析边写出，内存占用与文件大小无关
+//! - [`render`]：把字幕画到空白画面上，输出 PNG 静帧和 HTML 索引页
+//! - [`editor`]：写出前的字幕编辑（改文本、删除、合并、按帧平移、撤销）
+//! - [`pipeline`]：命令行背后的完整流程，把以上模块串起来：参数与预设合并、读取、换算、写出、预览和检查
+//! - [`web`]：编译成 WebAssembly 后供网页调用的转换入口
+//!
+//! 所有入口都基于 `Read` / `Write`，不依赖具体文件路径：
+//!
+//! ```
+//! use edl2srt::{parse_edl_to_entries, entries_to_cues, write_srt};
+//!
+//! let edl = "001  AX  A  C  00:00:00:00 00:00:01:00 01:00:00:00 01:00:01:00\n\
+//!            * FROM CLIP NAME: hello.wav\n";
+//! let cues = entries_to_cues(&parse_edl_to_entries(edl), 25.0);
+//!
+//! let mut out = Vec::new();
+//! write_srt(&mut out, &cues).unwrap();
+//! assert_eq!(
+//!     String::from_utf8(out).unwrap(),
+//!     "1\n01:00:00,000 --> 01:00:01,000\nhello\n\n"
+//! );
+//! ```
+
+pub mod bilingual;
+pub mod config;
+pub mod conform;
+pub mod diff;
+pub mod dump;
+pub mod editor;
+pub mod encoding;
+pub mod error;
+pub mod format;
+pub mod fps;
+pub mod i18n;
+pub mod model;
+pub mod pipeline;
+pub mod qc;
+pub mod reader;
+pub mod reels;
+pub mod render;
+pub mod speaker;
+pub mod stream;
+pub mod transcribe;
+pub mod transform;
+pub mod web;
+pub mod writer;
+
+pub use bilingual::{
+    Bilingual, BilingualCue, BilingualLayout, TranslationTable, pair_tracks, translate_with_table,
+};
+pub use config::{Preset, ProjectConfig, TextSource};
+pub use conform::{ConformResult, conform};
+pub use diff::{ChangeKind, DiffReport, diff_edls};
+pub use dump::{EdlDump, parse_dump_json, write_dump_json};
+pub use editor::{CueEditor, CueRow, Edge};
+pub use encoding::{EncodingDiagnosis, EncodingHint, diagnose_encoding};
+pub use error::{Error, Result};
+pub use format::{
+    FormatRegistry, ReadOptions, SubtitleReader, SubtitleWriter, TimelineReader, WriteOptions,
+};
+pub use fps::{FpsInference, check_frames_against_fps, infer_fps};
+pub use model::{Cue, Edl, EdlEvent, Locator, MotionEffect, SubtitleEntry, Timecode, Warning};
+pub use pipeline::{
+    CheckArgs, CheckOptions, CheckReport, Conversion, ConvertArgs, ConvertOptions, StreamJob,
+    check_timeline, load_timeline, read_timeline,
+};
+pub use qc::{QcIssue, QcLimits, RetimedEvent, chars_per_second, check_cues, retimed_events};
+pub use reader::{
+    DecodeResult, EdlParser, decode_edl_bytes, locators, parse_edl, parse_edl_to_entries, read_edl,
+};
+pub use reels::{MergedReels, Reel, ReelPlacement, merge_reels};
+pub use render::{CueRenderer, Frame, RenderOptions, write_contact_sheet};
+pub use speaker::{
+    SpeakerCue, SpeakerExtractor, SpeakerLine, SpeakerRules, SpeakerStyle, rebase_speaker_cues,
+    speaker_cues,
+};
+pub use stream::{EventStream, StreamOptions, StreamReport, stream_convert};
+pub use transcribe::{TranscribeReport, Transcriber};
+pub use transform::{
+    CleanupRules, clean_cues, convert_timecode, entries_to_cues, event_text, events_to_entries,
+    locators_to_entries, offset_cues, rebase_cues, strip_audio_extension,
+};
+pub use writer::{format_srt_timestamp, resolve_output_path, write_srt};
@@ -0,0 +1,421 @@
+//! 数据模型：时间码、EDL 事件以及最终要写出的字幕条目。
+//!
+//! 整个转换流程是：
+//! EDL 文本 -> [`Edl`]（事件列表）-> [`SubtitleEntry`]（EDL 时间码 + 文本）
+//! -> [`Cue`]（毫秒时间 + 文本）-> 各种字幕格式。
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+use crate::fps::nominal_rate;
+use crate::t;
+
+/// EDL 时间码，形如 `HH:MM:SS:FF`。
+///
+/// 丢帧时间码在最后一个分隔符处使用 `;`（例如 `01:00:00;02`），
+/// 解析时会记录到 `drop_frame` 中，输出时原样还原。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Timecode {
+    pub hours: u32,
+    pub minutes: u32,
+    pub seconds: u32,
+    pub frames: u32,
+    pub drop_frame: bool,
+}
+
+impl Timecode {
+    /// 构造一个非丢帧时间码。
+    pub fn new(hours: u32, minutes: u32, seconds: u32, frames: u32) -> Self {
+        Timecode {
+            hours,
+            minutes,
+            seconds,
+            frames,
+            drop_frame: false,
+        }
+    }
+
+    /// 解析 `HH:MM:SS:FF` / `HH:MM:SS;FF` / `HH;MM;SS;FF` 形式的时间码。
+    ///
+    /// 只要有一个字段不是纯数字，或者字段数不是 4，就返回 `None`。
+    pub fn parse(text: &str) -> Option<Timecode> {
+        let parts: Vec<&str> = text.split([':', ';']).collect();
+
+        if parts.len() != 4 {
+            return None;
+        }
+
+        let mut fields = [0u32; 4];
+        for (field, part) in fields.iter_mut().zip(&parts) {
+            if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+                return None;
+            }
+            *field = part.parse().ok()?;
+        }
+
+        Some(Timecode {
+            hours: fields[0],
+            minutes: fields[1],
+            seconds: fields[2],
+            frames: fields[3],
+            drop_frame: text.contains(';'),
+        })
+    }
+
+    /// 换算为毫秒。
+    ///
+    /// 时、分、秒按墙钟时间计算，帧按 `frames / fps` 换算后四舍五入到毫秒。
+    pub fn to_millis(&self, fps: f64) -> u64 {
+        let whole_seconds =
+            self.hours as u64 * 3_600 + self.minutes as u64 * 60 + self.seconds as u64;
+        let ms_from_frames = ((self.frames as f64) / fps) * 1000.0;
+
+        whole_seconds * 1_000 + ms_from_frames.round() as u64
+    }
+
+    /// 由毫秒换算回时间码，是 [`Timecode::to_millis`] 的逆运算。
+    ///
+    /// 秒以下的部分按 `fps` 四舍五入到最近的帧，进位到下一秒时帧数归零。
+    pub fn from_millis(total_ms: u64, fps: f64, drop_frame: bool) -> Self {
+        let rate = (fps.round() as u64).max(1);
+        let mut whole_seconds = total_ms / 1_000;
+        let mut frames = ((total_ms % 1_000) as f64 * fps / 1000.0).round() as u64;
+        if frames >= rate {
+            whole_seconds += 1;
+            frames = 0;
+        }
+
+        Timecode::from_frames(whole_seconds * rate + frames, rate as u32, drop_frame)
+    }
+
+    /// 按名义帧率（例如 29.97 对应 30）换算为总帧数。
+    ///
+    /// 这里按时间码标签计数，丢帧时间码也不扣除跳过的帧号，
+    /// 与 [`Timecode::to_millis`] 的换算方式保持一致。
+    pub fn to_frames(&self, nominal_rate: u32) -> u64 {
+        let whole_seconds =
+            self.hours as u64 * 3_600 + self.minutes as u64 * 60 + self.seconds as u64;
+        whole_seconds * nominal_rate as u64 + self.frames as u64
+    }
+
+    /// 由总帧数构造时间码，是 [`Timecode::to_frames`] 的逆运算。
+    pub fn from_frames(total_frames: u64, nominal_rate: u32, drop_frame: bool) -> Self {
+        let rate = nominal_rate.max(1) as u64;
+        let whole_seconds = total_frames / rate;
+
+        Timecode {
+            hours: (whole_seconds / 3_600) as u32,
+            minutes: (whole_seconds / 60 % 60) as u32,
+            seconds: (whole_seconds % 60) as u32,
+            frames: (total_frames % rate) as u32,
+            drop_frame,
+        }
+    }
+
+    /// 平移若干帧，结果小于 0 时截断为 0。
+    pub fn offset_frames(&self, delta: i64, nominal_rate: u32) -> Self {
+        let total = (self.to_frames(nominal_rate) as i64 + delta).max(0) as u64;
+        Timecode::from_frames(total, nominal_rate, self.drop_frame)
+    }
+}
+
+impl fmt::Display for Timecode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sep = if self.drop_frame { ';' } else { ':' };
+        write!(
+            f,
+            "{:02}:{:02}:{:02}{}{:02}",
+            self.hours, self.minutes, self.seconds, sep, self.frames
+        )
+    }
+}
+
+/// JSON 中时间码以字符串形式出现，例如 "01:00:00:00"。
+impl Serialize for Timecode {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timecode {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        Timecode::parse(&text)
+            .ok_or_else(|| serde::de::Error::custom(t!("model.invalid_timecode", text)))
+    }
+}
+
+/// EDL 中的一个事件（一行以数字开头的剪辑记录）及其附带的注释行。
+///
+/// 典型的事件行：
+/// `001  AX  AA/V  C  00:00:00:00 00:00:03:00 01:00:00:00 01:00:03:00`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EdlEvent {
+    /// 事件编号，例如 "001"
+    #[serde(default)]
+    pub number: String,
+
+    /// 素材卷名，例如 "AX"
+    #[serde(default)]
+    pub reel: String,
+
+    /// 轨道，例如 "V"、"A"、"AA/V"
+    #[serde(default)]
+    pub track: String,
+
+    /// 转场类型，例如 "C"、"D"、"W001"
+    #[serde(default)]
+    pub transition: String,
+
+    /// 源入点（行内只有两个时间码时为 None）
+    #[serde(default)]
+    pub source_in: Option<Timecode>,
+
+    /// 源出点（行内只有两个时间码时为 None）
+    #[serde(default)]
+    pub source_out: Option<Timecode>,
+
+    /// 录制入点，即字幕开始时间
+    pub record_in: Timecode,
+
+    /// 录制出点，即字幕结束时间
+    pub record_out: Timecode,
+
+    /// `* FROM CLIP NAME:` 中的片段名（原样保留，不去扩展名）
+    #[serde(default)]
+    pub clip_name: Option<String>,
+
+    /// 字幕文本。
+    ///
+    /// EDL 解析时始终为 None，此时使用去掉音频扩展名的片段名；
+    /// 从 JSON 导入或后续处理中填写后，优先于片段名。
+    #[serde(default)]
+    pub text: Option<String>,
+
+    /// 其它以 `*` 开头的注释行（去掉前导 `*` 和空白）
+    #[serde(default)]
+    pub comments: Vec<String>,
+
+    /// 事件行在原文件中的行号（从 1 开始）
+    #[serde(default)]
+    pub line: usize,
+
+    /// 属于这个事件的原始行：事件行本身以及其后的注释行
+    #[serde(default)]
+    pub source_lines: Vec<String>,
+
+    /// Avid `AUD 3 4` 续行中的音频通道号，表示事件还占用 A3、A4 等轨道
+    #[serde(default)]
+    pub audio_channels: Vec<u32>,
+
+    /// `M2` 行记录的运动效果（变速、倒放、定格）
+    #[serde(default)]
+    pub motion: Option<MotionEffect>,
+}
+
+/// 运动效果，来自事件后的 `M2 <卷名> <速度> <源时间码>` 行。
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MotionEffect {
+    /// 卷名（CMX3600 中最多 8 个字符，可能与事件行不完全一致）
+    pub reel: String,
+
+    /// 播放速度（帧/秒），负数为倒放，0 为定格
+    pub speed: f64,
+
+    /// 效果开始处的源时间码
+    pub entry: Timecode,
+}
+
+impl EdlEvent {
+    /// 变速片段的播放速度（百分比），正常速度时为 None。
+    ///
+    /// 有 `M2` 行时按其中的速度和帧率计算；否则比较源时长和录制时长，
+    /// 两者不同（例如不写 `M2` 的定格、变速）时按比例计算。
+    pub fn speed_percent(&self, fps: f64) -> Option<f64> {
+        let percent = match &self.motion {
+            Some(motion) => motion.speed / nominal_rate(fps).max(1) as f64 * 100.0,
+            None => {
+                let rate = nominal_rate(fps);
+                let frames = |tc_in: Timecode, tc_out: Timecode| {
+                    tc_out.to_frames(rate) as f64 - tc_in.to_frames(rate) as f64
+                };
+                let source = frames(self.source_in?, self.source_out?);
+                let record = frames(self.record_in, self.record_out);
+                if record <= 0.0 {
+                    return None;
+                }
+                source / record * 100.0
+            }
+        };
+
+        ((percent - 100.0).abs() > 0.05).then_some(percent)
+    }
+
+    /// 事件是否在指定轨道上（不区分大小写）。
+    ///
+    /// 除了事件行的轨道字段，`AUD` 续行中的通道也算，例如 `AUD 3` 对应 `A3`。
+    pub fn on_track(&self, track: &str) -> bool {
+        if self.track.eq_ignore_ascii_case(track) {
+            return true;
+        }
+        track
+            .strip_prefix(['A', 'a'])
+            .and_then(|n| n.parse::<u32>().ok())
+            .is_some_and(|n| self.audio_channels.contains(&n))
+    }
+}
+
+/// Avid 的定位点（`* LOC:` 注释）。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Locator {
+    /// 定位点所在的录制时间码
+    pub timecode: Timecode,
+
+    /// 颜色，统一为大写，例如 "YELLOW"
+    pub color: String,
+
+    /// 轨道，例如 "V1"；旧版 Avid 不写轨道
+    pub track: Option<String>,
+
+    /// 定位点文本
+    pub text: String,
+
+    /// 所属事件在 [`Edl::events`] 中的下标
+    pub event: usize,
+}
+
+/// 解析过程中发现的问题。不会中断解析，只用于提示。
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Warning {
+    /// 出问题的行号（从 1 开始）
+    pub line: usize,
+
+    pub message: String,
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&t!("model.warning", self.line, self.message))
+    }
+}
+
+/// 解析后的整份 EDL。
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Edl {
+    /// `TITLE:` 行的内容
+    #[serde(default)]
+    pub title: Option<String>,
+
+    /// `FCM:` 行的内容，例如 "NON-DROP FRAME"
+    #[serde(default)]
+    pub fcm: Option<String>,
+
+    /// 文件中明确记录的帧率（EDL 不含此信息，JSON 导入时来自 `fps` 字段）
+    #[serde(default)]
+    pub frame_rate: Option<f64>,
+
+    /// 按出现顺序排列的事件
+    pub events: Vec<EdlEvent>,
+
+    /// 解析警告
+    #[serde(default)]
+    pub warnings: Vec<Warning>,
+}
+
+/// 一条字幕条目：EDL 时间码 + 字幕文本。
+///
+/// 时间码尚未换算，需要结合帧率才能得到真实时间，见 [`SubtitleEntry::to_cue`]。
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleEntry {
+    pub start: Timecode,
+    pub end: Timecode,
+    pub text: String,
+}
+
+impl SubtitleEntry {
+    /// 按帧率换算为以毫秒为单位的 [`Cue`]。
+    pub fn to_cue(&self, fps: f64) -> Cue {
+        Cue {
+            start_ms: self.start.to_millis(fps),
+            end_ms: self.end.to_millis(fps),
+            text: self.text.clone(),
+        }
+    }
+}
+
+/// 一条已经换算成毫秒的字幕，是所有字幕写出器的输入。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cue {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub text: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_non_drop_timecode() {
+        let tc = Timecode::parse("01:02:03:04").unwrap();
+        assert_eq!(tc, Timecode::new(1, 2, 3, 4));
+        assert_eq!(tc.to_string(), "01:02:03:04");
+    }
+
+    #[test]
+    fn parse_drop_frame_timecode() {
+        let tc = Timecode::parse("00:01:00;02").unwrap();
+        assert!(tc.drop_frame);
+        assert_eq!(tc.frames, 2);
+        assert_eq!(tc.to_string(), "00:01:00;02");
+    }
+
+    #[test]
+    fn millis_round_trip() {
+        for fps in [23.976_f64, 25.0, 29.97, 30.0] {
+            let rate = fps.round() as u32;
+            for frames in 0..rate {
+                let tc = Timecode::new(1, 2, 3, frames);
+                assert_eq!(Timecode::from_millis(tc.to_millis(fps), fps, false), tc);
+            }
+        }
+        assert_eq!(
+            Timecode::from_millis(999, 25.0, false),
+            Timecode::new(0, 0, 1, 0)
+        );
+    }
+
+    #[test]
+    fn reject_malformed_timecode() {
+        assert_eq!(Timecode::parse("00:01:00"), None);
+        assert_eq!(Timecode::parse("00:01:xx:00"), None);
+        assert_eq!(Timecode::parse("00::00:00"), None);
+        assert_eq!(Timecode::parse("-1:00:00:00"), None);
+    }
+
+    #[test]
+    fn timecode_serializes_as_string() {
+        let tc = Timecode::parse("01:00:00;02").unwrap();
+        let json = serde_json::to_string(&tc).unwrap();
+        assert_eq!(json, "\"01:00:00;02\"");
+        assert_eq!(serde_json::from_str::<Timecode>(&json).unwrap(), tc);
+        assert!(serde_json::from_str::<Timecode>("\"1:00\"").is_err());
+    }
+
+    #[test]
+    fn frame_count_round_trip() {
+        let tc = Timecode::new(1, 2, 3, 24);
+        let frames = tc.to_frames(25);
+        assert_eq!(frames, (3600 + 2 * 60 + 3) * 25 + 24);
+        assert_eq!(Timecode::from_frames(frames, 25, false), tc);
+        assert_eq!(tc.offset_frames(1, 25), Timecode::new(1, 2, 4, 0));
+        assert_eq!(tc.offset_frames(-1_000_000, 25), Timecode::new(0, 0, 0, 0));
+    }
+
+    #[test]
+    fn timecode_to_millis_rounds_frames() {
+        let tc = Timecode::new(1, 0, 0, 12);
+        assert_eq!(tc.to_millis(24.0), 3_600_500);
+        assert_eq!(Timecode::new(0, 0, 0, 1).to_millis(30.0), 33);
+    }
+}
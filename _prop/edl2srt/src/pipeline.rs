@@ -0,0 +1,1342 @@
+//! 命令行背后的完整流程：参数与预设合并，读取、换算、写出字幕，以及 `check` 的检查。
+//!
+//! 命令行只负责解析参数和打印结果，流程本身都在这里，可以直接在库中调用：
+//! - [`ConvertOptions::new`] 把 [`ConvertArgs`] 与配置文件中的预设合并，并检查选项冲突
+//! - [`Conversion::prepare`] 读取输入并换算出要写出的字幕，[`Conversion::write`] 写到任意 `Write`
+//! - [`ConvertOptions::open_stream`] 和 [`StreamJob::write`] 是流式转换的两步
+//! - [`CheckOptions`] 和 [`check_timeline`] 对应 `check` 子命令
+//!
+//! 过程中给用户看的进度和警告交给 `notify` 回调，命令行把它们打印到标准错误。
+
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use crate::bilingual::{
+    Bilingual, BilingualLayout, TableFormat, TranslationTable, pair_tracks, translate_with_table,
+};
+use crate::config::{Preset, TextSource};
+use crate::conform::conform;
+use crate::dump::EdlDump;
+use crate::error::{Error, Result};
+use crate::format::ass::{write_bilingual_ass, write_speaker_ass};
+use crate::format::{FormatRegistry, SubtitleWriter, WriteOptions};
+use crate::fps::{check_frames_against_fps, infer_fps, is_valid_fps, uses_drop_frame};
+use crate::i18n::text;
+use crate::model::{Cue, Edl, Timecode, Warning};
+use crate::qc::{QcIssue, QcLimits, RetimedEvent, check_cues, retimed_events};
+use crate::reader::{DecodeResult, decode_edl_bytes};
+use crate::reels::{Reel, ReelPlacement, merge_reels};
+use crate::render::{CueRenderer, RenderOptions, still_file_name, write_contact_sheet};
+use crate::speaker::{SpeakerCue, SpeakerRules, SpeakerStyle, rebase_speaker_cues, speaker_cues};
+use crate::stream::{EventStream, StreamOptions, StreamReport, stream_convert};
+use crate::t;
+use crate::transcribe::{DEFAULT_ENDPOINT, Endpoint, Transcriber};
+use crate::transform::{
+    CleanupRules, clean_cues, entries_to_cues, events_to_entries, locators_to_entries, offset_cues,
+    rebase_cues,
+};
+use crate::writer::format_srt_timestamp;
+
+/// 输入文件超过这个大小时提示可以用 --stream
+const LARGE_INPUT_BYTES: u64 = 256 * 1024 * 1024;
+
+/// `convert` 的参数，只做了语法层面的检查；与预设合并、互斥检查见 [`ConvertOptions::new`]。
+#[derive(Debug, Default)]
+pub struct ConvertArgs {
+    pub fps: Option<f64>,
+    pub input: PathBuf,
+    pub output: PathBuf,
+    pub input_encoding: Option<String>,
+    pub format: Option<String>,
+    pub dump_json: Option<PathBuf>,
+    pub reels: Vec<PathBuf>,
+    pub reel_starts: Option<Vec<Timecode>>,
+    pub reel_lengths: Option<Vec<Timecode>>,
+    pub conform: Option<(PathBuf, PathBuf)>,
+    pub tracks: Option<(String, String)>,
+    pub translations: Option<PathBuf>,
+    pub bilingual_layout: Option<BilingualLayout>,
+
+    /// 给出了 --transcribe 或任一 --transcribe-* / --media-path
+    pub transcribe: bool,
+    pub transcribe_url: Option<String>,
+    pub transcribe_cache: Option<PathBuf>,
+    pub transcribe_language: Option<String>,
+    pub media_paths: Vec<PathBuf>,
+    pub origin: Option<Timecode>,
+    pub text_source: Option<TextSource>,
+    pub locator_colors: Vec<String>,
+    pub speaker_pattern: Option<String>,
+    pub speaker_style: Option<SpeakerStyle>,
+    pub speaker_reels: Vec<(String, String)>,
+    pub render_dir: Option<PathBuf>,
+    pub render_font: Option<PathBuf>,
+    pub render_options: Option<RenderOptions>,
+    pub preset: Option<String>,
+    pub config: Option<PathBuf>,
+    pub edit: bool,
+    pub stream: bool,
+}
+
+/// `check` 的参数。
+#[derive(Debug, Default)]
+pub struct CheckArgs {
+    pub fps: Option<f64>,
+    pub input: PathBuf,
+    pub input_encoding: Option<String>,
+    pub format: Option<String>,
+    pub origin: Option<Timecode>,
+    pub preset: Option<String>,
+    pub config: Option<PathBuf>,
+}
+
+/// 一次转换的设置：命令行参数与预设合并后的结果。
+#[derive(Debug)]
+pub struct ConvertOptions {
+    /// 帧率，例如 24、25、29.97
+    /// None 表示未在命令行给出，需要从 EDL 内容推断
+    pub fps: Option<f64>,
+
+    /// 输入 EDL 文件路径
+    pub input_path: PathBuf,
+
+    /// 用户期望的输出路径
+    ///
+    /// 注意：
+    /// 这只是“用户输入的目标路径”，
+    /// 如果该文件已存在，程序可能会自动改名后再输出。
+    pub output_path: PathBuf,
+
+    /// 可选的输入编码。
+    /// - Some("shift_jis") 表示用户明确指定编码
+    /// - None 表示程序自动检测
+    pub input_encoding: Option<String>,
+
+    /// 可选的输出格式名，例如 "srt"、"vtt"。
+    /// None 表示按输出文件扩展名判断，判断不出时使用 SRT。
+    pub format: Option<String>,
+
+    /// 可选的 JSON 导出路径，"-" 表示输出到标准输出
+    pub dump_json: Option<PathBuf>,
+
+    /// 用 --reel 追加的其它卷，按顺序接在主输入之后
+    pub reels: Vec<PathBuf>,
+
+    /// 每卷在合并后时间线上的起点（--reel-starts）
+    pub reel_starts: Option<Vec<Timecode>>,
+
+    /// 每卷的长度（--reel-lengths）
+    pub reel_lengths: Option<Vec<Timecode>>,
+
+    /// 套底模式：(旧 EDL, 按旧 EDL 做好的字幕)
+    pub conform: Option<(PathBuf, PathBuf)>,
+
+    /// 双语模式：(主语言轨道, 副语言轨道)
+    pub tracks: Option<(String, String)>,
+
+    /// 双语模式：按片段名索引的翻译表
+    pub translations: Option<PathBuf>,
+
+    /// 双语字幕的排布方式
+    pub bilingual_layout: BilingualLayout,
+
+    /// 转写模式：用转写服务的结果作为音频片段的字幕文本
+    pub transcribe: Option<Transcriber>,
+
+    /// 成片文件的起点时间码，输出时间都减去它（--origin）
+    pub origin: Option<Timecode>,
+
+    /// 字幕文本的清理规则，来自配置文件的预设
+    pub cleanup: CleanupRules,
+
+    /// 质检限制，来自配置文件的预设
+    pub qc: Option<QcLimits>,
+
+    /// 写出前打开终端界面预览和修改字幕（--edit）
+    pub edit: bool,
+
+    /// 用 Avid 定位点作为字幕时，只取这些颜色（空表示全部）；None 表示不用定位点
+    pub locator_colors: Option<Vec<String>>,
+
+    /// 说话人识别与标注规则；None 表示不标注说话人
+    pub speakers: Option<SpeakerRules>,
+
+    /// 烧录预览：(输出目录, 字体文件, 画面设置)；None 表示不生成预览（--render）
+    pub render: Option<(PathBuf, PathBuf, RenderOptions)>,
+
+    /// 流式转换：边读边写，不把整个文件读进内存（--stream）
+    pub stream: bool,
+}
+
+impl ConvertOptions {
+    /// 把 convert 的参数与配置文件中的预设合并，并检查选项之间的冲突。
+    ///
+    /// 命令行没给出的项取预设的值；选项冲突时返回 [`Error::Invalid`]，其中是给用户看的原因。
+    pub fn new(args: ConvertArgs, preset: Preset) -> Result<ConvertOptions> {
+        let ConvertArgs {
+            fps,
+            input: input_path,
+            output: output_path,
+            input_encoding,
+            format,
+            dump_json,
+            reels,
+            reel_starts,
+            reel_lengths,
+            conform,
+            mut tracks,
+            mut translations,
+            mut bilingual_layout,
+            transcribe,
+            transcribe_url,
+            transcribe_cache,
+            transcribe_language,
+            mut media_paths,
+            mut origin,
+            mut text_source,
+            mut locator_colors,
+            speaker_pattern,
+            speaker_style,
+            speaker_reels,
+            render_dir,
+            render_font,
+            render_options,
+            preset: _,
+            config: _,
+            edit,
+            stream,
+        } = args;
+        let invalid = |message: String| Err(Error::Invalid(message));
+
+        // --transcribe 及其子选项等同于 --text-source transcribe
+        if transcribe && text_source.is_none() {
+            text_source = Some(TextSource::Transcribe);
+        }
+        // --locator-color 等同于 --text-source locators
+        if !locator_colors.is_empty() && text_source.is_none() {
+            text_source = Some(TextSource::Locators);
+        }
+
+        if tracks.is_some() && translations.is_some() {
+            return invalid(t!("cli.tracks_and_translations"));
+        }
+
+        // 用配置文件中的预设补齐命令行没给出的项
+        let fps = match fps {
+            Some(fps) => Some(fps),
+            None => preset_fps(&preset)?,
+        };
+        let input_encoding = input_encoding.or(preset.input_encoding);
+        let format = format.or(preset.format);
+        let text_source = text_source.or(preset.text_source);
+        if locator_colors.is_empty() {
+            locator_colors = preset.locator_colors;
+        }
+        let transcribe_url = transcribe_url.or(preset.transcribe_url);
+        let transcribe_cache = transcribe_cache.or(preset.transcribe_cache);
+        let transcribe_language = transcribe_language.or(preset.transcribe_language);
+        if media_paths.is_empty() {
+            media_paths = preset.media_path;
+        }
+        if origin.is_none()
+            && let Some(text) = &preset.origin
+        {
+            origin = Some(parse_origin(text)?);
+        }
+
+        // 命令行已经选了字幕来源（套底或双语）时，不再使用预设里的双语设置
+        if conform.is_none() && tracks.is_none() && translations.is_none() {
+            if let Some(text) = &preset.tracks {
+                tracks = Some(parse_tracks(text).map_err(Error::Invalid)?);
+            }
+            translations = preset.translations;
+        }
+        if bilingual_layout.is_none()
+            && let Some(text) = &preset.bilingual_layout
+        {
+            bilingual_layout = Some(parse_bilingual_layout(text).map_err(Error::Invalid)?);
+        }
+
+        if tracks.is_some() && translations.is_some() {
+            return invalid(t!("cli.tracks_and_translations_config"));
+        }
+        if conform.is_some() && (tracks.is_some() || translations.is_some()) {
+            return invalid(t!("cli.conform_and_bilingual"));
+        }
+        if bilingual_layout.is_some() && tracks.is_none() && translations.is_none() {
+            return invalid(t!("cli.layout_needs_bilingual"));
+        }
+        if text_source == Some(TextSource::Locators)
+            && (conform.is_some() || tracks.is_some() || translations.is_some())
+        {
+            return invalid(t!("cli.locators_conflict"));
+        }
+        if edit && bilingual_layout == Some(BilingualLayout::TopBottom) {
+            return invalid(t!("cli.edit_top_bottom"));
+        }
+
+        // 命令行的说话人选项覆盖预设中的对应项
+        let speakers =
+            if speaker_pattern.is_some() || speaker_style.is_some() || !speaker_reels.is_empty() {
+                let mut rules = preset.speakers.unwrap_or_default();
+                rules.pattern = speaker_pattern.or(rules.pattern);
+                rules.style = speaker_style.unwrap_or(rules.style);
+                rules.reels.extend(speaker_reels);
+                Some(rules)
+            } else {
+                preset.speakers
+            };
+        if let Some(rules) = &speakers {
+            if rules.pattern.is_none() && rules.reels.is_empty() {
+                return invalid(t!("cli.speakers_need_rules"));
+            }
+            if conform.is_some()
+                || tracks.is_some()
+                || translations.is_some()
+                || text_source == Some(TextSource::Locators)
+            {
+                return invalid(t!("cli.speakers_conflict"));
+            }
+            if edit && rules.style == SpeakerStyle::AssStyles {
+                return invalid(t!("cli.edit_ass_styles"));
+            }
+        }
+
+        // 烧录预览的字体和分辨率可以写在配置文件里
+        let render = match render_dir {
+            Some(dir) => {
+                let Some(font) = render_font.or(preset.render_font) else {
+                    return invalid(t!("cli.render_needs_font"));
+                };
+                let render_options = match (render_options, &preset.render_size) {
+                    (Some(render_options), _) => render_options,
+                    (None, Some(text)) => parse_render_size(text).map_err(Error::Invalid)?,
+                    (None, None) => RenderOptions::default(),
+                };
+                Some((dir, font, render_options))
+            }
+            None if render_font.is_some() || render_options.is_some() => {
+                return invalid(t!("cli.render_options_alone"));
+            }
+            None => None,
+        };
+
+        // 流式转换只支持逐条换算的功能，需要看到全部事件的都不行
+        if stream {
+            let conflicts = [
+                ("--reel", !reels.is_empty()),
+                ("--conform", conform.is_some()),
+                (
+                    text("cli.feature_bilingual"),
+                    tracks.is_some() || translations.is_some(),
+                ),
+                (
+                    text("cli.feature_transcribe"),
+                    text_source == Some(TextSource::Transcribe),
+                ),
+                (
+                    text("cli.feature_locators"),
+                    text_source == Some(TextSource::Locators),
+                ),
+                (text("cli.feature_speakers"), speakers.is_some()),
+                ("--edit", edit),
+                ("--render", render.is_some()),
+                ("--dump-json", dump_json.is_some()),
+            ];
+            if let Some((name, _)) = conflicts.iter().find(|(_, used)| *used) {
+                return invalid(t!("cli.stream_conflict", name));
+            }
+        }
+
+        // 转写地址默认指向本机的 WhisperLiveKit；缓存默认放在输入 EDL 旁边
+        let transcribe = if text_source == Some(TextSource::Transcribe) {
+            let endpoint = Endpoint::parse(transcribe_url.as_deref().unwrap_or(DEFAULT_ENDPOINT))?;
+            let cache_dir = transcribe_cache.unwrap_or_else(|| {
+                input_path
+                    .parent()
+                    .unwrap_or(Path::new(""))
+                    .join(".edl2srt-cache")
+            });
+
+            let mut transcriber = Transcriber::new(endpoint, cache_dir);
+            transcriber.media_paths = media_paths;
+            transcriber.language = transcribe_language;
+            Some(transcriber)
+        } else {
+            None
+        };
+
+        Ok(ConvertOptions {
+            fps,
+            input_path,
+            output_path,
+            input_encoding,
+            format,
+            dump_json,
+            reels,
+            reel_starts,
+            reel_lengths,
+            conform,
+            tracks,
+            translations,
+            bilingual_layout: bilingual_layout.unwrap_or_default(),
+            transcribe,
+            origin,
+            cleanup: preset.cleanup.unwrap_or_default(),
+            qc: preset.qc,
+            edit,
+            locator_colors: (text_source == Some(TextSource::Locators)).then_some(locator_colors),
+            speakers,
+            render,
+            stream,
+        })
+    }
+
+    /// 选择输出格式，并检查它能否承载双语和说话人的写法。
+    ///
+    /// - 用户用 --format 指定时优先
+    /// - 否则按输出文件扩展名判断，判断不出时使用 SRT
+    pub fn writer<'r>(&self, registry: &'r FormatRegistry) -> Result<&'r dyn SubtitleWriter> {
+        let writer = match &self.format {
+            Some(name) => registry.subtitle_writer(name)?,
+            None => match registry.writer_for_path(&self.output_path) {
+                Some(writer) => writer,
+                None => registry.subtitle_writer("srt")?,
+            },
+        };
+
+        if self.bilingual_layout == BilingualLayout::TopBottom && writer.name() != "ass" {
+            return Err(Error::Invalid(t!("cli.top_bottom_ass_only")));
+        }
+        if let Some(rules) = &self.speakers
+            && let Some(required) = rules.style.required_format()
+            && writer.name() != required
+        {
+            return Err(Error::Invalid(t!(
+                "cli.speaker_style_format",
+                rules.style.name(),
+                required
+            )));
+        }
+        Ok(writer)
+    }
+
+    /// 流式转换的第一步：读入文件开头，确定编码和帧率。
+    ///
+    /// 帧率没有给出时只能根据文件开头推断，推断不出时返回错误，请用户指定。
+    pub fn open_stream<R: BufRead>(
+        &self,
+        input: R,
+        writer: &dyn SubtitleWriter,
+        notify: &mut dyn FnMut(String),
+    ) -> Result<StreamJob<R>> {
+        let events = EventStream::new(input, self.input_encoding.as_deref())
+            .map_err(|err| Error::Step(t!("cli.decode_failed", err)))?;
+        match &self.input_encoding {
+            Some(user_encoding) => notify(t!("cli.encoding_given", user_encoding)),
+            None => notify(t!("cli.encoding_detected_sample", events.encoding_name())),
+        }
+
+        let fps = match self.fps {
+            Some(fps) => fps,
+            None => {
+                let fps = events.sample_fps().resolve()?;
+                notify(t!("cli.fps_inferred_sample", fps));
+                fps
+            }
+        };
+
+        if !writer.streams() {
+            notify(t!("cli.stream_buffered", writer.name()));
+        }
+        if self.qc.is_some() {
+            notify(t!("cli.stream_no_qc"));
+        }
+
+        Ok(StreamJob {
+            events,
+            options: StreamOptions {
+                fps,
+                cleanup: self.cleanup.clone(),
+                origin_ms: self.origin.map(|origin| origin.to_millis(fps)),
+            },
+            origin: self.origin,
+        })
+    }
+}
+
+/// 流式转换中已经确定了编码和帧率的输入，见 [`ConvertOptions::open_stream`]。
+pub struct StreamJob<R: BufRead> {
+    pub events: EventStream<R>,
+    pub options: StreamOptions,
+    origin: Option<Timecode>,
+}
+
+impl<R: BufRead> StreamJob<R> {
+    /// 流式转换的第二步：边读边写到 `out`，最后报告解码错误和统计。
+    pub fn write(
+        &mut self,
+        writer: &dyn SubtitleWriter,
+        out: &mut dyn Write,
+        notify: &mut dyn FnMut(String),
+    ) -> Result<StreamReport> {
+        let report = stream_convert(
+            &mut self.events,
+            writer,
+            out,
+            &self.options,
+            &mut |warning| notify(t!("cli.warning", warning)),
+        )?;
+        out.flush()?;
+
+        if self.events.had_errors() {
+            notify(t!("cli.stream_decode_errors", self.events.encoding_name()));
+        }
+        if let Some(origin) = self.origin
+            && report.before_origin > 0
+        {
+            notify(t!("cli.before_origin", report.before_origin, origin));
+        }
+        notify(t!("cli.stream_done", report.events, report.cues));
+        Ok(report)
+    }
+}
+
+/// 换算好、等待写出的字幕，以及写出时要用到的双语和说话人信息。
+#[derive(Debug)]
+pub struct Conversion {
+    /// 合并多卷、转写之后的时间线
+    pub edl: Edl,
+
+    pub fps: f64,
+
+    /// 主输入的编码名
+    pub encoding_name: String,
+
+    /// 要写出的字幕，已清理文本、按起点平移；写出前可以再修改
+    pub cues: Vec<Cue>,
+
+    /// 双语配对结果，上下分屏排布时按它写出
+    pub bilingual: Option<Bilingual>,
+
+    pub bilingual_layout: BilingualLayout,
+
+    /// 说话人字幕，按 ASS 样式标注时按它写出
+    pub speakers: Option<Vec<SpeakerCue>>,
+
+    pub speaker_style: Option<SpeakerStyle>,
+}
+
+impl Conversion {
+    /// 读取主输入和 --reel 追加的其它卷，再按 [`Conversion::from_reels`] 换算。
+    pub fn prepare(
+        registry: &FormatRegistry,
+        options: &ConvertOptions,
+        notify: &mut dyn FnMut(String),
+    ) -> Result<Conversion> {
+        let mut reels = Vec::new();
+        let mut encoding_name = String::new();
+        for path in std::iter::once(&options.input_path).chain(&options.reels) {
+            let (edl, decoded) =
+                load_timeline(registry, path, options.input_encoding.as_deref(), notify)?;
+            if encoding_name.is_empty() {
+                encoding_name = decoded.encoding_name;
+            }
+            reels.push(Reel {
+                name: path.display().to_string(),
+                edl,
+            });
+        }
+
+        Conversion::from_reels(registry, options, reels, encoding_name, notify)
+    }
+
+    /// 从已解析的各卷换算出要写出的字幕。
+    ///
+    /// 依次确定帧率、合并多卷、转写，再按套底、双语、说话人、定位点或片段名生成字幕，
+    /// 最后清理文本、按起点平移并做质检。`reels` 至少要有一卷。
+    pub fn from_reels(
+        registry: &FormatRegistry,
+        options: &ConvertOptions,
+        mut reels: Vec<Reel>,
+        encoding_name: String,
+        notify: &mut dyn FnMut(String),
+    ) -> Result<Conversion> {
+        // 确定帧率：
+        // - 命令行给出时直接使用，但检查帧字段是否超出范围
+        // - 否则根据 EDL 内容推断（多卷时综合所有卷），有歧义时返回候选
+        let fps = match options.fps {
+            Some(fps) => {
+                for reel in &reels {
+                    for warning in check_frames_against_fps(&reel.edl, fps) {
+                        notify(reel_warning(&reels, &reel.name, &warning));
+                    }
+                }
+                fps
+            }
+            None => {
+                let mut combined = reels[0].edl.clone();
+                for reel in &reels[1..] {
+                    combined.events.extend(reel.edl.events.iter().cloned());
+                }
+
+                let fps = infer_fps(&combined).resolve()?;
+                notify(t!("cli.fps_inferred", fps));
+                fps
+            }
+        };
+
+        // 只有一卷时直接使用；多卷时按指定方式合并成一条时间线
+        let mut edl = if reels.len() == 1 {
+            let edl = reels.pop().expect("至少有一卷").edl;
+            for warning in &edl.warnings {
+                notify(t!("cli.warning", warning));
+            }
+            edl
+        } else {
+            let placement = match (&options.reel_starts, &options.reel_lengths) {
+                (Some(starts), _) => ReelPlacement::Starts(starts.clone()),
+                (None, Some(lengths)) => ReelPlacement::Lengths(lengths.clone()),
+                (None, None) => ReelPlacement::Sequential,
+            };
+
+            let merged = merge_reels(&reels, &placement, fps)
+                .map_err(|err| Error::Step(t!("cli.reel_merge_failed", err)))?;
+            for (reel, start) in reels.iter().zip(&merged.reel_starts) {
+                notify(t!("cli.reel_start", reel.name, start));
+            }
+            for warning in &merged.edl.warnings {
+                notify(t!("cli.warning", warning.message));
+            }
+            merged.edl
+        };
+
+        // 转写模式下，用音频内容替换片段名作为字幕文本
+        if let Some(transcriber) = &options.transcribe {
+            notify(t!(
+                "cli.transcribing",
+                transcriber.endpoint.host,
+                transcriber.endpoint.port,
+                transcriber.endpoint.path
+            ));
+            let report = transcriber.transcribe_events(&mut edl);
+            for warning in &report.warnings {
+                notify(t!("cli.warning", warning));
+            }
+            notify(t!(
+                "cli.transcribe_done",
+                report.transcribed,
+                report.cached,
+                report.warnings.len(),
+                transcriber.cache_dir.display()
+            ));
+        }
+
+        // 双语模式下先把主、副语言配对
+        let mut bilingual = if options.tracks.is_some() || options.translations.is_some() {
+            Some(build_bilingual(options, &edl, fps, notify)?)
+        } else {
+            None
+        };
+
+        // 标注说话人时先按规则识别，清理文本后再合成字幕
+        let mut speakers = match &options.speakers {
+            Some(rules) => {
+                let extractor = rules.compile()?;
+                let mut cues = speaker_cues(&edl.events, &extractor, fps);
+                for line in cues.iter_mut().flat_map(|cue| &mut cue.lines) {
+                    line.text = options.cleanup.apply(&line.text);
+                }
+                if !cues
+                    .iter()
+                    .flat_map(|cue| &cue.lines)
+                    .any(|line| line.speaker.is_some())
+                {
+                    notify(t!("cli.no_speakers"));
+                }
+                Some(cues)
+            }
+            None => None,
+        };
+
+        // 套底模式下字幕来自已翻译的旧字幕，双语模式下每条字幕两行，
+        // 标注说话人时来自识别结果，否则来自 EDL 片段名
+        let cues = match (&options.conform, &bilingual) {
+            (Some((old_path, subtitle_path)), _) => conform_subtitles(
+                registry,
+                options,
+                old_path,
+                subtitle_path,
+                &edl,
+                fps,
+                notify,
+            )?,
+            (None, Some(bilingual)) => bilingual.stacked_cues(),
+            (None, None) => match (&options.speakers, &speakers, &options.locator_colors) {
+                (Some(rules), Some(speakers), _) => {
+                    speakers.iter().map(|cue| rules.render(cue)).collect()
+                }
+                (_, _, Some(colors)) => {
+                    let entries = locators_to_entries(&edl, colors);
+                    if entries.is_empty() {
+                        notify(t!("cli.no_locators"));
+                    }
+                    entries_to_cues(&entries, fps)
+                }
+                _ => entries_to_cues(&events_to_entries(&edl.events), fps),
+            },
+        };
+
+        // 按预设中的规则清理字幕文本（说话人字幕在合成前已清理）
+        let cues = if options.cleanup.is_empty() || speakers.is_some() {
+            cues
+        } else {
+            if let Some(bilingual) = &mut bilingual {
+                for cue in &mut bilingual.cues {
+                    cue.primary = options.cleanup.apply(&cue.primary);
+                    cue.secondary = cue.secondary.as_deref().map(|s| options.cleanup.apply(s));
+                }
+            }
+            clean_cues(&cues, &options.cleanup)
+        };
+
+        // 录制时间码通常从 01:00:00:00 之类的位置开始，按成片文件内的时间输出时减去起点
+        let cues = match options.origin {
+            Some(origin) => {
+                let origin_ms = origin.to_millis(fps);
+                if let Some(bilingual) = &mut bilingual {
+                    bilingual.rebase(origin_ms);
+                }
+                if let Some(speakers) = &mut speakers {
+                    rebase_speaker_cues(speakers, origin_ms);
+                }
+                let rebased = rebase_cues(&cues, origin_ms);
+                if rebased.len() < cues.len() {
+                    notify(t!("cli.before_origin", cues.len() - rebased.len(), origin));
+                }
+                rebased
+            }
+            None => cues,
+        };
+
+        // 按预设中的交付规范做质检，只提示不修改；变速片段一并列出，便于人工核对
+        if let Some(limits) = &options.qc {
+            let issues = check_cues(&cues, limits);
+            for issue in &issues {
+                notify(format!("QC: {}", issue));
+            }
+            let retimed = retimed_events(&edl, fps);
+            for event in &retimed {
+                notify(format!("QC: {}", event));
+            }
+            if issues.is_empty() && retimed.is_empty() {
+                notify(t!("cli.qc_passed"));
+            }
+        }
+
+        Ok(Conversion {
+            edl,
+            fps,
+            encoding_name,
+            cues,
+            bilingual,
+            bilingual_layout: options.bilingual_layout,
+            speakers,
+            speaker_style: options.speakers.as_ref().map(|rules| rules.style),
+        })
+    }
+
+    /// 写出时使用的帧率、标题和丢帧格式。
+    pub fn write_options(&self) -> WriteOptions {
+        WriteOptions {
+            fps: self.fps,
+            title: self.edl.title.clone(),
+            drop_frame: uses_drop_frame(&self.edl),
+        }
+    }
+
+    /// 时间线的 JSON 中间表示，便于排查和二次处理。
+    pub fn dump(&self) -> EdlDump {
+        EdlDump::new(&self.edl, Some(self.fps), Some(&self.encoding_name))
+    }
+
+    /// 目标格式表示不了的内容（字符集、行长等）；上下分屏的双语 ASS 不经过 `writer`，没有提示。
+    pub fn warnings(&self, writer: &dyn SubtitleWriter) -> Vec<String> {
+        if self.bilingual.is_some() && self.bilingual_layout == BilingualLayout::TopBottom {
+            return Vec::new();
+        }
+        writer.warnings(&self.cues, &self.write_options())
+    }
+
+    /// 写出字幕：上下分屏的双语和按样式标注的说话人写成专门的 ASS，其余交给 `writer`。
+    pub fn write(&self, writer: &dyn SubtitleWriter, out: &mut dyn Write) -> io::Result<()> {
+        let options = self.write_options();
+        match (&self.bilingual, self.bilingual_layout, &self.speakers) {
+            (Some(bilingual), BilingualLayout::TopBottom, _) => {
+                write_bilingual_ass(out, &bilingual.cues, &options)
+            }
+            (_, _, Some(speakers)) if self.speaker_style == Some(SpeakerStyle::AssStyles) => {
+                write_speaker_ass(out, speakers, &options)
+            }
+            _ => writer.write(out, &self.cues, &options),
+        }
+    }
+
+    /// 把字幕逐条画成 PNG 静帧写到 `dir`，再写出索引页，返回索引页路径。
+    pub fn render_preview(
+        &self,
+        dir: &Path,
+        font_path: &Path,
+        render_options: RenderOptions,
+        notify: &mut dyn FnMut(String),
+    ) -> Result<PathBuf> {
+        let font_data = fs::read(font_path)?;
+        let renderer = CueRenderer::new(&font_data, render_options)?;
+        fs::create_dir_all(dir)?;
+
+        let mut missing: Vec<char> = Vec::new();
+        for (i, cue) in self.cues.iter().enumerate() {
+            for c in renderer.missing_chars(&cue.text) {
+                if !missing.contains(&c) {
+                    missing.push(c);
+                }
+            }
+            let file = File::create(dir.join(still_file_name(i + 1)))?;
+            renderer.render(&cue.text).write_png(BufWriter::new(file))?;
+        }
+        if !missing.is_empty() {
+            let shown: String = missing.iter().take(20).collect();
+            notify(t!(
+                "cli.font_missing_glyphs",
+                font_path.display(),
+                missing.len(),
+                shown
+            ));
+        }
+
+        let index_path = dir.join("index.html");
+        let file = File::create(&index_path)?;
+        write_contact_sheet(
+            BufWriter::new(file),
+            &self.cues,
+            self.fps,
+            self.edl.title.as_deref(),
+        )?;
+        Ok(index_path)
+    }
+}
+
+/// 读取、解码并解析一个时间线文件，文件很大时提示可以用 --stream。
+pub fn load_timeline(
+    registry: &FormatRegistry,
+    path: &Path,
+    input_encoding: Option<&str>,
+    notify: &mut dyn FnMut(String),
+) -> Result<(Edl, DecodeResult)> {
+    if let Ok(metadata) = fs::metadata(path)
+        && metadata.len() >= LARGE_INPUT_BYTES
+    {
+        notify(t!("cli.large_input", metadata.len() / 1024 / 1024));
+    }
+
+    let file = File::open(path)
+        .map_err(|e| Error::Step(t!("cli.read_input_failed", path.display(), e)))?;
+    read_timeline(registry, file, Some(path), input_encoding, notify)
+}
+
+/// 从 `input` 读取、解码并解析一个时间线。
+///
+/// 输入格式先看 `path` 的扩展名，再看内容，都认不出时按 EDL 处理。
+pub fn read_timeline<R: Read>(
+    registry: &FormatRegistry,
+    mut input: R,
+    path: Option<&Path>,
+    input_encoding: Option<&str>,
+    notify: &mut dyn FnMut(String),
+) -> Result<(Edl, DecodeResult)> {
+    // 先读取原始字节，而不是直接按 UTF-8 文本去读
+    // 这是为了兼容多种可能的 EDL 编码。
+    let mut bytes = Vec::new();
+    input.read_to_end(&mut bytes)?;
+
+    // 解码输入：
+    // - 已知编码时优先按指定编码解码
+    // - 否则先看 BOM，再自动检测
+    let decoded = decode_edl_bytes(&bytes, input_encoding)
+        .map_err(|err| Error::Step(t!("cli.decode_failed", err)))?;
+
+    // 把本次解码策略告诉用户，便于排查问题
+    notify(match (input_encoding, decoded.had_errors) {
+        (Some(user_encoding), true) => t!("cli.encoding_given_errors", user_encoding),
+        (Some(user_encoding), false) => t!("cli.encoding_given", user_encoding),
+        (None, true) => t!("cli.encoding_detected_errors", decoded.encoding_name),
+        (None, false) => t!("cli.encoding_detected", decoded.encoding_name),
+    });
+
+    let reader = match registry.detect_timeline_reader(path, &decoded.content) {
+        Some(reader) => reader,
+        None => registry.timeline_reader("edl")?,
+    };
+    let edl = reader
+        .parse(&decoded.content)
+        .map_err(|err| Error::Step(t!("cli.parse_failed", err)))?;
+    Ok((edl, decoded))
+}
+
+/// 套底：读取旧 EDL 和已翻译字幕，把字幕迁移到新 EDL 上，并报告迁移结果。
+///
+/// 一条都对不上时多半是帧率或起点不对，返回错误而不是得到空字幕。
+fn conform_subtitles(
+    registry: &FormatRegistry,
+    options: &ConvertOptions,
+    old_path: &Path,
+    subtitle_path: &Path,
+    new_edl: &Edl,
+    fps: f64,
+    notify: &mut dyn FnMut(String),
+) -> Result<Vec<Cue>> {
+    let (old_edl, _) = load_timeline(
+        registry,
+        old_path,
+        options.input_encoding.as_deref(),
+        notify,
+    )?;
+
+    let bytes = fs::read(subtitle_path)
+        .map_err(|e| Error::Step(t!("cli.read_subtitle_failed", subtitle_path.display(), e)))?;
+    let reader = registry
+        .detect_subtitle_reader(Some(subtitle_path), &bytes)
+        .ok_or_else(|| Error::Step(t!("cli.unknown_subtitle_format", subtitle_path.display())))?;
+    let translated = reader
+        .read(&bytes)
+        .map_err(|err| Error::Step(t!("cli.parse_subtitle_failed", err)))?;
+
+    // 用 --origin 导出的译文字幕是成片时间，先加回起点才能与旧 EDL 的录制时间对应
+    let translated = match options.origin {
+        Some(origin) => offset_cues(&translated, origin.to_millis(fps)),
+        None => translated,
+    };
+
+    let result = conform(&old_edl, new_edl, &translated, fps);
+
+    notify(t!(
+        "cli.conform_done",
+        result.cues.len(),
+        result.moved,
+        result.dropped.len(),
+        result.unmatched.len(),
+        result.needs_translation.len()
+    ));
+    for dropped in &result.dropped {
+        notify(t!(
+            "cli.conform_cut",
+            format_srt_timestamp(dropped.cue.start_ms),
+            dropped.cue.text.replace('\n', " / ")
+        ));
+    }
+    for cue in &result.unmatched {
+        notify(t!(
+            "cli.conform_unmatched",
+            format_srt_timestamp(cue.start_ms),
+            cue.text.replace('\n', " / ")
+        ));
+    }
+    for event in &result.needs_translation {
+        notify(t!(
+            "cli.conform_needs_translation",
+            event.number,
+            event.record_in,
+            event.clip_name.as_deref().unwrap_or("")
+        ));
+    }
+
+    if !translated.is_empty() && result.unmatched.len() == translated.len() {
+        return Err(Error::Step(t!("cli.conform_nothing_matched")));
+    }
+    Ok(result.cues)
+}
+
+/// 双语：按两条轨道或翻译表为字幕配上副语言，并报告未翻译、对不上的条目。
+fn build_bilingual(
+    options: &ConvertOptions,
+    edl: &Edl,
+    fps: f64,
+    notify: &mut dyn FnMut(String),
+) -> Result<Bilingual> {
+    let bilingual = match (&options.tracks, &options.translations) {
+        (Some((primary, secondary)), _) => pair_tracks(edl, primary, secondary, fps)?,
+        (None, Some(path)) => {
+            let bytes = fs::read(path)
+                .map_err(|e| Error::Step(t!("cli.read_translations_failed", path.display(), e)))?;
+
+            // 翻译表的编码和 EDL 未必相同，单独检测
+            let content = decode_edl_bytes(&bytes, None)
+                .map_err(|err| Error::Step(t!("cli.decode_translations_failed", err)))?
+                .content;
+
+            let format =
+                TableFormat::from_path(path).unwrap_or_else(|| TableFormat::sniff(&content));
+            let table = TranslationTable::parse(&content, format)
+                .map_err(|err| Error::Step(t!("cli.parse_translations_failed", err)))?;
+            translate_with_table(edl, &table, fps)
+        }
+        (None, None) => unreachable!("调用方已确认有双语设置"),
+    };
+
+    for warning in &bilingual.warnings {
+        notify(t!("cli.warning", warning));
+    }
+    for name in &bilingual.unused_translations {
+        notify(t!("cli.unused_translation", name));
+    }
+
+    let untranslated = bilingual
+        .cues
+        .iter()
+        .filter(|c| c.secondary.is_none())
+        .count();
+    notify(t!("cli.bilingual_done", bilingual.cues.len(), untranslated));
+
+    Ok(bilingual)
+}
+
+/// 警告文本；多卷时在前面加上卷名。
+fn reel_warning(reels: &[Reel], reel_name: &str, warning: &Warning) -> String {
+    if reels.len() > 1 {
+        t!("cli.reel_warning", reel_name, warning)
+    } else {
+        t!("cli.warning", warning)
+    }
+}
+
+/// `check` 的设置：命令行参数与预设合并后的结果。
+#[derive(Debug, Clone, Default)]
+pub struct CheckOptions {
+    /// None 表示根据 EDL 内容推断
+    pub fps: Option<f64>,
+    pub input_encoding: Option<String>,
+
+    /// 给出时检查这个格式表示不了的内容
+    pub format: Option<String>,
+    pub origin: Option<Timecode>,
+    pub cleanup: Option<CleanupRules>,
+    pub qc: QcLimits,
+}
+
+impl CheckOptions {
+    /// 命令行没给出的项取预设的值。
+    pub fn new(args: &CheckArgs, preset: Preset) -> Result<CheckOptions> {
+        let origin = match (args.origin, &preset.origin) {
+            (Some(origin), _) => Some(origin),
+            (None, Some(text)) => Some(parse_origin(text)?),
+            (None, None) => None,
+        };
+
+        let fps = match args.fps {
+            Some(fps) => Some(fps),
+            None => preset_fps(&preset)?,
+        };
+
+        Ok(CheckOptions {
+            fps,
+            input_encoding: args.input_encoding.clone().or(preset.input_encoding),
+            format: args.format.clone().or(preset.format),
+            origin,
+            cleanup: preset.cleanup,
+            qc: preset.qc.unwrap_or_default(),
+        })
+    }
+}
+
+/// `check` 的结果，各类问题分开列出。
+#[derive(Debug)]
+pub struct CheckReport {
+    /// 事件数
+    pub events: usize,
+
+    /// 换算出的字幕条数
+    pub cues: usize,
+
+    /// 解析警告和帧字段问题
+    pub warnings: Vec<Warning>,
+
+    /// 目标格式表示不了的内容
+    pub format_warnings: Vec<String>,
+
+    pub issues: Vec<QcIssue>,
+    pub retimed: Vec<RetimedEvent>,
+}
+
+impl CheckReport {
+    /// 问题总数，为 0 时检查通过。
+    pub fn problems(&self) -> usize {
+        self.warnings.len() + self.format_warnings.len() + self.issues.len() + self.retimed.len()
+    }
+}
+
+/// 检查一条时间线：换算字幕，列出解析警告、帧字段问题、目标格式表示不了的内容、
+/// 质检问题和变速片段，不写出文件。
+pub fn check_timeline(
+    registry: &FormatRegistry,
+    edl: &Edl,
+    options: &CheckOptions,
+    notify: &mut dyn FnMut(String),
+) -> Result<CheckReport> {
+    let mut warnings = edl.warnings.clone();
+    let fps = match options.fps {
+        Some(fps) => {
+            warnings.extend(check_frames_against_fps(edl, fps));
+            fps
+        }
+        None => {
+            let fps = infer_fps(edl).resolve()?;
+            notify(t!("cli.fps_inferred", fps));
+            fps
+        }
+    };
+
+    let cues = entries_to_cues(&events_to_entries(&edl.events), fps);
+    let cues = match &options.cleanup {
+        Some(rules) => clean_cues(&cues, rules),
+        None => cues,
+    };
+    let cues = match options.origin {
+        Some(origin) => rebase_cues(&cues, origin.to_millis(fps)),
+        None => cues,
+    };
+
+    let format_warnings = match &options.format {
+        Some(name) => {
+            let options = WriteOptions {
+                fps,
+                title: edl.title.clone(),
+                drop_frame: uses_drop_frame(edl),
+            };
+            registry.subtitle_writer(name)?.warnings(&cues, &options)
+        }
+        None => Vec::new(),
+    };
+
+    Ok(CheckReport {
+        events: edl.events.len(),
+        cues: cues.len(),
+        warnings,
+        format_warnings,
+        issues: check_cues(&cues, &options.qc),
+        retimed: retimed_events(edl, fps),
+    })
+}
+
+/// 配置文件中的帧率，不是大于 0 的有限数字时报错。
+fn preset_fps(preset: &Preset) -> Result<Option<f64>> {
+    match preset.fps {
+        Some(fps) if !is_valid_fps(fps) => Err(Error::Invalid(t!("cli.config_fps_not_positive"))),
+        other => Ok(other),
+    }
+}
+
+/// 解析配置文件中的起点时间码。
+fn parse_origin(text: &str) -> Result<Timecode> {
+    Timecode::parse(text).ok_or_else(|| Error::Invalid(t!("cli.config_bad_origin", text)))
+}
+
+/// 解析 "主轨,副轨"，例如 "A1,A2"。
+pub fn parse_tracks(value: &str) -> std::result::Result<(String, String), String> {
+    let Some((primary, secondary)) = value.split_once(',') else {
+        return Err(t!("cli.bad_tracks", value));
+    };
+    Ok((primary.trim().to_string(), secondary.trim().to_string()))
+}
+
+/// 解析烧录预览的分辨率，例如 "1920x1080"。
+pub fn parse_render_size(value: &str) -> std::result::Result<RenderOptions, String> {
+    RenderOptions::parse_size(value).ok_or_else(|| t!("cli.bad_render_size", value))
+}
+
+/// 解析双语排布：`stacked` 或 `top-bottom`。
+pub fn parse_bilingual_layout(value: &str) -> std::result::Result<BilingualLayout, String> {
+    BilingualLayout::parse(value).ok_or_else(|| t!("cli.bad_layout", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    const EDL: &str = "\
+TITLE: Pipeline
+001 AX A C 00:00:10:00 00:00:12:00 01:00:00:00 01:00:02:00
+* FROM CLIP NAME: 你好.wav
+002 AX A C 00:00:20:00 00:00:23:00 01:00:02:00 01:00:05:00
+* FROM CLIP NAME: 再见.wav
+";
+
+    fn args(output: &str) -> ConvertArgs {
+        ConvertArgs {
+            input: PathBuf::from("in.edl"),
+            output: PathBuf::from(output),
+            ..ConvertArgs::default()
+        }
+    }
+
+    fn read(registry: &FormatRegistry, messages: &mut Vec<String>) -> Edl {
+        let (edl, _) = read_timeline(registry, EDL.as_bytes(), None, None, &mut |line| {
+            messages.push(line)
+        })
+        .unwrap();
+        edl
+    }
+
+    #[test]
+    fn options_fall_back_to_the_preset() {
+        let preset = Preset {
+            fps: Some(25.0),
+            format: Some("vtt".to_string()),
+            origin: Some("01:00:00:00".to_string()),
+            ..Preset::default()
+        };
+        let mut given = args("out.srt");
+        given.fps = Some(24.0);
+
+        let options = ConvertOptions::new(given, preset).unwrap();
+        assert_eq!(options.fps, Some(24.0));
+        assert_eq!(options.format.as_deref(), Some("vtt"));
+        assert_eq!(options.origin, Timecode::parse("01:00:00:00"));
+    }
+
+    #[test]
+    fn preset_fps_must_be_finite_and_positive() {
+        for fps in [0.0, -25.0, f64::NAN, f64::INFINITY] {
+            let preset = Preset {
+                fps: Some(fps),
+                ..Preset::default()
+            };
+            assert!(matches!(
+                ConvertOptions::new(args("out.srt"), preset.clone()),
+                Err(Error::Invalid(_))
+            ));
+            assert!(matches!(
+                CheckOptions::new(&CheckArgs::default(), preset),
+                Err(Error::Invalid(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn conflicting_options_are_invalid() {
+        let mut given = args("out.srt");
+        given.stream = true;
+        given.edit = true;
+        assert!(matches!(
+            ConvertOptions::new(given, Preset::default()),
+            Err(Error::Invalid(_))
+        ));
+
+        let mut given = args("out.srt");
+        given.render_font = Some(PathBuf::from("font.ttf"));
+        assert!(matches!(
+            ConvertOptions::new(given, Preset::default()),
+            Err(Error::Invalid(_))
+        ));
+
+        // 上下分屏只有 ASS 能写
+        let mut given = args("out.srt");
+        given.tracks = Some(("A1".to_string(), "A2".to_string()));
+        given.bilingual_layout = Some(BilingualLayout::TopBottom);
+        let options = ConvertOptions::new(given, Preset::default()).unwrap();
+        assert!(matches!(
+            options.writer(&FormatRegistry::builtin()),
+            Err(Error::Invalid(_))
+        ));
+    }
+
+    #[test]
+    fn converts_from_a_reader_to_a_writer() {
+        let registry = FormatRegistry::builtin();
+        let mut messages = Vec::new();
+        let edl = read(&registry, &mut messages);
+        assert_eq!(messages.len(), 1);
+
+        let mut given = args("out.srt");
+        given.fps = Some(24.0);
+        given.origin = Timecode::parse("01:00:00:00");
+        let options = ConvertOptions::new(given, Preset::default()).unwrap();
+        let reels = vec![Reel {
+            name: "in.edl".to_string(),
+            edl,
+        }];
+        let conversion = Conversion::from_reels(
+            &registry,
+            &options,
+            reels,
+            "UTF-8".to_string(),
+            &mut |line| messages.push(line),
+        )
+        .unwrap();
+        assert_eq!(conversion.cues.len(), 2);
+
+        let writer = options.writer(&registry).unwrap();
+        let mut out = Vec::new();
+        conversion.write(writer, &mut out).unwrap();
+        let srt = String::from_utf8(out).unwrap();
+        assert!(srt.starts_with("1\n00:00:00,000 --> 00:00:02,000\n你好\n"));
+        assert!(srt.contains("00:00:02,000 --> 00:00:05,000\n再见\n"));
+    }
+
+    #[test]
+    fn unreadable_input_names_the_step() {
+        let registry = FormatRegistry::builtin();
+        let err = read_timeline(
+            &registry,
+            &b"\xff\xfe\x00"[..],
+            None,
+            Some("no-such-encoding"),
+            &mut |_| {},
+        )
+        .unwrap_err();
+        assert!(matches!(err, Error::Step(_)));
+    }
+
+    #[test]
+    fn check_reports_problems_without_writing() {
+        let registry = FormatRegistry::builtin();
+        let edl = read(&registry, &mut Vec::new());
+
+        let options = CheckOptions {
+            fps: Some(24.0),
+            ..CheckOptions::default()
+        };
+        let report = check_timeline(&registry, &edl, &options, &mut |_| {}).unwrap();
+        assert_eq!((report.events, report.cues), (2, 2));
+        assert_eq!(report.problems(), 0);
+
+        // 帧字段超出帧率范围的事件算作问题
+        let mut edl = edl;
+        edl.events[0].record_out = Timecode::parse("01:00:02:30").unwrap();
+        let report = check_timeline(&registry, &edl, &options, &mut |_| {}).unwrap();
+        assert!(report.problems() > 0);
+    }
+
+    #[test]
+    fn streams_from_a_reader_to_a_writer() {
+        let registry = FormatRegistry::builtin();
+        let mut given = args("out.srt");
+        given.fps = Some(24.0);
+        given.stream = true;
+        let options = ConvertOptions::new(given, Preset::default()).unwrap();
+        let writer = options.writer(&registry).unwrap();
+
+        let mut messages = Vec::new();
+        let mut job = options
+            .open_stream(Cursor::new(EDL), writer, &mut |line| messages.push(line))
+            .unwrap();
+        let mut out = Vec::new();
+        let report = job
+            .write(writer, &mut out, &mut |line| messages.push(line))
+            .unwrap();
+        assert_eq!((report.events, report.cues), (2, 2));
+        assert!(String::from_utf8(out).unwrap().contains("再见"));
+        assert!(messages.last().unwrap().contains('2'));
+    }
+}
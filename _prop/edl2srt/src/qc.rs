@@ -0,0 +1,229 @@
+//! 字幕质检：按交付规范检查行长、行数、时长和阅读速度，并列出变速片段。
+//!
+//! 限制通常写在配置文件的预设里（`[preset.x.qc]`），没写的项不检查。
+//! 检查只给出提示，不修改字幕。
+
+use std::fmt;
+
+use serde::Deserialize;
+
+use crate::model::{Cue, Edl};
+use crate::t;
+
+/// 质检限制。
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct QcLimits {
+    /// 每行最多字符数
+    pub max_chars_per_line: Option<usize>,
+
+    /// 每条最多行数
+    pub max_lines: Option<usize>,
+
+    /// 最短显示时长（毫秒）
+    pub min_duration_ms: Option<u64>,
+
+    /// 最长显示时长（毫秒）
+    pub max_duration_ms: Option<u64>,
+
+    /// 每秒最多字符数（不计空白）
+    pub max_chars_per_second: Option<f64>,
+}
+
+/// 一条质检问题。
+#[derive(Debug, Clone, PartialEq)]
+pub struct QcIssue {
+    /// 字幕序号（从 1 开始，与 SRT 编号一致）
+    pub index: usize,
+
+    pub message: String,
+}
+
+impl fmt::Display for QcIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&t!("qc.issue", self.index, self.message))
+    }
+}
+
+/// 变速、倒放或定格的事件。
+///
+/// 字幕时间取录制时间码，不受变速影响；但片段名对应的台词可能被加快、放慢或截断，
+/// 交付前通常需要人工核对。
+#[derive(Debug, Clone, PartialEq)]
+pub struct RetimedEvent {
+    /// 事件行的行号
+    pub line: usize,
+
+    /// 事件编号
+    pub number: String,
+
+    /// 播放速度（百分比），见 [`EdlEvent::speed_percent`](crate::model::EdlEvent::speed_percent)
+    pub speed_percent: f64,
+
+    /// 速度是否来自 `M2` 行（否则由源时长和录制时长推算）
+    pub from_m2: bool,
+}
+
+impl fmt::Display for RetimedEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&t!("qc.retimed_event", self.line, self.number))?;
+        if self.speed_percent == 0.0 {
+            f.write_str(&t!("qc.freeze"))?;
+        } else if self.speed_percent < 0.0 {
+            f.write_str(&t!("qc.reverse", format!("{:.1}", -self.speed_percent)))?;
+        } else {
+            f.write_str(&t!("qc.speed", format!("{:.1}", self.speed_percent)))?;
+        }
+        if !self.from_m2 {
+            f.write_str(&t!("qc.no_m2"))?;
+        }
+        Ok(())
+    }
+}
+
+/// 列出所有变速、倒放或定格的事件。
+pub fn retimed_events(edl: &Edl, fps: f64) -> Vec<RetimedEvent> {
+    edl.events
+        .iter()
+        .filter_map(|event| {
+            Some(RetimedEvent {
+                line: event.line,
+                number: event.number.clone(),
+                speed_percent: event.speed_percent(fps)?,
+                from_m2: event.motion.is_some(),
+            })
+        })
+        .collect()
+}
+
+/// 阅读速度：每秒字符数（不计空白），时长为 0 时为 None。
+pub fn chars_per_second(cue: &Cue) -> Option<f64> {
+    let duration_ms = cue.end_ms.saturating_sub(cue.start_ms);
+    if duration_ms == 0 {
+        return None;
+    }
+    let chars = cue.text.chars().filter(|c| !c.is_whitespace()).count();
+    Some(chars as f64 * 1000.0 / duration_ms as f64)
+}
+
+/// 按限制逐条检查字幕。
+pub fn check_cues(cues: &[Cue], limits: &QcLimits) -> Vec<QcIssue> {
+    let mut issues = Vec::new();
+
+    for (i, cue) in cues.iter().enumerate() {
+        let mut report = |message: String| {
+            issues.push(QcIssue {
+                index: i + 1,
+                message,
+            })
+        };
+
+        let lines: Vec<&str> = cue.text.lines().collect();
+        if let Some(max) = limits.max_chars_per_line
+            && let Some(longest) = lines.iter().map(|l| l.chars().count()).max()
+            && longest > max
+        {
+            report(t!("qc.line_too_long", longest, max));
+        }
+        if let Some(max) = limits.max_lines
+            && lines.len() > max
+        {
+            report(t!("qc.too_many_lines", lines.len(), max));
+        }
+
+        let duration_ms = cue.end_ms.saturating_sub(cue.start_ms);
+        if let Some(min) = limits.min_duration_ms
+            && duration_ms < min
+        {
+            report(t!("qc.too_short", duration_ms, min));
+        }
+        if let Some(max) = limits.max_duration_ms
+            && duration_ms > max
+        {
+            report(t!("qc.too_long", duration_ms, max));
+        }
+
+        if let Some(max) = limits.max_chars_per_second
+            && let Some(cps) = chars_per_second(cue)
+            && cps > max
+        {
+            report(t!("qc.too_fast", format!("{:.1}", cps), max));
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cue(start_ms: u64, end_ms: u64, text: &str) -> Cue {
+        Cue {
+            start_ms,
+            end_ms,
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn reports_each_limit() {
+        let limits = QcLimits {
+            max_chars_per_line: Some(8),
+            max_lines: Some(2),
+            min_duration_ms: Some(833),
+            max_duration_ms: Some(7_000),
+            max_chars_per_second: Some(9.0),
+        };
+        let cues = [
+            cue(0, 2_000, "没问题"),
+            cue(2_000, 2_500, "一二三四五六七八九十"),
+            cue(3_000, 11_000, "一\n二\n三"),
+        ];
+
+        let issues: Vec<String> = check_cues(&cues, &limits)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                "第 2 条字幕: 单行 10 个字符，超过上限 8",
+                "第 2 条字幕: 显示 500 毫秒，短于下限 833",
+                "第 2 条字幕: 阅读速度 20.0 字/秒，超过上限 9",
+                "第 3 条字幕: 共 3 行，超过上限 2",
+                "第 3 条字幕: 显示 8000 毫秒，长于上限 7000",
+            ]
+        );
+    }
+
+    #[test]
+    fn lists_retimed_events() {
+        let edl = crate::reader::parse_edl(
+            "001 AX V C 00:00:00:00 00:00:02:00 01:00:00:00 01:00:01:00\n\
+             M2   AX       050.0                00:00:00:00\n\
+             002 BX V C 00:00:05:00 00:00:05:01 01:00:01:00 01:00:03:00\n\
+             003 CX V C 00:00:05:00 00:00:06:00 01:00:03:00 01:00:04:00\n\
+             004 DX V C 00:00:05:00 00:00:06:00 01:00:04:00 01:00:05:00\n\
+             M2   DX       -025.0               00:00:06:00\n",
+        );
+
+        let events: Vec<String> = retimed_events(&edl, 25.0)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+        assert_eq!(
+            events,
+            [
+                "第 1 行事件 001: 变速 200.0%",
+                "第 3 行事件 002: 变速 2.0%（源时长与录制时长不同，没有 M2 行）",
+                "第 5 行事件 004: 倒放 100.0%",
+            ]
+        );
+    }
+
+    #[test]
+    fn no_limits_no_issues() {
+        assert!(check_cues(&[cue(0, 1, "很长很长很长")], &QcLimits::default()).is_empty());
+    }
+}
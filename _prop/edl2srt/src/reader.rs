@@ -0,0 +1,590 @@
+//! 读取 EDL：字节解码与文本解析。
+
+use chardetng::EncodingDetector;
+use encoding_rs::Encoding;
+use std::io::Read;
+
+use crate::error::{Error, Result};
+use crate::model::{Edl, EdlEvent, Locator, MotionEffect, SubtitleEntry, Timecode, Warning};
+use crate::t;
+use crate::transform::events_to_entries;
+
+/// 解码后的结果。
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodeResult {
+    /// 解码后的文本内容
+    pub content: String,
+
+    /// 本次使用的编码名称，方便打印提示信息
+    pub encoding_name: String,
+
+    /// 解码时是否遇到非法字节
+    pub had_errors: bool,
+}
+
+/// 从任意 `Read` 读取全部字节并解码。
+///
+/// 解码优先级与 [`decode_edl_bytes`] 相同。
+pub fn read_edl<R: Read>(mut input: R, preferred_encoding: Option<&str>) -> Result<DecodeResult> {
+    let mut bytes = Vec::new();
+    input.read_to_end(&mut bytes)?;
+    decode_edl_bytes(&bytes, preferred_encoding)
+}
+
+/// 解码 EDL 原始字节。
+///
+/// 优先级：
+/// 1. 用户手动指定编码
+/// 2. 文件自身 BOM
+/// 3. 自动检测
+pub fn decode_edl_bytes(bytes: &[u8], preferred_encoding: Option<&str>) -> Result<DecodeResult> {
+    if let Some(label) = preferred_encoding {
+        let encoding = Encoding::for_label(label.as_bytes())
+            .ok_or_else(|| Error::UnsupportedEncoding(label.to_string()))?;
+
+        let (content, had_errors) = decode_with_encoding(encoding, bytes);
+
+        return Ok(DecodeResult {
+            content,
+            encoding_name: encoding.name().to_string(),
+            had_errors,
+        });
+    }
+
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes) {
+        let (content, had_errors) = decode_with_encoding(encoding, &bytes[bom_len..]);
+
+        return Ok(DecodeResult {
+            content,
+            encoding_name: format!("{} (BOM)", encoding.name()),
+            had_errors,
+        });
+    }
+
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, true);
+    let guessed = detector.guess(None, true);
+
+    let (content, had_errors) = decode_with_encoding(guessed, bytes);
+
+    Ok(DecodeResult {
+        content,
+        encoding_name: guessed.name().to_string(),
+        had_errors,
+    })
+}
+
+/// 用指定编码把字节解码为 Rust String。
+pub fn decode_with_encoding(encoding: &'static Encoding, bytes: &[u8]) -> (String, bool) {
+    let (cow, _, had_errors) = encoding.decode(bytes);
+    (cow.into_owned(), had_errors)
+}
+
+/// 把 EDL 文本解析成事件列表。
+///
+/// 解析规则：
+/// - `TITLE:` / `FCM:` 行：记录到头部信息
+/// - 数字开头、且末尾至少有两个合法时间码的行：作为事件行
+/// - `* FROM CLIP NAME:` 行：作为最近一个事件的片段名
+/// - `AUD 3 4` 行（Avid 的音频续行）：记录到最近一个事件的 [`EdlEvent::audio_channels`]
+/// - `M2` 行（运动效果）：记录到最近一个事件的 [`EdlEvent::motion`]
+/// - 其它 `*` 开头的行：作为最近一个事件的注释；其中 `* LOC:` 定位点行
+///   格式不对时给出警告，定位点本身由 [`locators`] 从注释中取出
+///
+/// 无法归类的行不会中断解析，而是记录到 [`Edl::warnings`]。
+/// 逐行解析见 [`EdlParser`]。
+pub fn parse_edl(content: &str) -> Edl {
+    let mut parser = EdlParser::new();
+    let mut events = Vec::new();
+
+    for line in content.lines() {
+        events.extend(parser.push_line(line));
+    }
+    events.extend(parser.finish());
+
+    Edl {
+        title: parser.title,
+        fcm: parser.fcm,
+        frame_rate: None,
+        events,
+        warnings: parser.warnings,
+    }
+}
+
+/// 逐行解析 EDL，规则与 [`parse_edl`] 相同。
+///
+/// 事件的续行（片段名、注释、M2、AUD）跟在事件行后面，
+/// 所以一个事件要等到下一个事件行或文本结束才算完整：
+/// [`EdlParser::push_line`] 在这时交出上一个事件，[`EdlParser::finish`] 交出最后一个。
+/// 解析器只保留当前事件，内存占用与文件大小无关（警告除外，可随时用
+/// [`EdlParser::take_warnings`] 取走）。
+#[derive(Debug, Default)]
+pub struct EdlParser {
+    title: Option<String>,
+    fcm: Option<String>,
+    warnings: Vec<Warning>,
+    current: Option<EdlEvent>,
+    line_number: usize,
+}
+
+impl EdlParser {
+    pub fn new() -> Self {
+        EdlParser::default()
+    }
+
+    /// `TITLE:` 行的内容
+    pub fn title(&self) -> Option<&str> {
+        self.title.as_deref()
+    }
+
+    /// `FCM:` 行的内容
+    pub fn fcm(&self) -> Option<&str> {
+        self.fcm.as_deref()
+    }
+
+    /// 已读的行数
+    pub fn line_number(&self) -> usize {
+        self.line_number
+    }
+
+    /// 是否已经读到事件行
+    pub fn has_events(&self) -> bool {
+        self.current.is_some()
+    }
+
+    /// 取走目前为止的解析警告。
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        std::mem::take(&mut self.warnings)
+    }
+
+    /// 解析下一行（不含换行符）。这一行开始新事件时返回上一个事件。
+    pub fn push_line(&mut self, line: &str) -> Option<EdlEvent> {
+        self.line_number += 1;
+        let line_number = self.line_number;
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            return None;
+        }
+
+        if let Some(title) = trimmed.strip_prefix("TITLE:") {
+            self.title = Some(title.trim().to_string());
+        } else if let Some(fcm) = trimmed.strip_prefix("FCM:") {
+            self.fcm = Some(fcm.trim().to_string());
+        } else if trimmed
+            .chars()
+            .next()
+            .map(|c| c.is_ascii_digit())
+            .unwrap_or(false)
+        {
+            match parse_event_line(trimmed, line_number) {
+                Some(event) => {
+                    if event.record_out < event.record_in {
+                        self.warn(line_number, t!("reader.record_out_before_in"));
+                    }
+                    return self.current.replace(event);
+                }
+                None => self.warn(line_number, t!("reader.bad_event_line")),
+            }
+        } else if let Some(fields) = trimmed
+            .strip_prefix("M2")
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+        {
+            let Some(event) = self.current.as_mut() else {
+                self.warn(line_number, t!("reader.m2_without_event"));
+                return None;
+            };
+
+            match parse_motion(fields) {
+                Some(_) if event.motion.is_some() => {
+                    self.warn(line_number, t!("reader.duplicate_m2"))
+                }
+                Some(motion) => {
+                    event.source_lines.push(trimmed.to_string());
+                    event.motion = Some(motion);
+                }
+                None => self.warn(line_number, t!("reader.bad_m2")),
+            }
+        } else if let Some(channels) = trimmed
+            .strip_prefix("AUD")
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+        {
+            let Some(event) = self.current.as_mut() else {
+                self.warn(line_number, t!("reader.aud_without_event"));
+                return None;
+            };
+
+            match channels
+                .split_whitespace()
+                .map(str::parse::<u32>)
+                .collect::<std::result::Result<Vec<_>, _>>()
+            {
+                Ok(channels) if !channels.is_empty() => {
+                    event.source_lines.push(trimmed.to_string());
+                    event.audio_channels.extend(channels);
+                }
+                _ => self.warn(line_number, t!("reader.bad_aud")),
+            }
+        } else if let Some(note) = trimmed.strip_prefix('*') {
+            let Some(event) = self.current.as_mut() else {
+                self.warn(line_number, t!("reader.note_without_event"));
+                return None;
+            };
+
+            event.source_lines.push(trimmed.to_string());
+
+            let note = note.trim();
+            if let Some(name) = note.strip_prefix("FROM CLIP NAME:") {
+                if event.clip_name.is_none() {
+                    event.clip_name = Some(name.trim().to_string());
+                } else {
+                    self.warn(line_number, t!("reader.duplicate_clip_name"));
+                }
+            } else {
+                event.comments.push(note.to_string());
+                if note.starts_with("LOC:") && parse_locator(note).is_none() {
+                    self.warn(line_number, t!("reader.bad_locator"));
+                }
+            }
+        }
+
+        None
+    }
+
+    /// 文本结束，交出最后一个事件。
+    pub fn finish(&mut self) -> Option<EdlEvent> {
+        self.current.take()
+    }
+
+    fn warn(&mut self, line: usize, message: String) {
+        self.warnings.push(Warning { line, message });
+    }
+}
+
+/// 解析单个事件行。
+///
+/// 末尾连续的时间码里：最后两个是录制入/出点，再往前两个是源入/出点。
+/// 时间码之前的字段依次是：编号、卷名、轨道、转场，转场后面可能还有时长。
+/// Avid File_129 的卷名可能很长并带空格，所以编号之后的字段从右往左取，
+/// 剩下的部分都算作卷名。
+fn parse_event_line(line: &str, line_number: usize) -> Option<EdlEvent> {
+    let parts: Vec<&str> = line.split_whitespace().collect();
+
+    let timecodes: Vec<Timecode> = parts
+        .iter()
+        .rev()
+        .map_while(|part| Timecode::parse(part))
+        .take(4)
+        .collect();
+
+    if timecodes.len() < 2 {
+        return None;
+    }
+
+    let (source_in, source_out) = if timecodes.len() == 4 {
+        (Some(timecodes[3]), Some(timecodes[2]))
+    } else {
+        (None, None)
+    };
+
+    let head = &parts[..parts.len() - timecodes.len()];
+    let (reel, track, transition) = split_event_fields(head.get(1..).unwrap_or_default());
+
+    Some(EdlEvent {
+        number: head.first().map(|s| s.to_string()).unwrap_or_default(),
+        reel,
+        track,
+        transition,
+        source_in,
+        source_out,
+        record_in: timecodes[1],
+        record_out: timecodes[0],
+        clip_name: None,
+        text: None,
+        comments: Vec::new(),
+        line: line_number,
+        source_lines: vec![line.to_string()],
+        audio_channels: Vec::new(),
+        motion: None,
+    })
+}
+
+/// 解析 `M2` 之后的字段：卷名、速度（帧/秒）、源时间码。
+///
+/// 卷名可能带空格，所以速度和时间码从右往左取。
+fn parse_motion(fields: &str) -> Option<MotionEffect> {
+    let mut parts: Vec<&str> = fields.split_whitespace().collect();
+    let entry = Timecode::parse(parts.pop()?)?;
+    let speed: f64 = parts.pop()?.parse().ok()?;
+    if parts.is_empty() || !speed.is_finite() {
+        return None;
+    }
+
+    Some(MotionEffect {
+        reel: parts.join(" "),
+        speed,
+        entry,
+    })
+}
+
+/// 把编号和时间码之间的字段拆成卷名、轨道、转场。
+fn split_event_fields(fields: &[&str]) -> (String, String, String) {
+    let mut fields = fields.to_vec();
+
+    // 字段不全时按位置对应
+    if fields.len() < 3 {
+        let field = |i: usize| fields.get(i).map(|s| s.to_string()).unwrap_or_default();
+        return (field(0), field(1), field(2));
+    }
+
+    // 叠化、划像后面的时长，例如 "D 025"
+    if fields.len() >= 4
+        && fields
+            .last()
+            .is_some_and(|f| f.chars().all(|c| c.is_ascii_digit()))
+    {
+        fields.pop();
+    }
+
+    // 键控转场写成两个字段，例如 "K B"
+    let mut transition = fields.pop().unwrap_or_default().to_string();
+    if fields.len() >= 3 && matches!(transition.as_str(), "B" | "O") && fields.last() == Some(&"K")
+    {
+        fields.pop();
+        transition = format!("K {}", transition);
+    }
+
+    let track = fields.pop().unwrap_or_default().to_string();
+    (fields.join(" "), track, transition)
+}
+
+/// 解析 Avid 定位点注释，参数为去掉前导 `*` 后的内容。
+///
+/// 格式为 `LOC: <时间码> <颜色> [轨道] <文本>`，例如
+/// `LOC: 01:00:05:12 YELLOW V1 你好`。轨道字段只在较新的 Avid 中出现。
+pub fn parse_locator(note: &str) -> Option<Locator> {
+    let rest = note.trim().strip_prefix("LOC:")?;
+    let mut parts = rest.split_whitespace();
+    let timecode = Timecode::parse(parts.next()?)?;
+    let color = parts.next()?.to_ascii_uppercase();
+    if !color.chars().all(|c| c.is_ascii_alphabetic()) {
+        return None;
+    }
+
+    let mut rest: Vec<&str> = parts.collect();
+    let track = match rest.first() {
+        Some(field) if is_locator_track(field) => Some(rest.remove(0).to_string()),
+        _ => None,
+    };
+
+    Some(Locator {
+        timecode,
+        color,
+        track,
+        text: rest.join(" "),
+        event: 0,
+    })
+}
+
+/// 定位点的轨道字段：V1、A2、TC1 之类
+fn is_locator_track(field: &str) -> bool {
+    let digits = field
+        .strip_prefix("TC")
+        .or_else(|| field.strip_prefix('V'))
+        .or_else(|| field.strip_prefix('A'));
+    digits.is_some_and(|d| !d.is_empty() && d.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// 取出所有事件注释中的定位点，按出现顺序排列。
+pub fn locators(edl: &Edl) -> Vec<Locator> {
+    edl.events
+        .iter()
+        .enumerate()
+        .flat_map(|(index, event)| {
+            event
+                .comments
+                .iter()
+                .filter_map(|comment| parse_locator(comment))
+                .map(move |locator| Locator {
+                    event: index,
+                    ..locator
+                })
+        })
+        .collect()
+}
+
+/// 把 EDL 文本解析成字幕条目列表。
+///
+/// 每个带有 `* FROM CLIP NAME:` 的事件生成一条字幕，
+/// 开始 / 结束时间取录制入点 / 出点，文本为去掉音频扩展名后的片段名。
+pub fn parse_edl_to_entries(content: &str) -> Vec<SubtitleEntry> {
+    events_to_entries(&parse_edl(content).events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "TITLE: demo
+FCM: NON-DROP FRAME
+
+001  AX       AA/V  C        00:00:00:00 00:00:02:00 01:00:00:00 01:00:02:00
+* FROM CLIP NAME: hello.wav
+* SOURCE FILE: /media/hello.wav
+
+002  AX       A     D    012 00:00:05:00 00:00:06:12 01:00:02:00 01:00:03:12
+* FROM CLIP NAME: world
+";
+
+    #[test]
+    fn parse_header_and_events() {
+        let edl = parse_edl(SAMPLE);
+        assert_eq!(edl.title.as_deref(), Some("demo"));
+        assert_eq!(edl.fcm.as_deref(), Some("NON-DROP FRAME"));
+        assert_eq!(edl.events.len(), 2);
+
+        let first = &edl.events[0];
+        assert_eq!(first.number, "001");
+        assert_eq!(first.reel, "AX");
+        assert_eq!(first.track, "AA/V");
+        assert_eq!(first.transition, "C");
+        assert_eq!(first.source_in, Timecode::parse("00:00:00:00"));
+        assert_eq!(first.record_out, Timecode::new(1, 0, 2, 0));
+        assert_eq!(first.clip_name.as_deref(), Some("hello.wav"));
+        assert_eq!(first.comments, vec!["SOURCE FILE: /media/hello.wav"]);
+        assert_eq!(first.line, 4);
+        assert_eq!(first.source_lines.len(), 3);
+        assert!(first.source_lines[0].starts_with("001  AX"));
+
+        let second = &edl.events[1];
+        assert_eq!(second.transition, "D");
+        assert_eq!(second.record_in, Timecode::new(1, 0, 2, 0));
+    }
+
+    #[test]
+    fn entries_use_record_times_and_strip_extension() {
+        let entries = parse_edl_to_entries(SAMPLE);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].text, "hello");
+        assert_eq!(entries[0].start, Timecode::new(1, 0, 0, 0));
+        assert_eq!(entries[1].end, Timecode::new(1, 0, 3, 12));
+    }
+
+    #[test]
+    fn event_without_clip_name_is_skipped() {
+        let entries = parse_edl_to_entries(
+            "001 AX V C 00:00:00:00 00:00:01:00 00:00:00:00 00:00:01:00\n\
+             002 AX V C 00:00:01:00 00:00:02:00 00:00:01:00 00:00:02:00\n\
+             * FROM CLIP NAME: second\n",
+        );
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].text, "second");
+        assert_eq!(entries[0].start, Timecode::new(0, 0, 1, 0));
+    }
+
+    #[test]
+    fn unrecognised_lines_become_warnings() {
+        let edl = parse_edl(
+            "* orphan comment\n\
+             001 AX V C\n\
+             002 AX V C 00:00:00:00 00:00:01:00 00:00:02:00 00:00:01:00\n",
+        );
+        assert_eq!(edl.events.len(), 1);
+        let lines: Vec<usize> = edl.warnings.iter().map(|w| w.line).collect();
+        assert_eq!(lines, vec![1, 2, 3]);
+    }
+
+    const AVID: &str = "TITLE: AVID SEQ
+FCM: NON-DROP FRAME
+
+001  A004C012_220101_R1AB.MXF V1 AUDIO  NONE  C        00:00:10:00 00:00:15:00 01:00:00:00 01:00:05:00
+AUD  3    4
+* FROM CLIP NAME:  INTERVIEW 01
+* LOC: 01:00:01:00 YELLOW  第一句
+* LOC: 01:00:02:12 RED     V1    第二句
+* LOC: 01:00:03:00 BAD
+002  B001        V     K    B    000 00:00:00:00 00:00:01:00 01:00:05:00 01:00:06:00
+* LOC: 1:00:05 YELLOW bad
+";
+
+    #[test]
+    fn parse_avid_file_129() {
+        let edl = parse_edl(AVID);
+        assert_eq!(edl.events.len(), 2);
+
+        let first = &edl.events[0];
+        assert_eq!(first.reel, "A004C012_220101_R1AB.MXF V1 AUDIO");
+        assert_eq!(first.track, "NONE");
+        assert_eq!(first.transition, "C");
+        assert_eq!(first.audio_channels, [3, 4]);
+        assert!(first.on_track("a4") && !first.on_track("A2"));
+        assert_eq!(first.source_lines[1], "AUD  3    4");
+
+        let second = &edl.events[1];
+        assert_eq!(
+            (
+                second.reel.as_str(),
+                second.track.as_str(),
+                second.transition.as_str()
+            ),
+            ("B001", "V", "K B")
+        );
+
+        // 缺文本的 LOC 仍是合法定位点，时间码不对的给出警告
+        let lines: Vec<usize> = edl.warnings.iter().map(|w| w.line).collect();
+        assert_eq!(lines, [11]);
+
+        let locators = locators(&edl);
+        assert_eq!(locators.len(), 3);
+        assert_eq!(locators[0].color, "YELLOW");
+        assert_eq!(locators[0].track, None);
+        assert_eq!(locators[0].text, "第一句");
+        assert_eq!(locators[1].timecode, Timecode::new(1, 0, 2, 12));
+        assert_eq!(locators[1].track.as_deref(), Some("V1"));
+        assert_eq!(locators[2].text, "");
+    }
+
+    #[test]
+    fn m2_lines_attach_to_event() {
+        let edl = parse_edl(
+            "M2   AX       050.0                00:00:00:00\n\
+             001  AX       V     C        00:00:10:00 00:00:10:01 01:00:00:00 01:00:02:00\n\
+             M2   AX       000.0                00:00:10:00\n\
+             * FROM CLIP NAME: freeze.mov\n\
+             M2   AX       abc                  00:00:10:00\n",
+        );
+
+        let event = &edl.events[0];
+        let motion = event.motion.as_ref().unwrap();
+        assert_eq!((motion.reel.as_str(), motion.speed), ("AX", 0.0));
+        assert_eq!(motion.entry, Timecode::new(0, 0, 10, 0));
+        assert_eq!(event.clip_name.as_deref(), Some("freeze.mov"));
+        assert_eq!(event.speed_percent(25.0), Some(0.0));
+
+        let warnings: Vec<usize> = edl.warnings.iter().map(|w| w.line).collect();
+        assert_eq!(warnings, [1, 5]);
+    }
+
+    #[test]
+    fn decode_prefers_user_encoding() {
+        let (bytes, _, _) = encoding_rs::SHIFT_JIS.encode("こんにちは");
+        let result = decode_edl_bytes(&bytes, Some("shift_jis")).unwrap();
+        assert_eq!(result.content, "こんにちは");
+        assert_eq!(result.encoding_name, "Shift_JIS");
+        assert!(!result.had_errors);
+    }
+
+    #[test]
+    fn decode_uses_bom() {
+        let bytes = [0xFF, 0xFE, b'A', 0x00, b'B', 0x00];
+        let result = decode_edl_bytes(&bytes, None).unwrap();
+        assert_eq!(result.content, "AB");
+        assert_eq!(result.encoding_name, "UTF-16LE (BOM)");
+    }
+
+    #[test]
+    fn decode_rejects_unknown_label() {
+        let err = decode_edl_bytes(b"abc", Some("no-such-encoding")).unwrap_err();
+        assert!(matches!(err, Error::UnsupportedEncoding(_)));
+    }
+}
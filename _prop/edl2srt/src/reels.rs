@@ -0,0 +1,303 @@
+//! 多卷（reel）EDL 合并。
+//!
+//! 长片通常按卷交付，每卷一份 EDL，而且每卷的录制时间码往往各自从
+//! 01:00:00:00、02:00:00:00 之类的位置开始。合并时把每卷平移到同一条
+//! 时间线上，再按顺序拼接事件，得到一份可以直接交给写出器的 [`Edl`]。
+//!
+//! 每卷的“原点”取该卷所有事件中最早的录制入点，“长度”取最晚的录制出点减去原点。
+
+use crate::error::{Error, Result};
+use crate::fps::nominal_rate;
+use crate::model::{Edl, EdlEvent, Timecode, Warning};
+use crate::t;
+use crate::transform::event_text;
+
+/// 参与合并的一卷。
+#[derive(Debug, Clone)]
+pub struct Reel {
+    /// 卷名，通常是文件名，用于提示信息
+    pub name: String,
+
+    pub edl: Edl,
+}
+
+/// 每卷在合并后时间线上的位置。
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReelPlacement {
+    /// 首尾相接：第一卷保持原位，之后每卷紧接在上一卷的最后一个录制出点之后
+    Sequential,
+
+    /// 明确给出每卷的起点，数量必须与卷数一致
+    Starts(Vec<Timecode>),
+
+    /// 给出除最后一卷外每卷的长度，第一卷保持原位
+    Lengths(Vec<Timecode>),
+}
+
+/// 合并结果。
+///
+/// 各卷的解析警告（带卷名前缀）和跨卷重叠提示都放在 `edl.warnings` 中。
+#[derive(Debug, Clone)]
+pub struct MergedReels {
+    /// 合并后的时间线，事件已重新编号
+    pub edl: Edl,
+
+    /// 每卷在合并后时间线上的起点
+    pub reel_starts: Vec<Timecode>,
+}
+
+/// 按给定方式合并多卷 EDL。
+pub fn merge_reels(reels: &[Reel], placement: &ReelPlacement, fps: f64) -> Result<MergedReels> {
+    let rate = nominal_rate(fps);
+
+    let origins: Vec<u64> = reels
+        .iter()
+        .map(|reel| reel_origin(&reel.edl).map_or(0, |tc| tc.to_frames(rate)))
+        .collect();
+
+    let starts = reel_starts(reels, placement, &origins, rate)?;
+
+    let mut merged = Edl {
+        title: reels.iter().find_map(|r| r.edl.title.clone()),
+        fcm: reels.first().and_then(|r| r.edl.fcm.clone()),
+        frame_rate: Some(fps),
+        ..Edl::default()
+    };
+    let mut warnings = Vec::new();
+
+    // 上一卷中最晚结束的字幕：(结束帧, 卷名)
+    let mut previous_end: Option<(u64, &str)> = None;
+
+    for ((reel, &origin), &start) in reels.iter().zip(&origins).zip(&starts) {
+        let delta = start as i64 - origin as i64;
+
+        for warning in &reel.edl.warnings {
+            warnings.push(Warning {
+                line: warning.line,
+                message: format!("{}: {}", reel.name, warning.message),
+            });
+        }
+
+        let mut reel_end: Option<u64> = None;
+
+        for event in &reel.edl.events {
+            let shifted = shift_event(event, delta, rate);
+
+            if event_text(&shifted).is_some() {
+                let begin = shifted.record_in.to_frames(rate);
+                let end = shifted.record_out.to_frames(rate);
+
+                if let Some((prev_end, prev_name)) = previous_end
+                    && begin < prev_end
+                {
+                    warnings.push(Warning {
+                        line: event.line,
+                        message: t!(
+                            "reels.overlap",
+                            reel.name,
+                            shifted.record_in,
+                            prev_name,
+                            Timecode::from_frames(prev_end, rate, shifted.record_in.drop_frame)
+                        ),
+                    });
+                }
+
+                reel_end = reel_end.max(Some(end));
+            }
+
+            merged.events.push(shifted);
+        }
+
+        if let Some(end) = reel_end {
+            previous_end = Some((end, &reel.name));
+        }
+    }
+
+    for (index, event) in merged.events.iter_mut().enumerate() {
+        event.number = format!("{:03}", index + 1);
+    }
+
+    merged.warnings = warnings;
+
+    let drop_frame = merged
+        .events
+        .first()
+        .is_some_and(|e| e.record_in.drop_frame);
+
+    Ok(MergedReels {
+        edl: merged,
+        reel_starts: starts
+            .iter()
+            .map(|&frames| Timecode::from_frames(frames, rate, drop_frame))
+            .collect(),
+    })
+}
+
+/// 卷的原点：所有事件中最早的录制入点。
+pub fn reel_origin(edl: &Edl) -> Option<Timecode> {
+    edl.events.iter().map(|e| e.record_in).min()
+}
+
+/// 卷的长度（帧数）：最晚的录制出点减去原点。
+pub fn reel_length_frames(edl: &Edl, rate: u32) -> u64 {
+    let Some(origin) = reel_origin(edl) else {
+        return 0;
+    };
+
+    edl.events
+        .iter()
+        .map(|e| e.record_out.to_frames(rate))
+        .max()
+        .unwrap_or(0)
+        .saturating_sub(origin.to_frames(rate))
+}
+
+/// 计算每卷在合并后时间线上的起点（帧数）。
+fn reel_starts(
+    reels: &[Reel],
+    placement: &ReelPlacement,
+    origins: &[u64],
+    rate: u32,
+) -> Result<Vec<u64>> {
+    match placement {
+        ReelPlacement::Starts(starts) => {
+            if starts.len() != reels.len() {
+                return Err(Error::Invalid(t!(
+                    "reels.starts_count",
+                    starts.len(),
+                    reels.len()
+                )));
+            }
+            Ok(starts.iter().map(|tc| tc.to_frames(rate)).collect())
+        }
+        ReelPlacement::Sequential | ReelPlacement::Lengths(_) => {
+            if let ReelPlacement::Lengths(lengths) = placement
+                && lengths.len() + 1 != reels.len()
+            {
+                return Err(Error::Invalid(t!(
+                    "reels.lengths_count",
+                    lengths.len(),
+                    reels.len() - 1
+                )));
+            }
+
+            let mut starts = Vec::with_capacity(reels.len());
+            let mut cursor = origins.first().copied().unwrap_or(0);
+
+            for (index, reel) in reels.iter().enumerate() {
+                starts.push(cursor);
+                cursor += match placement {
+                    ReelPlacement::Lengths(lengths) if index < lengths.len() => {
+                        lengths[index].to_frames(rate)
+                    }
+                    _ => reel_length_frames(&reel.edl, rate),
+                };
+            }
+
+            Ok(starts)
+        }
+    }
+}
+
+fn shift_event(event: &EdlEvent, delta: i64, rate: u32) -> EdlEvent {
+    let mut shifted = event.clone();
+    shifted.record_in = event.record_in.offset_frames(delta, rate);
+    shifted.record_out = event.record_out.offset_frames(delta, rate);
+    shifted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::parse_edl;
+    use crate::transform::events_to_entries;
+
+    fn reel(name: &str, text: &str) -> Reel {
+        Reel {
+            name: name.to_string(),
+            edl: parse_edl(text),
+        }
+    }
+
+    fn reels() -> Vec<Reel> {
+        vec![
+            reel(
+                "r1.edl",
+                "001 AX A C 00:00:00:00 00:00:02:00 01:00:00:00 01:00:02:00\n\
+                 * FROM CLIP NAME: a\n\
+                 002 AX A C 00:00:00:00 00:00:03:00 01:00:07:00 01:00:10:00\n\
+                 * FROM CLIP NAME: b\n",
+            ),
+            reel(
+                "r2.edl",
+                "001 AX A C 00:00:00:00 00:00:01:00 02:00:00:00 02:00:01:00\n\
+                 * FROM CLIP NAME: c\n",
+            ),
+        ]
+    }
+
+    #[test]
+    fn sequential_places_reels_end_to_end() {
+        let merged = merge_reels(&reels(), &ReelPlacement::Sequential, 25.0).unwrap();
+
+        assert_eq!(
+            merged.reel_starts,
+            vec![Timecode::new(1, 0, 0, 0), Timecode::new(1, 0, 10, 0)]
+        );
+
+        let entries = events_to_entries(&merged.edl.events);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[2].start, Timecode::new(1, 0, 10, 0));
+        assert_eq!(entries[2].end, Timecode::new(1, 0, 11, 0));
+
+        let numbers: Vec<&str> = merged
+            .edl
+            .events
+            .iter()
+            .map(|e| e.number.as_str())
+            .collect();
+        assert_eq!(numbers, ["001", "002", "003"]);
+        assert!(merged.edl.warnings.is_empty());
+    }
+
+    #[test]
+    fn explicit_starts() {
+        let placement =
+            ReelPlacement::Starts(vec![Timecode::new(0, 0, 0, 0), Timecode::new(0, 0, 20, 0)]);
+        let merged = merge_reels(&reels(), &placement, 25.0).unwrap();
+        let entries = events_to_entries(&merged.edl.events);
+
+        assert_eq!(entries[0].start, Timecode::new(0, 0, 0, 0));
+        assert_eq!(entries[2].start, Timecode::new(0, 0, 20, 0));
+    }
+
+    #[test]
+    fn short_length_list_flags_overlap() {
+        // 第一卷只给 5 秒，但第二条字幕到 10 秒才结束
+        let placement = ReelPlacement::Lengths(vec![Timecode::new(0, 0, 5, 0)]);
+        let merged = merge_reels(&reels(), &placement, 25.0).unwrap();
+
+        assert_eq!(merged.reel_starts[1], Timecode::new(1, 0, 5, 0));
+        assert_eq!(merged.edl.warnings.len(), 1);
+        assert!(merged.edl.warnings[0].message.contains("r2.edl"));
+        assert!(merged.edl.warnings[0].message.contains("跨卷重叠"));
+    }
+
+    #[test]
+    fn wrong_offset_count_is_an_error() {
+        let placement = ReelPlacement::Starts(vec![Timecode::new(0, 0, 0, 0)]);
+        assert!(matches!(
+            merge_reels(&reels(), &placement, 25.0),
+            Err(Error::Invalid(_))
+        ));
+
+        // 两卷只需要一个长度，多给或不给都报错
+        for count in [0, 2] {
+            let placement = ReelPlacement::Lengths(vec![Timecode::new(0, 0, 20, 0); count]);
+            match merge_reels(&reels(), &placement, 25.0) {
+                Err(Error::Invalid(message)) => assert!(message.contains("应为 1 个")),
+                other => panic!("{} 个长度应报错: {:?}", count, other),
+            }
+        }
+    }
+}
@@ -0,0 +1,525 @@
+//! 烧录预览：把每条字幕按指定字体画到空白画面上，输出 PNG 静帧和 HTML 索引页。
+//!
+//! 审片时不用打开剪辑软件就能看到字幕在画面中的大小、换行和位置。
+//! 版式与 ASS 输出的 `Default` 样式一致：白字黑边，底部居中，
+//! 字号、边距和描边都按 1080 高度的值等比缩放。
+//! 字体光栅化用纯 Rust 的 fontdue，不依赖系统字体库，可以在无界面的服务器上运行。
+
+use std::io::{self, Write};
+
+use fontdue::{Font, FontSettings};
+
+use crate::error::{Error, Result};
+use crate::i18n;
+use crate::model::{Cue, Timecode};
+use crate::t;
+
+/// 基准高度，下面几个尺寸都按这个高度给出
+const BASE_HEIGHT: f32 = 1080.0;
+
+/// 字号，与 ASS 样式的 Fontsize 相同
+const BASE_FONT_SIZE: f32 = 54.0;
+
+/// 底边距，与 ASS 样式的 MarginV 相同
+const BASE_MARGIN_V: f32 = 40.0;
+
+/// 左右边距，与 ASS 样式的 MarginL / MarginR 相同
+const BASE_MARGIN_H: f32 = 60.0;
+
+/// 描边宽度，与 ASS 样式的 Outline 相同
+const BASE_OUTLINE: f32 = 2.0;
+
+/// 画面背景色
+const BACKGROUND: [u8; 3] = [0x20, 0x20, 0x20];
+
+/// 预览画面的设置。
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderOptions {
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions {
+            width: 1920,
+            height: 1080,
+        }
+    }
+}
+
+impl RenderOptions {
+    /// 解析 `1920x1080` 形式的分辨率。
+    pub fn parse_size(text: &str) -> Option<RenderOptions> {
+        let (width, height) = text.trim().split_once(['x', 'X', '*'])?;
+        let width: u32 = width.trim().parse().ok()?;
+        let height: u32 = height.trim().parse().ok()?;
+        if width == 0 || height == 0 || width > 8192 || height > 8192 {
+            return None;
+        }
+        Some(RenderOptions { width, height })
+    }
+
+    fn scale(&self) -> f32 {
+        self.height as f32 / BASE_HEIGHT
+    }
+}
+
+/// 一帧 RGBA 图像。
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    pub width: u32,
+    pub height: u32,
+
+    /// 逐行排列的 RGBA 像素
+    pub pixels: Vec<u8>,
+}
+
+impl Frame {
+    fn blank(width: u32, height: u32) -> Frame {
+        let [r, g, b] = BACKGROUND;
+        Frame {
+            width,
+            height,
+            pixels: [r, g, b, 0xFF].repeat(width as usize * height as usize),
+        }
+    }
+
+    /// 按覆盖率把颜色混合到背景上
+    fn blend(&mut self, mask: &Mask, color: [u8; 3]) {
+        for (i, &coverage) in mask.values.iter().enumerate() {
+            if coverage <= 0.0 {
+                continue;
+            }
+            let (x, y) = (mask.x + i % mask.width, mask.y + i / mask.width);
+            let offset = (y * self.width as usize + x) * 4;
+            for (channel, value) in color.iter().enumerate() {
+                let old = self.pixels[offset + channel] as f32;
+                self.pixels[offset + channel] =
+                    (old + (*value as f32 - old) * coverage.min(1.0)).round() as u8;
+            }
+        }
+    }
+
+    /// 写出 PNG。
+    pub fn write_png<W: Write>(&self, out: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width, self.height);
+        encoder.set_color(png::ColorType::Rgba);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.pixels)
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)
+    }
+}
+
+/// 画面中一块矩形区域的覆盖率
+struct Mask {
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+    values: Vec<f32>,
+}
+
+impl Mask {
+    /// 向外扩展 `radius` 像素，用于描边
+    fn dilate(&self, radius: usize, frame_width: usize, frame_height: usize) -> Mask {
+        let x = self.x.saturating_sub(radius);
+        let y = self.y.saturating_sub(radius);
+        let width = (self.x + self.width + radius).min(frame_width) - x;
+        let height = (self.y + self.height + radius).min(frame_height) - y;
+        let r = radius as isize;
+
+        let mut values = vec![0.0f32; width * height];
+        for row in 0..height {
+            for col in 0..width {
+                let (fx, fy) = ((x + col) as isize, (y + row) as isize);
+                let mut best = 0.0f32;
+                for dy in -r..=r {
+                    for dx in -r..=r {
+                        if dx * dx + dy * dy > r * r {
+                            continue;
+                        }
+                        let (sx, sy) = (fx + dx - self.x as isize, fy + dy - self.y as isize);
+                        if sx < 0
+                            || sy < 0
+                            || sx >= self.width as isize
+                            || sy >= self.height as isize
+                        {
+                            continue;
+                        }
+                        best = best.max(self.values[sy as usize * self.width + sx as usize]);
+                    }
+                }
+                values[row * width + col] = best;
+            }
+        }
+
+        Mask {
+            x,
+            y,
+            width,
+            height,
+            values,
+        }
+    }
+}
+
+/// 字幕渲染器：持有解析好的字体。
+pub struct CueRenderer {
+    font: Font,
+    options: RenderOptions,
+}
+
+impl CueRenderer {
+    /// 从 TrueType / OpenType 字体文件的内容创建渲染器。
+    pub fn new(font_data: &[u8], options: RenderOptions) -> Result<CueRenderer> {
+        let font = Font::from_bytes(font_data, FontSettings::default())
+            .map_err(|err| Error::Invalid(t!("render.bad_font", err)))?;
+        Ok(CueRenderer { font, options })
+    }
+
+    /// 字体中没有的字符（去重，按出现顺序），这些字符会画成字体的缺字符号。
+    pub fn missing_chars(&self, text: &str) -> Vec<char> {
+        let mut missing = Vec::new();
+        for c in text.chars() {
+            if !c.is_whitespace() && self.font.lookup_glyph_index(c) == 0 && !missing.contains(&c) {
+                missing.push(c);
+            }
+        }
+        missing
+    }
+
+    /// 把一条字幕画到空白画面上。
+    pub fn render(&self, text: &str) -> Frame {
+        let (width, height) = (self.options.width, self.options.height);
+        let scale = self.options.scale();
+        let size = BASE_FONT_SIZE * scale;
+        let mut frame = Frame::blank(width, height);
+
+        let max_width = width as f32 - 2.0 * BASE_MARGIN_H * scale;
+        let lines = wrap_lines(text, max_width, |c| {
+            self.font.metrics(c, size).advance_width
+        });
+        if lines.is_empty() {
+            return frame;
+        }
+
+        let (ascent, descent, line_height) = match self.font.horizontal_line_metrics(size) {
+            Some(m) => (m.ascent, m.descent, m.new_line_size),
+            None => (size * 0.8, -size * 0.2, size * 1.2),
+        };
+
+        // 最后一行的基线离底边 MarginV，其它行依次往上排
+        let last_baseline = height as f32 - BASE_MARGIN_V * scale + descent;
+        let first_baseline = last_baseline - line_height * (lines.len() - 1) as f32;
+        let top = (first_baseline - ascent).max(0.0) as usize;
+        let bottom = (last_baseline - descent).ceil().min(height as f32) as usize;
+        if top >= bottom {
+            return frame;
+        }
+
+        let mut mask = Mask {
+            x: 0,
+            y: top,
+            width: width as usize,
+            height: bottom - top,
+            values: vec![0.0; width as usize * (bottom - top)],
+        };
+
+        for (i, line) in lines.iter().enumerate() {
+            let baseline = first_baseline + line_height * i as f32;
+            let line_width: f32 = line
+                .chars()
+                .map(|c| self.font.metrics(c, size).advance_width)
+                .sum();
+            let mut pen_x = (width as f32 - line_width) / 2.0;
+
+            for c in line.chars() {
+                let (metrics, bitmap) = self.font.rasterize(c, size);
+                let glyph_x = (pen_x + metrics.xmin as f32).round() as isize;
+                let glyph_y =
+                    (baseline - metrics.ymin as f32 - metrics.height as f32).round() as isize;
+
+                for row in 0..metrics.height {
+                    let y = glyph_y + row as isize - top as isize;
+                    if y < 0 || y >= mask.height as isize {
+                        continue;
+                    }
+                    for col in 0..metrics.width {
+                        let x = glyph_x + col as isize;
+                        if x < 0 || x >= mask.width as isize {
+                            continue;
+                        }
+                        let value = &mut mask.values[y as usize * mask.width + x as usize];
+                        let coverage = bitmap[row * metrics.width + col] as f32 / 255.0;
+                        *value = value.max(coverage);
+                    }
+                }
+                pen_x += metrics.advance_width;
+            }
+        }
+
+        let outline = (BASE_OUTLINE * scale).round().max(1.0) as usize;
+        frame.blend(
+            &mask.dilate(outline, width as usize, height as usize),
+            [0, 0, 0],
+        );
+        frame.blend(&mask, [0xFF, 0xFF, 0xFF]);
+        frame
+    }
+}
+
+/// 按宽度折行：先按字幕里的换行分行，过长的行在最后一个空格处断开，
+/// 没有空格时（例如中文）在字符之间断开。
+pub fn wrap_lines(text: &str, max_width: f32, advance: impl Fn(char) -> f32) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for source in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let mut line = String::new();
+        let mut line_width = 0.0;
+
+        for c in source.chars() {
+            let w = advance(c);
+            if line_width + w > max_width && !line.is_empty() {
+                match line.rfind(' ') {
+                    Some(space) if space > 0 => {
+                        let rest = line[space + 1..].to_string();
+                        line.truncate(space);
+                        lines.push(std::mem::take(&mut line));
+                        line = rest;
+                    }
+                    _ => lines.push(std::mem::take(&mut line)),
+                }
+                line_width = line.chars().map(&advance).sum();
+                if c == ' ' && line.is_empty() {
+                    continue;
+                }
+            }
+            line.push(c);
+            line_width += w;
+        }
+
+        if !line.is_empty() {
+            lines.push(line);
+        }
+    }
+
+    lines
+}
+
+/// 第 `index` 条（从 1 开始）字幕的静帧文件名。
+pub fn still_file_name(index: usize) -> String {
+    format!("cue_{:04}.png", index)
+}
+
+/// 写出 HTML 索引页：每条字幕一张静帧，下面标出序号、入出点时间码和文本。
+///
+/// 图片按 [`still_file_name`] 引用，索引页应与静帧放在同一目录。
+pub fn write_contact_sheet<W: Write>(
+    mut out: W,
+    cues: &[Cue],
+    fps: f64,
+    title: Option<&str>,
+) -> io::Result<()> {
+    let title = escape_html(title.unwrap_or(i18n::text("render.sheet_title")));
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html lang=\"{}\">", i18n::lang().name())?;
+    writeln!(out, "<head>")?;
+    writeln!(out, "<meta charset=\"utf-8\">")?;
+    writeln!(out, "<title>{}</title>", title)?;
+    writeln!(
+        out,
+        "<style>\n\
+         body {{ background: #111; color: #ddd; font-family: sans-serif; margin: 24px; }}\n\
+         .sheet {{ display: grid; grid-template-columns: repeat(auto-fill, minmax(360px, 1fr)); gap: 16px; }}\n\
+         figure {{ margin: 0; background: #1b1b1b; padding: 8px; }}\n\
+         img {{ width: 100%; display: block; }}\n\
+         figcaption {{ font-size: 13px; margin-top: 6px; }}\n\
+         .tc {{ font-family: monospace; color: #8c8; }}\n\
+         </style>"
+    )?;
+    writeln!(out, "</head>")?;
+    writeln!(out, "<body>")?;
+    writeln!(out, "<h1>{}</h1>", title)?;
+    writeln!(
+        out,
+        "<p>{}</p>",
+        t!("render.sheet_summary", cues.len(), fps)
+    )?;
+    writeln!(out, "<div class=\"sheet\">")?;
+
+    for (i, cue) in cues.iter().enumerate() {
+        let start = Timecode::from_millis(cue.start_ms, fps, false);
+        let end = Timecode::from_millis(cue.end_ms, fps, false);
+        writeln!(out, "<figure>")?;
+        writeln!(
+            out,
+            "<img src=\"{}\" alt=\"{}\" loading=\"lazy\">",
+            still_file_name(i + 1),
+            t!("render.still_alt", i + 1)
+        )?;
+        writeln!(
+            out,
+            "<figcaption>#{} <span class=\"tc\">{} → {}</span><br>{}</figcaption>",
+            i + 1,
+            start,
+            end,
+            escape_html(&cue.text).replace('\n', "<br>")
+        )?;
+        writeln!(out, "</figure>")?;
+    }
+
+    writeln!(out, "</div>")?;
+    writeln!(out, "</body>")?;
+    writeln!(out, "</html>")?;
+    out.flush()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_size() {
+        assert_eq!(
+            RenderOptions::parse_size("1280x720"),
+            Some(RenderOptions {
+                width: 1280,
+                height: 720
+            })
+        );
+        assert_eq!(RenderOptions::parse_size("0x720"), None);
+        assert_eq!(RenderOptions::parse_size("1280"), None);
+    }
+
+    #[test]
+    fn wrap_at_spaces_or_between_chars() {
+        let advance = |_| 10.0;
+        assert_eq!(
+            wrap_lines("hello big world", 100.0, advance),
+            ["hello big", "world"]
+        );
+        assert_eq!(
+            wrap_lines("一二三四五\n六", 30.0, advance),
+            ["一二三", "四五", "六"]
+        );
+        assert!(wrap_lines(" \n", 30.0, advance).is_empty());
+    }
+
+    #[test]
+    fn frame_is_written_as_png() {
+        let frame = Frame::blank(4, 2);
+        let mut out = Vec::new();
+        frame.write_png(&mut out).unwrap();
+
+        let decoder = png::Decoder::new(io::Cursor::new(out));
+        let reader = decoder.read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (4, 2));
+    }
+
+    #[test]
+    fn contact_sheet_lists_stills_and_timecodes() {
+        let cues = [Cue {
+            start_ms: 3_600_000,
+            end_ms: 3_601_040,
+            text: "<你好>\n第二行".to_string(),
+        }];
+        let mut out = Vec::new();
+        write_contact_sheet(&mut out, &cues, 25.0, Some("第 1 集")).unwrap();
+        let html = String::from_utf8(out).unwrap();
+
+        assert!(html.contains("<html lang=\"zh\">"));
+        assert!(html.contains("<title>第 1 集</title>"));
+        assert!(html.contains("alt=\"第 1 条\""));
+        assert!(html.contains("<img src=\"cue_0001.png\""));
+        assert!(html.contains("01:00:00:00 → 01:00:01:01"));
+        assert!(html.contains("&lt;你好&gt;<br>第二行"));
+    }
+
+    /// 测试字体：ASCII 可见字符都是 500x700 的实心方块，字宽 600，
+    /// ascent 800、descent -200（em 为 1000），由 tests/fixtures/blocks_font.py 生成
+    fn block_renderer() -> CueRenderer {
+        let font = include_bytes!("../tests/fixtures/blocks.ttf");
+        let options = RenderOptions {
+            width: 640,
+            height: 360,
+        };
+        CueRenderer::new(font, options).unwrap()
+    }
+
+    /// 与背景色不同的像素坐标
+    fn painted(frame: &Frame) -> Vec<(usize, usize)> {
+        let [r, g, b] = BACKGROUND;
+        frame
+            .pixels
+            .chunks(4)
+            .enumerate()
+            .filter(|(_, pixel)| pixel[..3] != [r, g, b])
+            .map(|(i, _)| (i % frame.width as usize, i / frame.width as usize))
+            .collect()
+    }
+
+    /// 有白色文字的行连成几段，也就是画了几行字
+    fn text_lines(frame: &Frame) -> usize {
+        let rows: Vec<bool> = frame
+            .pixels
+            .chunks(frame.width as usize * 4)
+            .map(|row| row.chunks(4).any(|pixel| pixel[..3] == [0xFF; 3]))
+            .collect();
+        rows.windows(2).filter(|w| !w[0] && w[1]).count() + usize::from(rows[0])
+    }
+
+    #[test]
+    fn renders_outlined_lines_in_bottom_band() {
+        let renderer = block_renderer();
+        let frame = renderer.render("HELLO\nWORLD");
+        assert!(renderer.missing_chars("HELLO WORLD").is_empty());
+
+        // 360 高时缩放 1/3：字号 18，行高 18，底边距 40/3，描边 1 像素
+        let size = 18.0;
+        let last_baseline = 360.0 - 40.0 / 3.0 - 0.2 * size;
+        let top = last_baseline - size - 0.7 * size;
+        let pixels = painted(&frame);
+        assert!(!pixels.is_empty());
+        for &(_, y) in &pixels {
+            let y = y as f32;
+            assert!(
+                y >= top - 2.0 && y <= last_baseline + 2.0,
+                "第 {} 行不应有像素",
+                y
+            );
+        }
+        assert_eq!(text_lines(&frame), 2);
+
+        // 水平居中：五个字宽 5 x 10.8 像素
+        let min_x = pixels.iter().map(|&(x, _)| x).min().unwrap() as f32;
+        let max_x = pixels.iter().map(|&(x, _)| x).max().unwrap() as f32;
+        assert!((min_x + max_x - 640.0).abs() <= 3.0);
+        assert!((max_x - min_x) < 5.0 * 0.6 * size + 4.0);
+
+        // 白字外有黑色描边
+        assert!(frame.pixels.chunks(4).any(|pixel| pixel[..3] == [0, 0, 0]));
+    }
+
+    #[test]
+    fn long_line_wraps_inside_side_margins() {
+        let renderer = block_renderer();
+        // 120 个字约 1296 像素，左右各留 20 像素边距后每行放得下 55 个字
+        let frame = renderer.render(&"ABCDE ".repeat(20));
+        assert_eq!(text_lines(&frame), 3);
+        for (x, _) in painted(&frame) {
+            assert!((19..=621).contains(&x), "第 {} 列超出左右边距", x);
+        }
+        assert_eq!(text_lines(&renderer.render("")), 0);
+    }
+}
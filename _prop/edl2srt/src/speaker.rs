@@ -0,0 +1,360 @@
+//! 说话人：按片段名或卷名识别每个事件的说话人，并按不同方式标注到字幕上。
+//!
+//! 识别规则通常写在配置文件的预设里：
+//!
+//! ```toml
+//! [preset.drama.speakers]
+//! # 片段名上的正则，名为 speaker 的捕获组（没有时取第一个捕获组）是说话人，
+//! # 匹配到的部分默认从字幕文本中去掉：A_小明_台词03 -> 小明 / 台词03
+//! pattern = "^[A-Z]_(?P<speaker>[^_]+)_"
+//! # 正则匹配不到时按卷名查找
+//! reels = { R01 = "小明", R02 = "小红" }
+//! style = "prefix"
+//! ```
+//!
+//! 录制时间重叠的事件合成一条 [`SpeakerCue`]，每个事件一行。
+//! 标注方式见 [`SpeakerStyle`]；其中 `ass-styles` 需要交给
+//! [`crate::format::ass::write_speaker_ass`]，其它方式都合成普通字幕。
+
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::error::{Error, Result};
+use crate::model::{Cue, EdlEvent};
+use crate::t;
+use crate::transform::strip_audio_extension;
+
+/// 说话人的标注方式。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SpeakerStyle {
+    /// 每行前加 `小明：`
+    #[default]
+    Prefix,
+
+    /// 只有一个说话人时不标注，多人对话时每行前加破折号
+    Dash,
+
+    /// 每个说话人一个 ASS 样式，仅 ASS 支持
+    AssStyles,
+
+    /// WebVTT 的 `<v 小明>` 声音标签，仅 WebVTT 支持
+    VttVoice,
+}
+
+impl SpeakerStyle {
+    pub fn parse(name: &str) -> Option<SpeakerStyle> {
+        match name {
+            "prefix" => Some(SpeakerStyle::Prefix),
+            "dash" => Some(SpeakerStyle::Dash),
+            "ass-styles" => Some(SpeakerStyle::AssStyles),
+            "vtt-voice" => Some(SpeakerStyle::VttVoice),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SpeakerStyle::Prefix => "prefix",
+            SpeakerStyle::Dash => "dash",
+            SpeakerStyle::AssStyles => "ass-styles",
+            SpeakerStyle::VttVoice => "vtt-voice",
+        }
+    }
+
+    /// 只能用于某种输出格式时，返回该格式名
+    pub fn required_format(&self) -> Option<&'static str> {
+        match self {
+            SpeakerStyle::AssStyles => Some("ass"),
+            SpeakerStyle::VttVoice => Some("vtt"),
+            _ => None,
+        }
+    }
+}
+
+fn default_separator() -> String {
+    "：".to_string()
+}
+
+fn default_dash() -> String {
+    "-".to_string()
+}
+
+fn default_strip() -> bool {
+    true
+}
+
+/// 说话人识别与标注规则。
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct SpeakerRules {
+    /// 片段名上的正则
+    pub pattern: Option<String>,
+
+    /// 卷名到说话人的对应表，正则没有匹配时使用
+    pub reels: BTreeMap<String, String>,
+
+    /// 是否从字幕文本中去掉正则匹配到的部分
+    #[serde(default = "default_strip")]
+    pub strip: bool,
+
+    pub style: SpeakerStyle,
+
+    /// `prefix` 方式中说话人和台词之间的分隔
+    #[serde(default = "default_separator")]
+    pub separator: String,
+
+    /// `dash` 方式中每行前的破折号
+    #[serde(default = "default_dash")]
+    pub dash: String,
+}
+
+impl Default for SpeakerRules {
+    fn default() -> Self {
+        SpeakerRules {
+            pattern: None,
+            reels: BTreeMap::new(),
+            strip: default_strip(),
+            style: SpeakerStyle::default(),
+            separator: default_separator(),
+            dash: default_dash(),
+        }
+    }
+}
+
+/// 字幕中的一行：说话人和台词。
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeakerLine {
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+/// 一条带说话人的字幕，可能包含多人对话。
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeakerCue {
+    pub start_ms: u64,
+    pub end_ms: u64,
+    pub lines: Vec<SpeakerLine>,
+}
+
+/// 编译后的识别规则。
+#[derive(Debug, Clone)]
+pub struct SpeakerExtractor {
+    pattern: Option<Regex>,
+    reels: BTreeMap<String, String>,
+    strip: bool,
+}
+
+impl SpeakerRules {
+    /// 编译正则，正则无效时报错。
+    pub fn compile(&self) -> Result<SpeakerExtractor> {
+        let pattern = self
+            .pattern
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|err| Error::Invalid(t!("speaker.bad_regex", err)))?;
+
+        Ok(SpeakerExtractor {
+            pattern,
+            reels: self.reels.clone(),
+            strip: self.strip,
+        })
+    }
+
+    /// 把带说话人的字幕合成普通字幕。`ass-styles` 方式按 `prefix` 合成，供质检和提示使用。
+    pub fn render(&self, cue: &SpeakerCue) -> Cue {
+        let speakers = cue
+            .lines
+            .iter()
+            .filter_map(|line| line.speaker.as_deref())
+            .collect::<std::collections::BTreeSet<_>>();
+
+        let lines: Vec<String> = cue
+            .lines
+            .iter()
+            .map(|line| match (&line.speaker, self.style) {
+                (Some(speaker), SpeakerStyle::Prefix | SpeakerStyle::AssStyles) => {
+                    format!("{}{}{}", speaker, self.separator, line.text)
+                }
+                (Some(speaker), SpeakerStyle::VttVoice) => {
+                    format!("<v {}>{}", speaker.replace('>', "&gt;"), line.text)
+                }
+                (_, SpeakerStyle::Dash) if cue.lines.len() > 1 && speakers.len() > 1 => {
+                    format!("{}{}", self.dash, line.text)
+                }
+                _ => line.text.clone(),
+            })
+            .collect();
+
+        Cue {
+            start_ms: cue.start_ms,
+            end_ms: cue.end_ms,
+            text: lines.join("\n"),
+        }
+    }
+}
+
+impl SpeakerExtractor {
+    /// 识别事件的说话人和台词。没有任何文本的事件返回 None。
+    ///
+    /// 事件已有 `text`（例如转写结果）时台词直接使用它，
+    /// 否则使用片段名去掉匹配部分和音频扩展名后的结果。
+    pub fn extract(&self, event: &EdlEvent) -> Option<SpeakerLine> {
+        let clip_name = event.clip_name.as_deref().unwrap_or("");
+
+        let matched = self.pattern.as_ref().and_then(|re| {
+            let caps = re.captures(clip_name)?;
+            let speaker = caps
+                .name("speaker")
+                .or_else(|| caps.get(1))
+                .map(|m| m.as_str().trim().to_string())
+                .filter(|s| !s.is_empty())?;
+            Some((speaker, caps.get(0).expect("整个匹配").range()))
+        });
+
+        let speaker = match &matched {
+            Some((speaker, _)) => Some(speaker.clone()),
+            None => self.reels.get(&event.reel).cloned(),
+        };
+
+        let text = match (&event.text, &matched) {
+            (Some(text), _) => text.clone(),
+            (None, Some((_, range))) if self.strip => {
+                let mut rest = clip_name.to_string();
+                rest.replace_range(range.clone(), "");
+                strip_audio_extension(rest.trim())
+            }
+            (None, _) if event.clip_name.is_some() => strip_audio_extension(clip_name),
+            (None, _) => return None,
+        };
+
+        Some(SpeakerLine { speaker, text })
+    }
+}
+
+/// 按说话人生成字幕：录制时间重叠的事件合成一条，每个事件一行。
+pub fn speaker_cues(
+    events: &[EdlEvent],
+    extractor: &SpeakerExtractor,
+    fps: f64,
+) -> Vec<SpeakerCue> {
+    let mut lines: Vec<(u64, u64, SpeakerLine)> = events
+        .iter()
+        .filter_map(|event| {
+            let line = extractor.extract(event)?;
+            Some((
+                event.record_in.to_millis(fps),
+                event.record_out.to_millis(fps),
+                line,
+            ))
+        })
+        .filter(|(_, _, line)| !line.text.is_empty())
+        .collect();
+    lines.sort_by_key(|(start, _, _)| *start);
+
+    let mut cues: Vec<SpeakerCue> = Vec::new();
+    for (start_ms, end_ms, line) in lines {
+        match cues.last_mut() {
+            Some(cue) if start_ms < cue.end_ms => {
+                cue.end_ms = cue.end_ms.max(end_ms);
+                cue.lines.push(line);
+            }
+            _ => cues.push(SpeakerCue {
+                start_ms,
+                end_ms,
+                lines: vec![line],
+            }),
+        }
+    }
+    cues
+}
+
+/// 把带说话人的字幕整体前移 `origin_ms`，规则同 [`crate::transform::rebase_cues`]。
+pub fn rebase_speaker_cues(cues: &mut Vec<SpeakerCue>, origin_ms: u64) {
+    cues.retain(|cue| cue.end_ms > origin_ms);
+    for cue in cues {
+        cue.start_ms = cue.start_ms.saturating_sub(origin_ms);
+        cue.end_ms -= origin_ms;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::parse_edl;
+
+    const EDL: &str = "001 R01 A1 C 00:00:00:00 00:00:02:00 01:00:00:00 01:00:02:00\n\
+                       * FROM CLIP NAME: A_小明_你好.wav\n\
+                       002 R02 A2 C 00:00:00:00 00:00:02:00 01:00:01:00 01:00:03:00\n\
+                       * FROM CLIP NAME: 你也好.wav\n\
+                       003 R01 A1 C 00:00:00:00 00:00:02:00 01:00:04:00 01:00:05:00\n\
+                       * FROM CLIP NAME: A_小明_再见\n";
+
+    fn rules(style: SpeakerStyle) -> SpeakerRules {
+        SpeakerRules {
+            pattern: Some("^[A-Z]_(?P<speaker>[^_]+)_".to_string()),
+            reels: BTreeMap::from([("R02".to_string(), "小红".to_string())]),
+            style,
+            ..SpeakerRules::default()
+        }
+    }
+
+    fn render(style: SpeakerStyle) -> Vec<String> {
+        let rules = rules(style);
+        let cues = speaker_cues(&parse_edl(EDL).events, &rules.compile().unwrap(), 25.0);
+        cues.iter().map(|cue| rules.render(cue).text).collect()
+    }
+
+    #[test]
+    fn overlapping_events_share_a_cue() {
+        let rules = rules(SpeakerStyle::Prefix);
+        let cues = speaker_cues(&parse_edl(EDL).events, &rules.compile().unwrap(), 25.0);
+
+        assert_eq!(cues.len(), 2);
+        assert_eq!((cues[0].start_ms, cues[0].end_ms), (3_600_000, 3_603_000));
+        assert_eq!(
+            cues[0].lines,
+            [
+                SpeakerLine {
+                    speaker: Some("小明".to_string()),
+                    text: "你好".to_string()
+                },
+                SpeakerLine {
+                    speaker: Some("小红".to_string()),
+                    text: "你也好".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn render_styles() {
+        assert_eq!(
+            render(SpeakerStyle::Prefix),
+            ["小明：你好\n小红：你也好", "小明：再见"]
+        );
+        assert_eq!(render(SpeakerStyle::Dash), ["-你好\n-你也好", "再见"]);
+        assert_eq!(
+            render(SpeakerStyle::VttVoice),
+            ["<v 小明>你好\n<v 小红>你也好", "<v 小明>再见"]
+        );
+    }
+
+    #[test]
+    fn invalid_pattern_is_reported() {
+        let rules = SpeakerRules {
+            pattern: Some("(".to_string()),
+            ..SpeakerRules::default()
+        };
+        assert!(
+            rules
+                .compile()
+                .unwrap_err()
+                .to_string()
+                .contains("说话人正则无效")
+        );
+    }
+}
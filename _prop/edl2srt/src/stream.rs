@@ -0,0 +1,487 @@
+//! 流式转换：边读边解码、边解析边写出，用于几个 GB 的超大 EDL 或日志。
+//!
+//! 普通流程先把整个文件读进内存、解码成一个 `String`、收集全部事件后再写出；
+//! 这里按块读取 [`BufRead`]，逐行解析，每个事件一完整就换算成字幕交给写出器，
+//! 内存占用与文件大小无关。
+//!
+//! 自动检测编码和推断帧率只看文件开头的一段样本（[`SAMPLE_SIZE`]），
+//! 优先级与 [`crate::reader::decode_edl_bytes`] 相同：指定编码、BOM、自动检测。
+//!
+//! ```
+//! use edl2srt::format::srt::SrtFormat;
+//! use edl2srt::stream::{EventStream, StreamOptions, stream_convert};
+//!
+//! let edl = "001  AX  A  C  00:00:00:00 00:00:01:00 01:00:00:00 01:00:01:00\n\
+//!            * FROM CLIP NAME: hello.wav\n";
+//! let mut events = EventStream::new(edl.as_bytes(), None).unwrap();
+//!
+//! let mut out = Vec::new();
+//! let options = StreamOptions::new(25.0);
+//! let report = stream_convert(&mut events, &SrtFormat, &mut out, &options, &mut |_| {}).unwrap();
+//! assert_eq!(report.cues, 1);
+//! assert_eq!(
+//!     String::from_utf8(out).unwrap(),
+//!     "1\n01:00:00,000 --> 01:00:01,000\nhello\n\n"
+//! );
+//! ```
+
+use std::io::{self, BufRead, Read, Write};
+
+use chardetng::EncodingDetector;
+use encoding_rs::{CoderResult, Decoder, Encoding};
+
+use crate::error::{Error, Result};
+use crate::format::{SubtitleWriter, WriteOptions};
+use crate::fps::{FpsInference, infer_fps};
+use crate::model::{Cue, EdlEvent, SubtitleEntry, Warning};
+use crate::reader::{EdlParser, parse_edl};
+use crate::transform::{CleanupRules, event_text};
+
+/// 自动检测编码和推断帧率时看的样本大小
+pub const SAMPLE_SIZE: usize = 64 * 1024;
+
+/// 逐行解码：按块从 [`BufRead`] 读取字节，增量解码后按行交出。
+///
+/// 换行规则与 [`str::lines`] 相同；多字节字符和 `\r\n` 跨块也能正确处理。
+pub struct LineDecoder<R> {
+    input: R,
+    encoding_name: String,
+    decoder: Decoder,
+
+    /// 检测编码时读出的样本，第一次取行时先解码它
+    sample: Vec<u8>,
+
+    /// 已解码、还没交出的文本
+    text: String,
+    eof: bool,
+    had_errors: bool,
+}
+
+impl<R: BufRead> LineDecoder<R> {
+    /// 读取开头的样本并确定编码，优先级：指定编码、BOM、自动检测。
+    pub fn new(mut input: R, preferred_encoding: Option<&str>) -> Result<Self> {
+        let mut sample = Vec::with_capacity(SAMPLE_SIZE);
+        (&mut input)
+            .take(SAMPLE_SIZE as u64)
+            .read_to_end(&mut sample)?;
+        let eof = sample.len() < SAMPLE_SIZE;
+
+        let (encoding, encoding_name) = match preferred_encoding {
+            Some(label) => {
+                let encoding = Encoding::for_label(label.as_bytes())
+                    .ok_or_else(|| Error::UnsupportedEncoding(label.to_string()))?;
+                (encoding, encoding.name().to_string())
+            }
+            None => match Encoding::for_bom(&sample) {
+                Some((encoding, _)) => (encoding, format!("{} (BOM)", encoding.name())),
+                None => {
+                    let mut detector = EncodingDetector::new();
+                    detector.feed(&sample, eof);
+                    let guessed = detector.guess(None, true);
+                    (guessed, guessed.name().to_string())
+                }
+            },
+        };
+
+        Ok(LineDecoder {
+            input,
+            encoding_name,
+            // 与 Encoding::decode 一样识别并去掉 BOM
+            decoder: encoding.new_decoder(),
+            sample,
+            text: String::new(),
+            eof: false,
+            had_errors: false,
+        })
+    }
+
+    /// 本次使用的编码名称
+    pub fn encoding_name(&self) -> &str {
+        &self.encoding_name
+    }
+
+    /// 目前为止解码时是否遇到非法字节
+    pub fn had_errors(&self) -> bool {
+        self.had_errors
+    }
+
+    /// 开头样本解码后的文本，去掉可能被截断的最后一行。
+    ///
+    /// 只在第一次调用 [`LineDecoder::next_line`] 之前有内容。
+    pub fn sample_text(&self) -> String {
+        let mut decoder = self.decoder.encoding().new_decoder();
+        let mut text = String::new();
+        let complete = self.sample.len() < SAMPLE_SIZE;
+        decode_into(&mut decoder, &self.sample, &mut text, complete);
+        if !complete {
+            let end = text.rfind('\n').map_or(0, |i| i + 1);
+            text.truncate(end);
+        }
+        text
+    }
+
+    /// 取下一行（不含换行符），读完时返回 None。
+    pub fn next_line(&mut self) -> io::Result<Option<String>> {
+        if !self.sample.is_empty() {
+            let sample = std::mem::take(&mut self.sample);
+            self.had_errors |= decode_into(&mut self.decoder, &sample, &mut self.text, false);
+        }
+
+        let mut searched = 0;
+        loop {
+            if let Some(i) = self.text[searched..].find('\n') {
+                let end = searched + i;
+                let line = self.text[..end]
+                    .strip_suffix('\r')
+                    .unwrap_or(&self.text[..end]);
+                let line = line.to_string();
+                self.text.drain(..=end);
+                return Ok(Some(line));
+            }
+            searched = self.text.len();
+
+            if self.eof {
+                if self.text.is_empty() {
+                    return Ok(None);
+                }
+                return Ok(Some(std::mem::take(&mut self.text)));
+            }
+
+            let chunk = self.input.fill_buf()?;
+            let last = chunk.is_empty();
+            self.had_errors |= decode_into(&mut self.decoder, chunk, &mut self.text, last);
+            let read = chunk.len();
+            self.input.consume(read);
+            self.eof = last;
+        }
+    }
+}
+
+/// 把 `src` 解码后追加到 `text`，返回是否遇到非法字节。
+fn decode_into(decoder: &mut Decoder, mut src: &[u8], text: &mut String, last: bool) -> bool {
+    let mut had_errors = false;
+    loop {
+        if let Some(needed) = decoder.max_utf8_buffer_length(src.len()) {
+            text.reserve(needed);
+        }
+        let (result, read, errors) = decoder.decode_to_string(src, text, last);
+        had_errors |= errors;
+        src = &src[read..];
+        if result == CoderResult::InputEmpty {
+            return had_errors;
+        }
+    }
+}
+
+/// 逐个交出 EDL 事件的迭代器。
+///
+/// 创建时先读到第一个事件行为止，这样写出器开始写之前就能拿到 `TITLE:`。
+/// 读取出错后迭代结束，错误作为最后一项交出。
+pub struct EventStream<R> {
+    lines: LineDecoder<R>,
+    parser: EdlParser,
+    sample_fps: FpsInference,
+    eof: bool,
+}
+
+impl<R: BufRead> EventStream<R> {
+    pub fn new(input: R, preferred_encoding: Option<&str>) -> Result<Self> {
+        let lines = LineDecoder::new(input, preferred_encoding)?;
+        let sample_fps = infer_fps(&parse_edl(&lines.sample_text()));
+
+        let mut stream = EventStream {
+            lines,
+            parser: EdlParser::new(),
+            sample_fps,
+            eof: false,
+        };
+        while !stream.parser.has_events() {
+            match stream.lines.next_line()? {
+                Some(line) => {
+                    // 还没有事件，不会交出事件
+                    stream.parser.push_line(&line);
+                }
+                None => {
+                    stream.eof = true;
+                    break;
+                }
+            }
+        }
+        Ok(stream)
+    }
+
+    /// 本次使用的编码名称
+    pub fn encoding_name(&self) -> &str {
+        self.lines.encoding_name()
+    }
+
+    /// 目前为止解码时是否遇到非法字节
+    pub fn had_errors(&self) -> bool {
+        self.lines.had_errors()
+    }
+
+    /// 事件之前的 `TITLE:` 行
+    pub fn title(&self) -> Option<&str> {
+        self.parser.title()
+    }
+
+    /// 根据开头样本推断的帧率
+    pub fn sample_fps(&self) -> &FpsInference {
+        &self.sample_fps
+    }
+
+    /// 取走目前为止的解析警告。
+    pub fn take_warnings(&mut self) -> Vec<Warning> {
+        self.parser.take_warnings()
+    }
+}
+
+impl<R: BufRead> Iterator for EventStream<R> {
+    type Item = Result<EdlEvent>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.eof {
+            match self.lines.next_line() {
+                Ok(Some(line)) => {
+                    if let Some(event) = self.parser.push_line(&line) {
+                        return Some(Ok(event));
+                    }
+                }
+                Ok(None) => self.eof = true,
+                Err(err) => {
+                    self.eof = true;
+                    self.parser.finish();
+                    return Some(Err(err.into()));
+                }
+            }
+        }
+        self.parser.finish().map(Ok)
+    }
+}
+
+/// 流式转换的设置。
+#[derive(Debug, Clone)]
+pub struct StreamOptions {
+    pub fps: f64,
+
+    /// 字幕文本的清理规则
+    pub cleanup: CleanupRules,
+
+    /// 成片文件的起点，输出时间都减去它（毫秒）
+    pub origin_ms: Option<u64>,
+}
+
+impl StreamOptions {
+    pub fn new(fps: f64) -> Self {
+        StreamOptions {
+            fps,
+            cleanup: CleanupRules::default(),
+            origin_ms: None,
+        }
+    }
+}
+
+/// 流式转换的统计。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamReport {
+    /// 读到的事件数
+    pub events: usize,
+
+    /// 写出的字幕条数
+    pub cues: usize,
+
+    /// 早于起点而忽略的字幕条数
+    pub before_origin: usize,
+}
+
+/// 把事件逐个换算成字幕交给写出器，结果与普通流程相同。
+///
+/// 解析警告随读随交给 `on_warning`，不在内存中累积。
+/// 写出器不支持流式（见 [`SubtitleWriter::streams`]）时仍会先收集全部字幕。
+pub fn stream_convert<R: BufRead>(
+    events: &mut EventStream<R>,
+    writer: &dyn SubtitleWriter,
+    out: &mut dyn Write,
+    options: &StreamOptions,
+    on_warning: &mut dyn FnMut(Warning),
+) -> Result<StreamReport> {
+    let write_options = WriteOptions {
+        fps: options.fps,
+        title: events.title().map(str::to_string),
+        drop_frame: events.sample_fps().evidence.drop_frame,
+    };
+
+    let mut report = StreamReport::default();
+    let mut failure: Option<Error> = None;
+    {
+        let mut cues = std::iter::from_fn(|| {
+            loop {
+                let next = events.next();
+                for warning in events.take_warnings() {
+                    on_warning(warning);
+                }
+                let event = match next? {
+                    Ok(event) => event,
+                    Err(err) => {
+                        failure = Some(err);
+                        return None;
+                    }
+                };
+                report.events += 1;
+
+                if let Some(cue) = event_cue(&event, options, &mut report) {
+                    report.cues += 1;
+                    return Some(cue);
+                }
+            }
+        });
+        writer.write_iter(out, &mut cues, &write_options)?;
+    }
+
+    match failure {
+        Some(err) => Err(err),
+        None => Ok(report),
+    }
+}
+
+/// 单个事件对应的字幕，依次做文本清理和起点换算，规则与普通流程相同。
+fn event_cue(event: &EdlEvent, options: &StreamOptions, report: &mut StreamReport) -> Option<Cue> {
+    let entry = SubtitleEntry {
+        start: event.record_in,
+        end: event.record_out,
+        text: event_text(event)?,
+    };
+    let mut cue = entry.to_cue(options.fps);
+
+    if !options.cleanup.is_empty() {
+        cue.text = options.cleanup.apply(&cue.text);
+        if cue.text.trim().is_empty() {
+            return None;
+        }
+    }
+
+    if let Some(origin_ms) = options.origin_ms {
+        if cue.end_ms <= origin_ms {
+            report.before_origin += 1;
+            return None;
+        }
+        cue.start_ms = cue.start_ms.saturating_sub(origin_ms);
+        cue.end_ms -= origin_ms;
+    }
+
+    Some(cue)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::srt::SrtFormat;
+    use crate::transform::{clean_cues, entries_to_cues, events_to_entries, rebase_cues};
+    use std::io::BufReader;
+
+    const EDL: &str = "TITLE: 流式\r\nFCM: NON-DROP FRAME\r\n\r\n\
+                       001  AX  A  C  00:00:00:00 00:00:01:00 00:59:58:00 00:59:59:12\r\n\
+                       * FROM CLIP NAME: 开场.wav\r\n\
+                       002  AX  A  C  00:00:00:00 00:00:01:00 01:00:01:00 01:00:02:00\r\n\
+                       * FROM CLIP NAME: 你好_世界.wav\r\n\
+                       * 备注\r\n\
+                       003  AX  A  C  00:00:00:00 00:00:01:00 01:00:03:00 01:00:04:00\r\n\
+                       * FROM CLIP NAME: (V)\r\n\
+                       oops\r\n\
+                       004  AX  A  C  00:00:00:00 00:00:01:00 01:00:05:00 01:00:06:00\r\n\
+                       * FROM CLIP NAME: 最后一句";
+
+    fn options() -> StreamOptions {
+        StreamOptions {
+            fps: 25.0,
+            cleanup: CleanupRules {
+                replace: vec![("_".to_string(), " ".to_string())],
+                remove: vec!["(V)".to_string()],
+                collapse_spaces: true,
+            },
+            origin_ms: Some(3_600_000),
+        }
+    }
+
+    /// 超过样本大小的 EDL，读完样本后还要按块读很多次
+    fn large_edl() -> String {
+        let (header, body) = EDL.split_once("\r\n\r\n").unwrap();
+        let mut text = format!("{}\r\n\r\n", header);
+        while text.len() < SAMPLE_SIZE * 2 {
+            text.push_str(body);
+            text.push_str("\r\n");
+        }
+        text
+    }
+
+    #[test]
+    fn splits_lines_across_tiny_chunks() {
+        // 样本正好在“第”字中间结束，之后每次只给一个字节，\r\n 也会被切开
+        let padding = "x".repeat(SAMPLE_SIZE - 2);
+        let text = format!("{}\n第一行\r\n第二行\n\n最后", padding);
+        let input = BufReader::with_capacity(1, text.as_bytes());
+        let mut lines = LineDecoder::new(input, Some("utf-8")).unwrap();
+
+        let mut got = Vec::new();
+        while let Some(line) = lines.next_line().unwrap() {
+            got.push(line);
+        }
+        assert_eq!(got, [padding.as_str(), "第一行", "第二行", "", "最后"]);
+        assert!(!lines.had_errors());
+    }
+
+    #[test]
+    fn matches_whole_file_conversion() {
+        let text = large_edl();
+        let edl = parse_edl(&text);
+        let expected = rebase_cues(
+            &clean_cues(
+                &entries_to_cues(&events_to_entries(&edl.events), 25.0),
+                &options().cleanup,
+            ),
+            3_600_000,
+        );
+        let mut expected_out = Vec::new();
+        SrtFormat
+            .write(&mut expected_out, &expected, &WriteOptions::new(25.0))
+            .unwrap();
+
+        for capacity in [1, 7, 8192] {
+            let input = BufReader::with_capacity(capacity, text.as_bytes());
+            let mut events = EventStream::new(input, None).unwrap();
+            assert_eq!(events.title(), Some("流式"));
+
+            let mut warnings = Vec::new();
+            let mut out = Vec::new();
+            let report = stream_convert(&mut events, &SrtFormat, &mut out, &options(), &mut |w| {
+                warnings.push(w)
+            })
+            .unwrap();
+
+            assert!(out == expected_out, "块大小 {} 时输出不一致", capacity);
+            assert_eq!(warnings, edl.warnings);
+            assert_eq!(report.events, edl.events.len());
+            assert_eq!(report.cues, expected.len());
+            assert_eq!(report.before_origin, edl.events.len() / 4);
+        }
+    }
+
+    #[test]
+    fn detects_encoding_and_fps_from_sample() {
+        let text = large_edl();
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in text.encode_utf16() {
+            bytes.extend(unit.to_le_bytes());
+        }
+        let mut events = EventStream::new(BufReader::with_capacity(5, &bytes[..]), None).unwrap();
+        assert_eq!(events.encoding_name(), "UTF-16LE (BOM)");
+        assert_eq!(
+            events.sample_fps().candidates,
+            infer_fps(&parse_edl(EDL)).candidates
+        );
+
+        let streamed: Vec<EdlEvent> = events.by_ref().map(|event| event.unwrap()).collect();
+        assert!(!events.had_errors());
+        assert_eq!(streamed, parse_edl(&text).events);
+    }
+}
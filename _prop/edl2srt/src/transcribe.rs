@@ -0,0 +1,473 @@
+//! 用语音转写结果作为音频片段的字幕文本。
+//!
+//! EDL 里的片段名往往只是 `VO_012.wav`，真正的台词在音频里。这里把每个音频事件
+//! 引用的源文件发给本地的 WhisperLiveKit（或任何兼容 OpenAI
+//! `/v1/audio/transcriptions` 接口的服务），把返回的文本写进事件的 `text`。
+//!
+//! - 源文件优先取 `* SOURCE FILE:` 注释里的路径，找不到时在媒体搜索目录中按文件名查找
+//! - 转写结果按文件内容的 SHA-256 缓存，同一个文件只转写一次，重复运行也不会再请求
+//! - 只使用标准库的 TCP 连接，支持 `http://` 地址，适合本机或局域网内的服务
+
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::error::{Error, Result};
+use crate::model::{Edl, EdlEvent, Warning};
+use crate::t;
+
+/// 默认的转写接口，对应仓库中 WhisperLiveKit docker-compose 映射的端口。
+pub const DEFAULT_ENDPOINT: &str = "http://localhost:8000/v1/audio/transcriptions";
+
+/// 单个文件转写的超时时间
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// multipart 请求的分隔符
+const BOUNDARY: &str = "----edl2srt-transcribe-boundary";
+
+/// 转写服务的地址。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Endpoint {
+    pub host: String,
+    pub port: u16,
+
+    /// 请求路径，例如 `/v1/audio/transcriptions`
+    pub path: String,
+}
+
+impl Endpoint {
+    /// 解析 `http://主机[:端口][/路径]`，省略路径时使用标准的转写路径。
+    pub fn parse(url: &str) -> Result<Self> {
+        let rest = url
+            .strip_prefix("http://")
+            .ok_or_else(|| Error::Invalid(t!("transcribe.http_only", url)))?;
+
+        let (authority, path) = match rest.find('/') {
+            Some(i) => (&rest[..i], &rest[i..]),
+            None => (rest, "/v1/audio/transcriptions"),
+        };
+
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => {
+                let port = port
+                    .parse()
+                    .map_err(|_| Error::Invalid(t!("transcribe.bad_port", url)))?;
+                (host, port)
+            }
+            None => (authority, 80),
+        };
+
+        if host.is_empty() {
+            return Err(Error::Invalid(t!("transcribe.missing_host", url)));
+        }
+
+        Ok(Endpoint {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+}
+
+/// 转写器：负责查找源文件、查缓存、调用转写服务。
+#[derive(Debug, Clone)]
+pub struct Transcriber {
+    pub endpoint: Endpoint,
+
+    /// 缓存目录，不存在时自动创建
+    pub cache_dir: PathBuf,
+
+    /// 找不到 `SOURCE FILE` 路径时，按文件名在这些目录（含子目录）中查找
+    pub media_paths: Vec<PathBuf>,
+
+    /// 识别语言，例如 "zh"；None 表示交给服务自动判断
+    pub language: Option<String>,
+
+    /// 单个文件的超时时间
+    pub timeout: Duration,
+}
+
+/// 一个文件的转写结果。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transcript {
+    pub text: String,
+
+    /// 是否来自缓存
+    pub cached: bool,
+}
+
+/// 批量转写的统计与警告。
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TranscribeReport {
+    /// 本次实际请求了转写服务的文件数
+    pub transcribed: usize,
+
+    /// 命中缓存的事件数
+    pub cached: usize,
+
+    /// 找不到源文件、转写失败等问题；这些事件保留原来的片段名
+    pub warnings: Vec<Warning>,
+}
+
+impl Transcriber {
+    pub fn new(endpoint: Endpoint, cache_dir: impl Into<PathBuf>) -> Self {
+        Transcriber {
+            endpoint,
+            cache_dir: cache_dir.into(),
+            media_paths: Vec::new(),
+            language: None,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+
+    /// 找到事件引用的源文件。
+    pub fn resolve_source(&self, event: &EdlEvent) -> Option<PathBuf> {
+        let source = source_file(event);
+
+        if let Some(path) = source.map(Path::new)
+            && path.is_file()
+        {
+            return Some(path.to_path_buf());
+        }
+
+        // SOURCE FILE 往往是剪辑机上的绝对路径，这里只取文件名来找
+        let names: Vec<&str> = source
+            .and_then(|s| s.rsplit(['/', '\\']).next())
+            .into_iter()
+            .chain(event.clip_name.as_deref())
+            .collect();
+
+        self.media_paths
+            .iter()
+            .find_map(|dir| names.iter().find_map(|name| find_file(dir, name)))
+    }
+
+    /// 转写一个文件，命中缓存时不请求服务。
+    pub fn transcribe_file(&self, path: &Path) -> Result<Transcript> {
+        let bytes = fs::read(path)?;
+        let cache_path = self.cache_path(&bytes);
+
+        if let Ok(text) = fs::read_to_string(&cache_path) {
+            return Ok(Transcript { text, cached: true });
+        }
+
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("audio.wav");
+        let body = self.request(file_name, &bytes)?;
+        let text = transcript_text(&body)?;
+
+        fs::create_dir_all(&self.cache_dir)?;
+        fs::write(&cache_path, &text)?;
+
+        Ok(Transcript {
+            text,
+            cached: false,
+        })
+    }
+
+    /// 为 EDL 中的每个音频事件转写源文件，结果写入事件的 `text`。
+    ///
+    /// 单个事件失败不影响其它事件，失败原因记在报告的警告里。
+    pub fn transcribe_events(&self, edl: &mut Edl) -> TranscribeReport {
+        let mut report = TranscribeReport::default();
+
+        for event in edl.events.iter_mut().filter(|e| is_audio_event(e)) {
+            let Some(path) = self.resolve_source(event) else {
+                report.warnings.push(Warning {
+                    line: event.line,
+                    message: t!(
+                        "transcribe.source_not_found",
+                        source_file(event)
+                            .or(event.clip_name.as_deref())
+                            .unwrap_or("")
+                    ),
+                });
+                continue;
+            };
+
+            match self.transcribe_file(&path) {
+                Ok(transcript) if transcript.text.trim().is_empty() => {
+                    report.warnings.push(Warning {
+                        line: event.line,
+                        message: t!("transcribe.empty_result", path.display()),
+                    });
+                }
+                Ok(transcript) => {
+                    if transcript.cached {
+                        report.cached += 1;
+                    } else {
+                        report.transcribed += 1;
+                    }
+                    event.text = Some(transcript.text.trim().to_string());
+                }
+                Err(err) => report.warnings.push(Warning {
+                    line: event.line,
+                    message: format!("{}: {}", path.display(), err),
+                }),
+            }
+        }
+
+        report
+    }
+
+    fn cache_path(&self, bytes: &[u8]) -> PathBuf {
+        let hash: String = Sha256::digest(bytes)
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect();
+        let name = match &self.language {
+            Some(language) => format!("{}.{}.txt", hash, language),
+            None => format!("{}.txt", hash),
+        };
+        self.cache_dir.join(name)
+    }
+
+    /// 以 multipart/form-data 上传文件，返回响应正文。
+    fn request(&self, file_name: &str, bytes: &[u8]) -> Result<Vec<u8>> {
+        let mut body = Vec::with_capacity(bytes.len() + 512);
+        let mut field = |name: &str, value: &str| {
+            body.extend_from_slice(
+                format!(
+                    "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                    BOUNDARY, name, value
+                )
+                .as_bytes(),
+            );
+        };
+        field("model", "whisper-1");
+        field("response_format", "json");
+        if let Some(language) = &self.language {
+            field("language", language);
+        }
+        body.extend_from_slice(
+            format!(
+                "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\n\
+                 Content-Type: application/octet-stream\r\n\r\n",
+                BOUNDARY,
+                file_name.replace('"', "_")
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(bytes);
+        body.extend_from_slice(format!("\r\n--{}--\r\n", BOUNDARY).as_bytes());
+
+        let endpoint = &self.endpoint;
+        let mut stream = TcpStream::connect((endpoint.host.as_str(), endpoint.port))?;
+        stream.set_read_timeout(Some(self.timeout))?;
+        stream.set_write_timeout(Some(self.timeout))?;
+
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: {}:{}\r\nUser-Agent: edl2srt\r\nAccept: application/json\r\n\
+             Content-Type: multipart/form-data; boundary={}\r\nContent-Length: {}\r\n\
+             Connection: close\r\n\r\n",
+            endpoint.path,
+            endpoint.host,
+            endpoint.port,
+            BOUNDARY,
+            body.len()
+        )?;
+        stream.write_all(&body)?;
+        stream.flush()?;
+
+        let mut response = Vec::new();
+        stream.read_to_end(&mut response)?;
+        parse_http_response(&response)
+    }
+}
+
+/// 事件是否是音频事件：轨道以 A 开头（A、A1、AA 等），或者带有 Avid 的 `AUD` 续行。
+pub fn is_audio_event(event: &EdlEvent) -> bool {
+    event
+        .track
+        .chars()
+        .next()
+        .is_some_and(|c| c.eq_ignore_ascii_case(&'A'))
+        || !event.audio_channels.is_empty()
+}
+
+/// 事件注释中的 `SOURCE FILE:` 路径。
+pub fn source_file(event: &EdlEvent) -> Option<&str> {
+    event.comments.iter().find_map(|comment| {
+        let (key, value) = comment.split_once(':')?;
+        key.trim()
+            .eq_ignore_ascii_case("SOURCE FILE")
+            .then(|| value.trim())
+            .filter(|v| !v.is_empty())
+    })
+}
+
+/// 在目录及其子目录中查找文件名相同的文件（不区分大小写）。
+fn find_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    let mut subdirs = Vec::new();
+
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            subdirs.push(path);
+        } else if entry
+            .file_name()
+            .to_str()
+            .is_some_and(|n| n.eq_ignore_ascii_case(name))
+        {
+            return Some(path);
+        }
+    }
+
+    subdirs.sort();
+    subdirs.iter().find_map(|sub| find_file(sub, name))
+}
+
+/// 拆出 HTTP 响应的正文，非 2xx 状态视为错误。
+fn parse_http_response(response: &[u8]) -> Result<Vec<u8>> {
+    let split = response
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .ok_or_else(|| Error::Transcribe(t!("transcribe.incomplete_response")))?;
+    let head = String::from_utf8_lossy(&response[..split]);
+    let body = &response[split + 4..];
+
+    let mut lines = head.lines();
+    let status: u16 = lines
+        .next()
+        .and_then(|l| l.split_whitespace().nth(1))
+        .and_then(|s| s.parse().ok())
+        .ok_or_else(|| Error::Transcribe(t!("transcribe.not_http")))?;
+
+    let header = |name: &str| {
+        head.lines().skip(1).find_map(|l| {
+            let (key, value) = l.split_once(':')?;
+            key.trim()
+                .eq_ignore_ascii_case(name)
+                .then(|| value.trim().to_string())
+        })
+    };
+
+    let body = if header("Transfer-Encoding").is_some_and(|v| v.eq_ignore_ascii_case("chunked")) {
+        decode_chunked(body)?
+    } else if let Some(length) = header("Content-Length").and_then(|v| v.parse::<usize>().ok()) {
+        body[..length.min(body.len())].to_vec()
+    } else {
+        body.to_vec()
+    };
+
+    if !(200..300).contains(&status) {
+        return Err(Error::Transcribe(format!(
+            "HTTP {}: {}",
+            status,
+            String::from_utf8_lossy(&body).trim()
+        )));
+    }
+
+    Ok(body)
+}
+
+fn decode_chunked(mut data: &[u8]) -> Result<Vec<u8>> {
+    let broken = || Error::Transcribe(t!("transcribe.bad_chunked"));
+    let mut body = Vec::new();
+
+    loop {
+        let line_end = data
+            .windows(2)
+            .position(|w| w == b"\r\n")
+            .ok_or_else(broken)?;
+        let size_text = String::from_utf8_lossy(&data[..line_end]);
+        let size = usize::from_str_radix(size_text.split(';').next().unwrap_or("").trim(), 16)
+            .map_err(|_| broken())?;
+        data = &data[line_end + 2..];
+
+        if size == 0 {
+            return Ok(body);
+        }
+        if data.len() < size {
+            return Err(broken());
+        }
+        body.extend_from_slice(&data[..size]);
+        data = data.get(size + 2..).unwrap_or(&[]);
+    }
+}
+
+/// 从 JSON 响应中取出文本：`{"text": ...}`，或 WhisperLiveKit 的 `{"lines": [{"text": ...}]}`。
+fn transcript_text(body: &[u8]) -> Result<String> {
+    let value: serde_json::Value = serde_json::from_slice(body)?;
+
+    if let Some(text) = value.get("text").and_then(|t| t.as_str()) {
+        return Ok(text.trim().to_string());
+    }
+
+    if let Some(lines) = value.get("lines").and_then(|l| l.as_array()) {
+        let texts: Vec<&str> = lines
+            .iter()
+            .filter_map(|l| l.get("text").and_then(|t| t.as_str()))
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .collect();
+        return Ok(texts.join("\n"));
+    }
+
+    Err(Error::Transcribe(t!("transcribe.no_text")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reader::parse_edl;
+
+    #[test]
+    fn endpoint_parsing() {
+        assert_eq!(
+            Endpoint::parse("http://localhost:8000").unwrap(),
+            Endpoint {
+                host: "localhost".to_string(),
+                port: 8000,
+                path: "/v1/audio/transcriptions".to_string(),
+            }
+        );
+        assert_eq!(Endpoint::parse("http://wlk/asr").unwrap().port, 80);
+        assert!(Endpoint::parse("https://localhost:8000").is_err());
+        assert!(Endpoint::parse("http://:8000").is_err());
+    }
+
+    #[test]
+    fn source_file_and_audio_tracks() {
+        let edl = parse_edl(
+            "001 AX A2 C 00:00:00:00 00:00:01:00 00:00:00:00 00:00:01:00\n\
+             * FROM CLIP NAME: VO_012.wav\n\
+             * SOURCE FILE: D:\\VO\\VO_012.wav\n\
+             002 AX V C 00:00:00:00 00:00:01:00 00:00:00:00 00:00:01:00\n",
+        );
+        assert_eq!(source_file(&edl.events[0]), Some("D:\\VO\\VO_012.wav"));
+        assert!(is_audio_event(&edl.events[0]));
+        assert!(!is_audio_event(&edl.events[1]));
+    }
+
+    #[test]
+    fn http_responses() {
+        let ok = b"HTTP/1.1 200 OK\r\nContent-Length: 15\r\n\r\n{\"text\":\"hi\"}\r\nextra";
+        assert_eq!(parse_http_response(ok).unwrap(), b"{\"text\":\"hi\"}\r\n");
+
+        let chunked = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n";
+        assert_eq!(parse_http_response(chunked).unwrap(), b"hello world");
+
+        let err = parse_http_response(b"HTTP/1.1 500 Oops\r\n\r\nboom").unwrap_err();
+        assert!(err.to_string().contains("HTTP 500: boom"));
+    }
+
+    #[test]
+    fn transcript_shapes() {
+        assert_eq!(
+            transcript_text(r#"{"text": " 你好 "}"#.as_bytes()).unwrap(),
+            "你好"
+        );
+        assert_eq!(
+            transcript_text(br#"{"lines": [{"text": "a"}, {"text": ""}, {"text": "b"}]}"#).unwrap(),
+            "a\nb"
+        );
+        assert!(transcript_text(b"{}").is_err());
+    }
+}
@@ -0,0 +1,273 @@
+//! 转换：EDL 事件 -> 字幕条目 -> 毫秒字幕，以及相关的小工具函数。
+
+use serde::Deserialize;
+
+use crate::model::{Cue, Edl, EdlEvent, SubtitleEntry, Timecode};
+use crate::reader::locators;
+use crate::writer::format_srt_timestamp;
+
+/// 从事件列表生成字幕条目。
+///
+/// 事件的 `text` 有值时直接使用；否则使用去掉常见音频扩展名的片段名。
+/// 两者都没有的事件不生成字幕。
+pub fn events_to_entries(events: &[EdlEvent]) -> Vec<SubtitleEntry> {
+    events
+        .iter()
+        .filter_map(|event| {
+            Some(SubtitleEntry {
+                start: event.record_in,
+                end: event.record_out,
+                text: event_text(event)?,
+            })
+        })
+        .collect()
+}
+
+/// 事件对应的字幕文本，规则见 [`events_to_entries`]。
+pub fn event_text(event: &EdlEvent) -> Option<String> {
+    match (&event.text, &event.clip_name) {
+        (Some(text), _) => Some(text.clone()),
+        (None, Some(name)) => Some(strip_audio_extension(name)),
+        (None, None) => None,
+    }
+}
+
+/// 用 Avid 定位点生成字幕条目。
+///
+/// `colors` 非空时只使用这些颜色的定位点（不区分大小写），没有文本的定位点跳过。
+/// 每条字幕从定位点开始，到下一个定位点为止，但不超过所属事件的录制出点。
+pub fn locators_to_entries(edl: &Edl, colors: &[String]) -> Vec<SubtitleEntry> {
+    let mut selected: Vec<_> = locators(edl)
+        .into_iter()
+        .filter(|l| colors.is_empty() || colors.iter().any(|c| c.eq_ignore_ascii_case(&l.color)))
+        .filter(|l| !l.text.is_empty())
+        .collect();
+    selected.sort_by_key(|l| l.timecode);
+
+    selected
+        .iter()
+        .enumerate()
+        .filter_map(|(i, locator)| {
+            let event_end = edl.events[locator.event].record_out;
+            let next = selected[i + 1..]
+                .iter()
+                .map(|l| l.timecode)
+                .find(|&t| t > locator.timecode);
+            let end = match next {
+                Some(next) if event_end > locator.timecode => next.min(event_end),
+                Some(next) => next,
+                None => event_end,
+            };
+
+            (end > locator.timecode).then(|| SubtitleEntry {
+                start: locator.timecode,
+                end,
+                text: locator.text.clone(),
+            })
+        })
+        .collect()
+}
+
+/// 按帧率把字幕条目批量换算为 [`Cue`]。
+pub fn entries_to_cues(entries: &[SubtitleEntry], fps: f64) -> Vec<Cue> {
+    entries.iter().map(|entry| entry.to_cue(fps)).collect()
+}
+
+/// 把字幕整体前移 `origin_ms`。
+///
+/// 用于把录制时间码（例如从 01:00:00:00 开始）换成成片文件内的时间：
+/// 完全早于起点的字幕被丢弃，跨过起点的字幕从 0 开始。
+pub fn rebase_cues(cues: &[Cue], origin_ms: u64) -> Vec<Cue> {
+    cues.iter()
+        .filter(|cue| cue.end_ms > origin_ms)
+        .map(|cue| Cue {
+            start_ms: cue.start_ms.saturating_sub(origin_ms),
+            end_ms: cue.end_ms - origin_ms,
+            text: cue.text.clone(),
+        })
+        .collect()
+}
+
+/// 把字幕整体后移 `origin_ms`，是 [`rebase_cues`] 的逆操作。
+///
+/// 用于把按成片时间导出的字幕换回录制时间码，例如套底时读入的译文字幕。
+pub fn offset_cues(cues: &[Cue], origin_ms: u64) -> Vec<Cue> {
+    cues.iter()
+        .map(|cue| Cue {
+            start_ms: cue.start_ms + origin_ms,
+            end_ms: cue.end_ms + origin_ms,
+            text: cue.text.clone(),
+        })
+        .collect()
+}
+
+/// 字幕文本的清理规则，通常写在配置文件的预设里。
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct CleanupRules {
+    /// 按顺序做的字面替换，例如 `[["_", " "]]`
+    pub replace: Vec<(String, String)>,
+
+    /// 直接删掉的字符串，例如 `["(V)", "_final"]`
+    pub remove: Vec<String>,
+
+    /// 把连续空白压成一个空格，并去掉每行首尾的空白
+    pub collapse_spaces: bool,
+}
+
+impl CleanupRules {
+    pub fn is_empty(&self) -> bool {
+        self.replace.is_empty() && self.remove.is_empty() && !self.collapse_spaces
+    }
+
+    /// 按“删除 -> 替换 -> 压缩空白”的顺序清理一段文本，保留换行。
+    pub fn apply(&self, text: &str) -> String {
+        let mut text = text.to_string();
+        for pattern in self.remove.iter().filter(|p| !p.is_empty()) {
+            text = text.replace(pattern.as_str(), "");
+        }
+        for (from, to) in self.replace.iter().filter(|(from, _)| !from.is_empty()) {
+            text = text.replace(from.as_str(), to);
+        }
+
+        if self.collapse_spaces {
+            text = text
+                .lines()
+                .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+                .collect::<Vec<_>>()
+                .join("\n");
+        }
+        text
+    }
+}
+
+/// 清理每条字幕的文本，清理后为空的字幕被丢弃。
+pub fn clean_cues(cues: &[Cue], rules: &CleanupRules) -> Vec<Cue> {
+    cues.iter()
+        .map(|cue| Cue {
+            start_ms: cue.start_ms,
+            end_ms: cue.end_ms,
+            text: rules.apply(&cue.text),
+        })
+        .filter(|cue| !cue.text.trim().is_empty())
+        .collect()
+}
+
+/// 将 EDL 时间码 (HH:MM:SS:FF) 转换为 SRT 时间码 (HH:MM:SS,mmm)
+///
+/// 无法识别的时间码原样返回。
+pub fn convert_timecode(edl_time: &str, fps: f64) -> String {
+    match Timecode::parse(edl_time) {
+        Some(tc) => format_srt_timestamp(tc.to_millis(fps)),
+        None => edl_time.to_string(),
+    }
+}
+
+/// 去除常见音频扩展名。
+///
+/// 例如：
+/// - hello.wav -> hello
+/// - test.MP3  -> test
+pub fn strip_audio_extension(filename: &str) -> String {
+    let extensions = [
+        ".flac", ".wav", ".mp3", ".aac", ".ogg", ".wma", ".m4a", ".aiff", ".aif",
+    ];
+
+    let lower_name = filename.to_lowercase();
+
+    for ext in &extensions {
+        if lower_name.ends_with(ext) {
+            return filename[..filename.len() - ext.len()].to_string();
+        }
+    }
+
+    filename.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn convert_timecode_matches_srt_format() {
+        assert_eq!(convert_timecode("01:00:00:12", 24.0), "01:00:00,500");
+        assert_eq!(convert_timecode("00:00:59:29", 30.0), "00:00:59,967");
+        assert_eq!(convert_timecode("00:00:01:15", 29.97), "00:00:01,501");
+    }
+
+    #[test]
+    fn convert_timecode_keeps_unknown_text() {
+        assert_eq!(convert_timecode("garbage", 25.0), "garbage");
+    }
+
+    #[test]
+    fn rebase_drops_cues_before_origin() {
+        let cue = |start_ms, end_ms| Cue {
+            start_ms,
+            end_ms,
+            text: String::new(),
+        };
+        let cues = [cue(1_000, 2_000), cue(3_000, 5_000), cue(6_000, 7_000)];
+        assert_eq!(
+            rebase_cues(&cues, 4_000),
+            vec![cue(0, 1_000), cue(2_000, 3_000)]
+        );
+        assert_eq!(
+            offset_cues(&rebase_cues(&cues[1..], 1_000), 1_000),
+            cues[1..]
+        );
+    }
+
+    #[test]
+    fn locators_become_entries() {
+        let edl = crate::reader::parse_edl(
+            "001 AX V C 00:00:00:00 00:00:05:00 01:00:00:00 01:00:05:00\n\
+             * LOC: 01:00:01:00 YELLOW 第一句\n\
+             * LOC: 01:00:02:00 RED 备注\n\
+             * LOC: 01:00:03:00 YELLOW V1 第二句\n",
+        );
+        let tc = |s: u32| Timecode::new(1, 0, s, 0);
+
+        let all = locators_to_entries(&edl, &[]);
+        assert_eq!(all.len(), 3);
+        assert_eq!((all[0].end, all[2].end), (tc(2), tc(5)));
+
+        let yellow = locators_to_entries(&edl, &["yellow".to_string()]);
+        let spans: Vec<_> = yellow
+            .iter()
+            .map(|e| (e.start, e.end, e.text.as_str()))
+            .collect();
+        assert_eq!(spans, [(tc(1), tc(3), "第一句"), (tc(3), tc(5), "第二句")]);
+    }
+
+    #[test]
+    fn cleanup_rules_apply_in_order() {
+        let rules = CleanupRules {
+            replace: vec![("_".to_string(), " ".to_string())],
+            remove: vec!["(V)".to_string()],
+            collapse_spaces: true,
+        };
+        assert_eq!(
+            rules.apply("SC_01 (V)  台词\n 第二行 "),
+            "SC 01 台词\n第二行"
+        );
+
+        let cue = |text: &str| Cue {
+            start_ms: 0,
+            end_ms: 1_000,
+            text: text.to_string(),
+        };
+        assert_eq!(
+            clean_cues(&[cue("(V)"), cue("ok")], &rules),
+            vec![cue("ok")]
+        );
+    }
+
+    #[test]
+    fn strip_audio_extension_is_case_insensitive() {
+        assert_eq!(strip_audio_extension("hello.wav"), "hello");
+        assert_eq!(strip_audio_extension("test.MP3"), "test");
+        assert_eq!(strip_audio_extension("clip.mov"), "clip.mov");
+        assert_eq!(strip_audio_extension("台词.Flac"), "台词");
+    }
+}
@@ -0,0 +1,379 @@
+//! `--edit` 终端界面：写出前预览和修改字幕。
+//!
+//! 编辑逻辑都在库里的 [`CueEditor`]，这里只负责绘制和按键。
+
+use std::io;
+
+use edl2srt::i18n::text;
+use edl2srt::{Cue, CueEditor, CueRow, Edge, QcLimits, t};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Position};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+/// 编辑框中表示换行的字符；字幕里本来的 `|` 和 `\` 在编辑框中写成 `\|`、`\\`
+const LINE_BREAK: char = '|';
+
+/// 编辑框中的转义字符
+const ESCAPE: char = '\\';
+
+enum Mode {
+    /// 浏览和单键操作
+    Browse,
+
+    /// 修改当前字幕的文本，`cursor` 为字符下标
+    Edit { buffer: String, cursor: usize },
+
+    /// 有未写出的修改时按了 q，等待确认
+    ConfirmQuit,
+}
+
+struct App {
+    editor: CueEditor,
+    table: TableState,
+    mode: Mode,
+    status: String,
+    target: String,
+}
+
+/// 打开编辑界面。按 w 返回修改后的字幕，放弃时返回 None。
+///
+/// `target` 只用于标题栏，例如 “srt → output.srt”。
+pub fn edit_cues(
+    cues: Vec<Cue>,
+    fps: f64,
+    limits: QcLimits,
+    target: String,
+) -> io::Result<Option<Vec<Cue>>> {
+    let mut app = App {
+        editor: CueEditor::new(cues, fps, limits),
+        table: TableState::default().with_selected(Some(0)),
+        mode: Mode::Browse,
+        status: String::new(),
+        target,
+    };
+
+    let mut terminal = ratatui::try_init()?;
+    let result = app.run(&mut terminal);
+    ratatui::restore();
+
+    Ok(result?.then(|| app.editor.into_cues()))
+}
+
+impl App {
+    /// 事件循环，返回 true 表示写出
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<bool> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            // Windows 上松开按键也会产生事件
+            if key.kind == KeyEventKind::Release {
+                continue;
+            }
+
+            self.status.clear();
+            let done = match self.mode {
+                Mode::Browse => self.browse_key(key),
+                Mode::Edit { .. } => {
+                    self.edit_key(key);
+                    None
+                }
+                Mode::ConfirmQuit => {
+                    if matches!(key.code, KeyCode::Char('q')) {
+                        Some(false)
+                    } else {
+                        self.mode = Mode::Browse;
+                        None
+                    }
+                }
+            };
+            if let Some(write) = done {
+                return Ok(write);
+            }
+        }
+    }
+
+    fn selected(&self) -> Option<usize> {
+        self.table
+            .selected()
+            .filter(|&index| index < self.editor.len())
+    }
+
+    fn browse_key(&mut self, key: KeyEvent) -> Option<bool> {
+        let selected = self.selected();
+        match key.code {
+            KeyCode::Up | KeyCode::Char('k') => self.table.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') => self.table.select_next(),
+            KeyCode::PageUp => self.table.scroll_up_by(10),
+            KeyCode::PageDown => self.table.scroll_down_by(10),
+            KeyCode::Home | KeyCode::Char('g') => self.table.select_first(),
+            KeyCode::End | KeyCode::Char('G') => self.table.select_last(),
+            KeyCode::Enter | KeyCode::Char('e') => {
+                if let Some(index) = selected {
+                    let buffer = to_edit_buffer(&self.editor.cues()[index].text);
+                    let cursor = buffer.chars().count();
+                    self.mode = Mode::Edit { buffer, cursor };
+                }
+            }
+            KeyCode::Char('d') | KeyCode::Delete => {
+                if let Some(index) = selected {
+                    self.editor.delete(index);
+                    self.status = t!("tui.deleted", index + 1);
+                }
+            }
+            KeyCode::Char('m') => {
+                if let Some(index) = selected {
+                    self.status = if self.editor.merge_with_next(index) {
+                        t!("tui.merged", index + 1, index + 2)
+                    } else {
+                        t!("tui.nothing_to_merge")
+                    };
+                }
+            }
+            KeyCode::Char(c @ ('[' | ']' | '{' | '}' | '<' | '>' | ',' | '.')) => {
+                if let Some(index) = selected {
+                    let (edge, frames) = match c {
+                        '[' => (Edge::Start, -1),
+                        ']' => (Edge::Start, 1),
+                        '{' => (Edge::End, -1),
+                        '}' => (Edge::End, 1),
+                        '<' | ',' => (Edge::Both, -1),
+                        _ => (Edge::Both, 1),
+                    };
+                    if !self.editor.nudge(index, edge, frames) {
+                        self.status = t!("tui.at_boundary");
+                    }
+                }
+            }
+            KeyCode::Char('u') => {
+                self.status = if self.editor.undo() {
+                    t!("tui.undone")
+                } else {
+                    t!("tui.nothing_to_undo")
+                };
+            }
+            KeyCode::Char('w') => return Some(true),
+            KeyCode::Char('q') | KeyCode::Esc => {
+                if !self.editor.is_modified() {
+                    return Some(false);
+                }
+                self.mode = Mode::ConfirmQuit;
+            }
+            _ => {}
+        }
+
+        // 删除、合并后选中行可能越界
+        if self.editor.is_empty() {
+            self.table.select(None);
+        } else if self.table.selected().is_none_or(|i| i >= self.editor.len()) {
+            self.table.select(Some(self.editor.len() - 1));
+        }
+        None
+    }
+
+    fn edit_key(&mut self, key: KeyEvent) {
+        let Mode::Edit { buffer, cursor } = &mut self.mode else {
+            return;
+        };
+        let byte_index = |buffer: &str, cursor: usize| {
+            buffer
+                .char_indices()
+                .nth(cursor)
+                .map_or(buffer.len(), |(i, _)| i)
+        };
+
+        match key.code {
+            KeyCode::Enter => {
+                let text = from_edit_buffer(buffer);
+                if let Some(index) = self.selected() {
+                    self.editor.set_text(index, &text);
+                }
+                self.mode = Mode::Browse;
+            }
+            KeyCode::Esc => self.mode = Mode::Browse,
+            KeyCode::Char(c) => {
+                buffer.insert(byte_index(buffer, *cursor), c);
+                *cursor += 1;
+            }
+            KeyCode::Backspace if *cursor > 0 => {
+                *cursor -= 1;
+                buffer.remove(byte_index(buffer, *cursor));
+            }
+            KeyCode::Delete if *cursor < buffer.chars().count() => {
+                buffer.remove(byte_index(buffer, *cursor));
+            }
+            KeyCode::Left => *cursor = cursor.saturating_sub(1),
+            KeyCode::Right => *cursor = (*cursor + 1).min(buffer.chars().count()),
+            KeyCode::Home => *cursor = 0,
+            KeyCode::End => *cursor = buffer.chars().count(),
+            _ => {}
+        }
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [table_area, detail_area, footer_area] = Layout::vertical([
+            Constraint::Min(5),
+            Constraint::Length(6),
+            Constraint::Length(2),
+        ])
+        .areas(frame.area());
+
+        let rows = self.editor.rows();
+        let modified = if self.editor.is_modified() { " *" } else { "" };
+        let title = t!("tui.title", rows.len(), self.target, modified);
+        let table = Table::new(
+            rows.iter().map(table_row),
+            [
+                Constraint::Length(5),
+                Constraint::Length(11),
+                Constraint::Length(11),
+                Constraint::Length(8),
+                Constraint::Length(6),
+                Constraint::Fill(3),
+                Constraint::Fill(2),
+            ],
+        )
+        .header(
+            Row::new([
+                "#",
+                text("tui.column_in"),
+                text("tui.column_out"),
+                text("tui.column_duration"),
+                "CPS",
+                text("tui.column_text"),
+                text("tui.column_warnings"),
+            ])
+            .style(Style::new().add_modifier(Modifier::BOLD)),
+        )
+        .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+        .block(Block::new().borders(Borders::ALL).title(title));
+        frame.render_stateful_widget(table, table_area, &mut self.table);
+
+        // 选中行的完整文本和警告
+        let detail: Vec<Line> = match self.selected().map(|index| &rows[index]) {
+            Some(row) => row
+                .text
+                .lines()
+                .map(Line::from)
+                .chain(
+                    row.warnings
+                        .iter()
+                        .map(|w| Line::styled(t!("tui.warning", w), Color::Yellow)),
+                )
+                .collect(),
+            None => vec![Line::from(text("tui.no_cues"))],
+        };
+        frame.render_widget(
+            Paragraph::new(detail).wrap(Wrap { trim: false }).block(
+                Block::new()
+                    .borders(Borders::ALL)
+                    .title(text("tui.current")),
+            ),
+            detail_area,
+        );
+
+        let footer = match &self.mode {
+            Mode::Browse => vec![
+                Line::from(self.status.as_str()),
+                Line::from(text("tui.help")),
+            ],
+            Mode::Edit { buffer, cursor } => {
+                let prefix = t!("tui.edit_prefix");
+                let before: String = buffer.chars().take(*cursor).collect();
+                let x = Line::from(format!("{}{}", prefix, before)).width() as u16;
+                frame.set_cursor_position(Position::new(
+                    footer_area.x + x.min(footer_area.width.saturating_sub(1)),
+                    footer_area.y,
+                ));
+                vec![
+                    Line::from(format!("{}{}", prefix, buffer)),
+                    Line::from(t!("tui.edit_help", LINE_BREAK)),
+                ]
+            }
+            Mode::ConfirmQuit => vec![
+                Line::styled(text("tui.confirm_quit"), Color::Yellow),
+                Line::from(text("tui.help")),
+            ],
+        };
+        frame.render_widget(Paragraph::new(footer), footer_area);
+    }
+}
+
+/// 字幕文本写成单行编辑框的内容：换行写成 `|`，本来的 `|` 和 `\` 前加 `\`。
+fn to_edit_buffer(text: &str) -> String {
+    let mut buffer = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\n' => buffer.push(LINE_BREAK),
+            LINE_BREAK | ESCAPE => {
+                buffer.push(ESCAPE);
+                buffer.push(c);
+            }
+            _ => buffer.push(c),
+        }
+    }
+    buffer
+}
+
+/// 编辑框的内容还原为字幕文本，是 [`to_edit_buffer`] 的逆运算；各行去掉首尾空白，空行不要。
+fn from_edit_buffer(buffer: &str) -> String {
+    let mut lines = vec![String::new()];
+    let mut chars = buffer.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            // 末尾落单的 `\` 按原样保留
+            ESCAPE => lines
+                .last_mut()
+                .expect("至少有一行")
+                .push(chars.next().unwrap_or(ESCAPE)),
+            LINE_BREAK => lines.push(String::new()),
+            _ => lines.last_mut().expect("至少有一行").push(c),
+        }
+    }
+
+    lines
+        .iter()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn table_row(row: &CueRow) -> Row<'_> {
+    let style = if row.warnings.is_empty() {
+        Style::new()
+    } else {
+        Style::new().fg(Color::Yellow)
+    };
+    Row::new([
+        Cell::from(row.index.to_string()),
+        Cell::from(row.start.to_string()),
+        Cell::from(row.end.to_string()),
+        Cell::from(format!("{:.2}s", row.duration_ms as f64 / 1000.0)),
+        Cell::from(row.cps.map_or("-".to_string(), |cps| format!("{:.1}", cps))),
+        Cell::from(row.text.replace('\n', " | ")),
+        Cell::from(row.warnings.join(text("tui.warning_separator"))),
+    ])
+    .style(style)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn edit_buffer_keeps_literal_bars() {
+        let text = "A | B\nC:\\D";
+        let buffer = to_edit_buffer(text);
+        assert_eq!(buffer, "A \\| B|C:\\\\D");
+        assert_eq!(from_edit_buffer(&buffer), text);
+
+        assert_eq!(from_edit_buffer(" 第一行 | | 第二行 "), "第一行\n第二行");
+        assert_eq!(from_edit_buffer("结尾\\"), "结尾\\");
+    }
+}
@@ -0,0 +1,332 @@
+//! 网页版入口：编译成 wasm32 后由仓库根目录的 `edl2srt.html` 调用。
+//!
+//! 网页与命令行使用同一套解码、解析和写出代码，全部在浏览器中运行。
+//! 为了不引入 wasm-bindgen，这里只导出几个 C ABI 函数，请求和结果都是 JSON：
+//!
+//! 1. `edl2srt_alloc` 在 wasm 内存中分配缓冲区，JS 写入文件字节和请求 JSON
+//! 2. `edl2srt_convert` / `edl2srt_formats` 把结果 JSON 放进结果缓冲区
+//! 3. JS 通过 `edl2srt_result_ptr` / `edl2srt_result_len` 读出结果，
+//!    再用 `edl2srt_free` 释放自己分配的缓冲区
+//!
+//! 构建：
+//!
+//! ```text
+//! cargo rustc --lib --release --target wasm32-unknown-unknown --crate-type cdylib
+//! ```
+
+use std::cell::RefCell;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::format::{FormatRegistry, WriteOptions};
+use crate::fps::{check_frames_against_fps, infer_fps, is_valid_fps, uses_drop_frame};
+use crate::reader::decode_edl_bytes;
+use crate::t;
+use crate::transform::{entries_to_cues, events_to_entries};
+use crate::writer::format_srt_timestamp;
+
+/// 网页端不提供的格式：二进制的 STL 要写当天日期，wasm32-unknown-unknown 上没有系统时钟。
+const UNSUPPORTED_ON_WEB: &[&str] = &["stl"];
+
+/// 网页发来的转换请求。
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct WebRequest {
+    /// 原始文件名，用于按扩展名选择输入格式
+    pub file_name: Option<String>,
+
+    /// 帧率，None 表示从内容推断
+    pub fps: Option<f64>,
+
+    /// 输出格式名，None 表示 SRT
+    pub format: Option<String>,
+
+    /// 输入编码，None 表示自动检测
+    pub encoding: Option<String>,
+}
+
+/// 预览表中的一行。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WebCue {
+    pub index: usize,
+    pub start: String,
+    pub end: String,
+    pub duration_ms: u64,
+    pub text: String,
+}
+
+/// 转换结果。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WebResult {
+    /// 实际使用的编码
+    pub encoding: String,
+
+    /// 实际使用的帧率
+    pub fps: f64,
+
+    /// 帧率是否为推断得到
+    pub fps_inferred: bool,
+
+    pub cues: Vec<WebCue>,
+    pub warnings: Vec<String>,
+
+    /// 写出的字幕文本
+    pub output: String,
+
+    /// 下载文件的扩展名
+    pub extension: String,
+}
+
+/// 网页端可选的输出格式。
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct WebFormat {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub extension: &'static str,
+}
+
+/// 列出网页端可选的输出格式。
+pub fn web_formats(registry: &FormatRegistry) -> Vec<WebFormat> {
+    registry
+        .subtitle_writers()
+        .filter(|w| !UNSUPPORTED_ON_WEB.contains(&w.name()))
+        .map(|w| WebFormat {
+            name: w.name(),
+            description: w.description(),
+            extension: w.extensions().first().copied().unwrap_or("txt"),
+        })
+        .collect()
+}
+
+/// 与命令行相同的流程：解码、选择读取器、确定帧率、生成字幕并写出。
+pub fn convert_for_web(
+    registry: &FormatRegistry,
+    bytes: &[u8],
+    request: &WebRequest,
+) -> Result<WebResult> {
+    let format = request.format.as_deref().unwrap_or("srt");
+    if UNSUPPORTED_ON_WEB.contains(&format) {
+        return Err(Error::UnknownFormat(t!("web.unsupported_format", format)));
+    }
+    let writer = registry.subtitle_writer(format)?;
+
+    let decoded = decode_edl_bytes(bytes, request.encoding.as_deref())?;
+    let mut warnings = Vec::new();
+    if decoded.had_errors {
+        warnings.push(t!("web.decode_errors", decoded.encoding_name));
+    }
+
+    let path = request.file_name.as_deref().map(Path::new);
+    let reader = match registry.detect_timeline_reader(path, &decoded.content) {
+        Some(reader) => reader,
+        None => registry.timeline_reader("edl")?,
+    };
+    let edl = reader.parse(&decoded.content)?;
+    warnings.extend(edl.warnings.iter().map(ToString::to_string));
+
+    let (fps, fps_inferred) = match request.fps {
+        Some(fps) if is_valid_fps(fps) => {
+            warnings.extend(
+                check_frames_against_fps(&edl, fps)
+                    .iter()
+                    .map(ToString::to_string),
+            );
+            (fps, false)
+        }
+        Some(fps) => return Err(Error::Invalid(t!("web.bad_fps", fps))),
+        None => (infer_fps(&edl).resolve()?, true),
+    };
+
+    let cues = entries_to_cues(&events_to_entries(&edl.events), fps);
+    let options = WriteOptions {
+        fps,
+        title: edl.title.clone(),
+        drop_frame: uses_drop_frame(&edl),
+    };
+    warnings.extend(writer.warnings(&cues, &options));
+
+    let mut output = Vec::new();
+    writer.write(&mut output, &cues, &options)?;
+
+    Ok(WebResult {
+        encoding: decoded.encoding_name,
+        fps,
+        fps_inferred,
+        cues: cues
+            .iter()
+            .enumerate()
+            .map(|(i, cue)| WebCue {
+                index: i + 1,
+                start: format_srt_timestamp(cue.start_ms),
+                end: format_srt_timestamp(cue.end_ms),
+                duration_ms: cue.end_ms.saturating_sub(cue.start_ms),
+                text: cue.text.clone(),
+            })
+            .collect(),
+        warnings,
+        output: String::from_utf8_lossy(&output).into_owned(),
+        extension: writer
+            .extensions()
+            .first()
+            .copied()
+            .unwrap_or("txt")
+            .to_string(),
+    })
+}
+
+/// 转换结果的 JSON：成功时为 `{"ok": true, ...}`，失败时为 `{"ok": false, "error": "..."}`。
+pub fn convert_to_json(registry: &FormatRegistry, bytes: &[u8], request_json: &[u8]) -> String {
+    #[derive(Serialize)]
+    struct Response {
+        ok: bool,
+        #[serde(flatten)]
+        result: Option<WebResult>,
+        error: Option<String>,
+    }
+
+    let result = serde_json::from_slice::<WebRequest>(request_json)
+        .map_err(Error::from)
+        .and_then(|request| convert_for_web(registry, bytes, &request));
+    let response = match result {
+        Ok(result) => Response {
+            ok: true,
+            result: Some(result),
+            error: None,
+        },
+        Err(err) => Response {
+            ok: false,
+            result: None,
+            error: Some(err.to_string()),
+        },
+    };
+    serde_json::to_string(&response).expect("结果总能序列化")
+}
+
+thread_local! {
+    /// 最近一次调用的结果，供 JS 读取
+    static RESULT: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+}
+
+fn set_result(json: String) {
+    RESULT.with(|result| *result.borrow_mut() = json.into_bytes());
+}
+
+/// 分配 `len` 字节，供 JS 写入输入数据。
+#[unsafe(no_mangle)]
+pub extern "C" fn edl2srt_alloc(len: usize) -> *mut u8 {
+    let mut buffer = Vec::<u8>::with_capacity(len);
+    let ptr = buffer.as_mut_ptr();
+    std::mem::forget(buffer);
+    ptr
+}
+
+/// 释放 [`edl2srt_alloc`] 分配的缓冲区。
+///
+/// # Safety
+///
+/// `ptr` 和 `len` 必须来自同一次 [`edl2srt_alloc`] 调用，且只释放一次。
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn edl2srt_free(ptr: *mut u8, len: usize) {
+    if !ptr.is_null() {
+        // SAFETY: 由调用方保证 ptr / len 来自 edl2srt_alloc
+        drop(unsafe { Vec::from_raw_parts(ptr, 0, len) });
+    }
+}
+
+/// 转换一个文件，结果 JSON 见 [`convert_to_json`]。
+///
+/// # Safety
+///
+/// 两段内存必须是 JS 已写满的有效缓冲区。
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn edl2srt_convert(
+    input_ptr: *const u8,
+    input_len: usize,
+    request_ptr: *const u8,
+    request_len: usize,
+) {
+    // SAFETY: 由调用方保证两段内存有效
+    let (input, request) = unsafe {
+        (
+            std::slice::from_raw_parts(input_ptr, input_len),
+            std::slice::from_raw_parts(request_ptr, request_len),
+        )
+    };
+    set_result(convert_to_json(&FormatRegistry::builtin(), input, request));
+}
+
+/// 把可选的输出格式列表（JSON 数组）放进结果缓冲区。
+#[unsafe(no_mangle)]
+pub extern "C" fn edl2srt_formats() {
+    let formats = web_formats(&FormatRegistry::builtin());
+    set_result(serde_json::to_string(&formats).expect("格式列表总能序列化"));
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn edl2srt_result_ptr() -> *const u8 {
+    RESULT.with(|result| result.borrow().as_ptr())
+}
+
+#[unsafe(no_mangle)]
+pub extern "C" fn edl2srt_result_len() -> usize {
+    RESULT.with(|result| result.borrow().len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EDL: &str = "TITLE: 网页测试\n\
+                       001  AX  A  C  00:00:00:00 00:00:01:00 01:00:00:00 01:00:01:12\n\
+                       * FROM CLIP NAME: 你好.wav\n";
+
+    #[test]
+    fn converts_like_the_cli() {
+        let registry = FormatRegistry::builtin();
+        let json = convert_to_json(
+            &registry,
+            EDL.as_bytes(),
+            br#"{"fps": 25, "format": "vtt"}"#,
+        );
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(value["ok"], true);
+        assert_eq!(value["encoding"], "UTF-8");
+        assert_eq!(value["extension"], "vtt");
+        assert_eq!(value["cues"][0]["end"], "01:00:01,480");
+        assert_eq!(value["cues"][0]["duration_ms"], 1480);
+        assert!(value["output"].as_str().unwrap().starts_with("WEBVTT"));
+    }
+
+    #[test]
+    fn errors_are_reported_in_json() {
+        let registry = FormatRegistry::builtin();
+        let json = convert_to_json(&registry, EDL.as_bytes(), br#"{"format": "stl"}"#);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["ok"], false);
+        assert!(value["error"].as_str().unwrap().contains("网页版不支持"));
+
+        assert!(
+            !web_formats(&registry)
+                .iter()
+                .any(|format| format.name == "stl")
+        );
+    }
+
+    #[test]
+    fn abi_round_trip() {
+        let request = br#"{"fps": 25}"#;
+        unsafe {
+            let input = edl2srt_alloc(EDL.len());
+            std::ptr::copy_nonoverlapping(EDL.as_ptr(), input, EDL.len());
+            edl2srt_convert(input, EDL.len(), request.as_ptr(), request.len());
+            edl2srt_free(input, EDL.len());
+
+            let result =
+                std::slice::from_raw_parts(edl2srt_result_ptr(), edl2srt_result_len()).to_vec();
+            assert!(String::from_utf8(result).unwrap().contains("\"ok\":true"));
+        }
+    }
+}
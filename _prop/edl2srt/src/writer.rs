@@ -0,0 +1,65 @@
+//! 写出字幕，以及输出路径相关的工具函数。
+//!
+//! 具体格式的写出器位于 [`crate::format`]，这里保留最常用的 SRT 入口。
+
+use chrono::Local;
+use std::path::{Path, PathBuf};
+
+pub use crate::format::srt::{format_srt_timestamp, write_srt};
+
+/// 根据原始输出路径，决定最终真正写入的输出路径。
+///
+/// 规则：
+/// - 如果文件不存在，直接使用原路径
+/// - 如果文件已存在，则自动追加时间戳
+///
+/// 例如：
+///   tomoni.srt
+/// 会变成：
+///   tomoni_20260711_194900.srt
+pub fn resolve_output_path(path: &Path) -> PathBuf {
+    if !path.exists() {
+        return path.to_path_buf();
+    }
+
+    // parent() 取父目录
+    // 如果拿不到，就退回当前目录 "."
+    let parent = path.parent().unwrap_or_else(|| Path::new("."));
+
+    // file_stem() 取“不带扩展名”的文件名
+    // 例如 "tomoni.srt" -> "tomoni"
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("output");
+
+    // extension() 取扩展名
+    // 例如 "tomoni.srt" -> "srt"
+    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+    // 用 chrono 生成当前本地时间
+    // format("%Y%m%d_%H%M%S") 例如：
+    // 20260711_194900
+    let timestamp = Local::now().format("%Y%m%d_%H%M%S").to_string();
+
+    // 如果原文件有扩展名，就保留扩展名
+    // 没有扩展名也能工作
+    let new_file_name = if ext.is_empty() {
+        format!("{}_{}", stem, timestamp)
+    } else {
+        format!("{}_{}.{}", stem, timestamp, ext)
+    };
+
+    parent.join(new_file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_output_path_keeps_missing_path() {
+        let path = std::env::temp_dir().join("edl2srt_definitely_missing_4f1c.srt");
+        assert_eq!(resolve_output_path(&path), path);
+    }
+}
@@ -0,0 +1,107 @@
+//! 通过公开 API 走一遍完整的 读取 -> 解析 -> 换算 -> 写出 流程。
+
+use edl2srt::{
+    Cue, Timecode, decode_edl_bytes, entries_to_cues, parse_edl, parse_edl_to_entries, read_edl,
+    resolve_output_path, write_srt,
+};
+use std::fs;
+use std::io::Cursor;
+
+const PREMIERE_EDL: &str = "TITLE: Sequence 01
+FCM: NON-DROP FRAME
+
+001  AX       AA    C        00:00:00:00 00:00:01:12 00:00:00:00 00:00:01:12
+* FROM CLIP NAME: 第一句.wav
+
+002  AX       AA    C        00:00:00:00 00:00:02:00 00:00:02:00 00:00:04:00
+* FROM CLIP NAME: second line.MP3
+";
+
+#[test]
+fn read_parse_write_round_trip() {
+    let decoded = read_edl(Cursor::new(PREMIERE_EDL.as_bytes()), None).unwrap();
+    assert_eq!(decoded.encoding_name, "UTF-8");
+
+    let entries = parse_edl_to_entries(&decoded.content);
+    let cues = entries_to_cues(&entries, 24.0);
+
+    let mut out = Vec::new();
+    write_srt(&mut out, &cues).unwrap();
+
+    assert_eq!(
+        String::from_utf8(out).unwrap(),
+        "1\n00:00:00,000 --> 00:00:01,500\n第一句\n\n\
+         2\n00:00:02,000 --> 00:00:04,000\nsecond line\n\n"
+    );
+}
+
+#[test]
+fn utf16_edl_with_bom_decodes() {
+    let mut bytes = vec![0xFF, 0xFE];
+    for unit in PREMIERE_EDL.encode_utf16() {
+        bytes.extend_from_slice(&unit.to_le_bytes());
+    }
+
+    let decoded = decode_edl_bytes(&bytes, None).unwrap();
+    assert_eq!(decoded.encoding_name, "UTF-16LE (BOM)");
+
+    let edl = parse_edl(&decoded.content);
+    assert_eq!(edl.title.as_deref(), Some("Sequence 01"));
+    assert_eq!(edl.events.len(), 2);
+    assert_eq!(edl.events[1].record_in, Timecode::new(0, 0, 2, 0));
+}
+
+#[test]
+fn write_srt_accepts_any_writer() {
+    let cues = [Cue {
+        start_ms: 61_001,
+        end_ms: 62_000,
+        text: "x".to_string(),
+    }];
+
+    let mut out = Cursor::new(Vec::new());
+    write_srt(&mut out, &cues).unwrap();
+    assert!(
+        out.into_inner()
+            .starts_with(b"1\n00:01:01,001 --> 00:01:02,000\n")
+    );
+}
+
+#[test]
+fn decode_respects_preferred_encoding() {
+    let bytes = "* FROM CLIP NAME: 台词".as_bytes();
+    let decoded = decode_edl_bytes(bytes, Some("utf-8")).unwrap();
+    assert_eq!(decoded.content, "* FROM CLIP NAME: 台词");
+    assert!(!decoded.had_errors);
+
+    // 指定错了编码也照样解码，但会标记出错
+    let gbk = decode_edl_bytes(&[0xFF, 0xFF, 0xFF], Some("gbk")).unwrap();
+    assert!(gbk.had_errors);
+
+    assert!(decode_edl_bytes(bytes, Some("no-such-encoding")).is_err());
+}
+
+#[test]
+fn resolve_output_path_keeps_new_paths_and_renames_existing() {
+    let dir = std::env::temp_dir().join(format!("edl2srt-api-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+
+    let fresh = dir.join("fresh.srt");
+    assert_eq!(resolve_output_path(&fresh), fresh);
+
+    let existing = dir.join("tomoni.srt");
+    fs::write(&existing, "").unwrap();
+    let renamed = resolve_output_path(&existing);
+    assert_eq!(renamed.parent(), Some(dir.as_path()));
+    assert_eq!(renamed.extension().unwrap(), "srt");
+    let stem = renamed.file_stem().unwrap().to_str().unwrap();
+    // tomoni_20260711_194900
+    assert!(stem.starts_with("tomoni_"), "{}", stem);
+    assert_eq!(stem.len(), "tomoni_20260711_194900".len());
+
+    let no_extension = dir.join("plain");
+    fs::write(&no_extension, "").unwrap();
+    assert!(resolve_output_path(&no_extension).extension().is_none());
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,112 @@
+//! 命令行测试：直接运行编译出的 `edl2srt`，检查标准输出、标准错误和退出码。
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+fn fixture_path(file_name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(file_name)
+}
+
+/// 每个测试一个临时目录，避免并行运行时互相覆盖输出文件
+fn temp_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("edl2srt-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_edl2srt"))
+        .args(args)
+        .env("LANG", "zh_CN.UTF-8")
+        .env_remove("LC_ALL")
+        .env_remove("LC_MESSAGES")
+        .output()
+        .expect("运行 edl2srt")
+}
+
+#[test]
+fn dump_json_to_stdout_is_pure_json() {
+    let dir = temp_dir("dump");
+    let input = fixture_path("resolve_24.edl");
+    let output = dir.join("out.srt");
+
+    let result = run(&[
+        "24",
+        input.to_str().unwrap(),
+        output.to_str().unwrap(),
+        "--dump-json",
+        "-",
+    ]);
+    assert!(result.status.success());
+
+    // 标准输出只能有一个 JSON 文档，状态信息都在标准错误
+    let stdout = String::from_utf8(result.stdout).unwrap();
+    let dump: serde_json::Value = serde_json::from_str(&stdout).expect("标准输出应为 JSON");
+    assert_eq!(dump["events"].as_array().unwrap().len(), 4);
+    assert!(String::from_utf8_lossy(&result.stderr).contains("转换成功"));
+    assert!(output.exists());
+}
+
+#[test]
+fn conform_accepts_subtitles_exported_with_origin() {
+    let dir = temp_dir("conform");
+    let edl = fixture_path("resolve_24.edl");
+    let edl = edl.to_str().unwrap();
+    let translated = dir.join("v3_en.srt");
+    let translated = translated.to_str().unwrap();
+    let origin = "01:00:00:00";
+
+    // 按成片时间导出“译文”，再用同一个 --origin 套底，结果应与原样一致
+    let result = run(&["24", edl, translated, "--origin", origin]);
+    assert!(result.status.success());
+    let output = dir.join("out.srt");
+    let output = output.to_str().unwrap();
+    let result = run(&[
+        "24",
+        edl,
+        output,
+        "--conform",
+        edl,
+        translated,
+        "--origin",
+        origin,
+    ]);
+    assert!(result.status.success());
+    assert_eq!(
+        fs::read_to_string(output).unwrap(),
+        fs::read_to_string(translated).unwrap()
+    );
+
+    // 漏了 --origin 时一条都对不上，报错而不是写出空文件
+    let missing = dir.join("missing.srt");
+    let result = run(&[
+        "24",
+        edl,
+        missing.to_str().unwrap(),
+        "--conform",
+        edl,
+        translated,
+    ]);
+    assert_eq!(result.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&result.stderr).contains("没有一条能与旧 EDL 对应"));
+    assert!(!missing.exists());
+}
+
+#[test]
+fn fps_with_a_single_path_is_a_usage_error() {
+    let dir = temp_dir("fps-only");
+    let input = dir.join("in.edl");
+    fs::copy(fixture_path("resolve_24.edl"), &input).unwrap();
+
+    // 忘了输出文件时不能把输入当成输出写出
+    let result = run(&["25", input.to_str().unwrap()]);
+    assert_eq!(result.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&result.stderr).contains("'25' 被当作帧率"));
+    let files: Vec<_> = fs::read_dir(&dir).unwrap().collect();
+    assert_eq!(files.len(), 1);
+}
@@ -0,0 +1,111 @@
+"""生成渲染测试用的字体 blocks.ttf：python3 blocks_font.py > blocks.ttf
+
+只有 ASCII 可见字符，每个字都是同样大小的实心方块（字宽 600、方块 500x700，
+em 为 1000，ascent 800、descent -200），空格为空白。字形简单、度量固定，
+测试可以按字号算出文字应落在画面的哪个区域。
+"""
+
+import struct
+import sys
+
+UNITS_PER_EM = 1000
+ASCENT, DESCENT = 800, -200
+ADVANCE = 600
+BLOCK = (50, 0, 550, 700)  # xMin, yMin, xMax, yMax
+FIRST, LAST = 0x20, 0x7E
+
+# 字形 0 为 .notdef，1 为空格，其余按字符顺序
+NUM_GLYPHS = 1 + (LAST - FIRST + 1)
+
+
+def block_glyph():
+    x0, y0, x1, y1 = BLOCK
+    points = [(x0, y0), (x0, y1), (x1, y1), (x1, y0)]  # 顺时针
+    data = struct.pack(">hhhhh", 1, x0, y0, x1, y1)
+    data += struct.pack(">HH", len(points) - 1, 0)
+    data += bytes([0x01] * len(points))
+    xs, ys, last = [], [], (0, 0)
+    for x, y in points:
+        xs.append(x - last[0])
+        ys.append(y - last[1])
+        last = (x, y)
+    data += struct.pack(">%dh" % len(xs), *xs)
+    data += struct.pack(">%dh" % len(ys), *ys)
+    return data + b"\0" * (len(data) % 4 and 4 - len(data) % 4)
+
+
+def tables():
+    glyph = block_glyph()
+    glyf, loca = b"", [0]
+    for index in range(NUM_GLYPHS):
+        if index >= 2:
+            glyf += glyph
+        loca.append(len(glyf))
+
+    x0, y0, x1, y1 = BLOCK
+    head = struct.pack(
+        ">IIIIHHqqhhhhHHhhh",
+        0x00010000, 0x00010000, 0, 0x5F0F3CF5, 0x000B, UNITS_PER_EM,
+        0, 0, x0, y0, x1, y1, 0, 8, 2, 1, 0,
+    )
+    hhea = struct.pack(
+        ">IhhhHhhhhhhhhhhhH",
+        0x00010000, ASCENT, DESCENT, 0, ADVANCE, 0, 0, x1, 1, 0, 0,
+        0, 0, 0, 0, 0, NUM_GLYPHS,
+    )
+    maxp = struct.pack(">IHHHHHHHHHHHHHH", 0x00010000, NUM_GLYPHS, 4, 1, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 0)
+    hmtx = b"".join(
+        struct.pack(">Hh", ADVANCE, x0 if index >= 2 else 0) for index in range(NUM_GLYPHS)
+    )
+
+    # cmap 格式 4：0x20..0x7E 映射到字形 1..，再加结尾的 0xFFFF 段
+    delta = (1 - FIRST) & 0xFFFF
+    segments = struct.pack(">HH", LAST, 0xFFFF) + b"\0\0"
+    segments += struct.pack(">HH", FIRST, 0xFFFF)
+    segments += struct.pack(">HH", delta, 1) + struct.pack(">HH", 0, 0)
+    subtable = struct.pack(">HHHHHHH", 4, 14 + len(segments), 0, 4, 4, 1, 0) + segments
+    cmap = struct.pack(">HHHHI", 0, 1, 3, 1, 12) + subtable
+
+    family = "edl2srt blocks".encode("utf-16-be")
+    name = struct.pack(">HHH", 0, 1, 6 + 12) + struct.pack(">HHHHHH", 3, 1, 0x409, 1, len(family), 0) + family
+    post = struct.pack(">IIhhIIIII", 0x00030000, 0, -100, 50, 1, 0, 0, 0, 0)
+
+    return {
+        b"cmap": cmap,
+        b"glyf": glyf,
+        b"head": head,
+        b"hhea": hhea,
+        b"hmtx": hmtx,
+        b"loca": struct.pack(">%dI" % len(loca), *loca),
+        b"maxp": maxp,
+        b"name": name,
+        b"post": post,
+    }
+
+
+def checksum(data):
+    data += b"\0" * (-len(data) % 4)
+    return sum(struct.unpack(">%dI" % (len(data) // 4), data)) & 0xFFFFFFFF
+
+
+def build():
+    items = sorted(tables().items())
+    count = len(items)
+    power = 1 << (count.bit_length() - 1)
+    font = struct.pack(">IHHHH", 0x00010000, count, power * 16, power.bit_length() - 1, count * 16 - power * 16)
+
+    offset = 12 + 16 * count
+    directory, body = b"", b""
+    for tag, data in items:
+        directory += struct.pack(">4sIII", tag, checksum(data), offset + len(body), len(data))
+        body += data + b"\0" * (-len(data) % 4)
+    font += directory + body
+
+    # head.checkSumAdjustment 在 head 表偏移 8 处
+    head_offset = offset + sum(len(d) + (-len(d) % 4) for t, d in items if t < b"head")
+    adjustment = (0xB1B0AFBA - checksum(font)) & 0xFFFFFFFF
+    return font[: head_offset + 8] + struct.pack(">I", adjustment) + font[head_offset + 12 :]
+
+
+if __name__ == "__main__":
+    sys.stdout.buffer.write(build())
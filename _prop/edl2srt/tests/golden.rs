@@ -0,0 +1,127 @@
+//! 金样测试：`tests/fixtures/` 下每个 EDL 走完整流程后写出的 SRT，
+//! 必须与同名 `.srt` 文件逐字节一致。
+//!
+//! 夹具是按 Premiere、Resolve、Avid 导出的版式手写的，并非真实导出，
+//! 另有丢帧、Shift_JIS 和带 BOM 的 UTF-16 编码。金样是当前实现的输出，
+//! 用来发现意外的改动，不代表已与剪辑软件或播放器核对过；
+//! 拿到真实导出后应替换进来，并逐条核对金样。
+//! 有意修改输出时，用 `UPDATE_GOLDEN=1 cargo test --test golden` 重新生成金样，
+//! 再逐个检查差异后提交。
+//!
+//! 流式转换用同一批夹具再走一遍，结果也必须与金样一致。
+
+use std::fs;
+use std::io::BufReader;
+use std::path::PathBuf;
+
+use edl2srt::format::srt::SrtFormat;
+use edl2srt::{
+    EventStream, StreamOptions, decode_edl_bytes, entries_to_cues, parse_edl_to_entries,
+    stream_convert, write_srt,
+};
+
+struct Fixture {
+    /// 不带扩展名的文件名
+    name: &'static str,
+
+    fps: f64,
+
+    /// 自动检测应得到的编码
+    encoding: &'static str,
+}
+
+const FIXTURES: &[Fixture] = &[
+    Fixture {
+        name: "premiere_2398",
+        fps: 23.976,
+        encoding: "UTF-8",
+    },
+    Fixture {
+        name: "resolve_24",
+        fps: 24.0,
+        encoding: "UTF-8",
+    },
+    // 丢帧金样只记录当前的换算方式：时间码标签按墙钟秒加帧计算，
+    // 没有扣除跳过的帧号，尚未与真实的 Avid 导出和播放结果核对
+    Fixture {
+        name: "avid_2997_df",
+        fps: 29.97,
+        encoding: "UTF-8",
+    },
+    Fixture {
+        name: "shift_jis_24",
+        fps: 24.0,
+        encoding: "Shift_JIS",
+    },
+    Fixture {
+        name: "utf16le_bom_25",
+        fps: 25.0,
+        encoding: "UTF-16LE (BOM)",
+    },
+];
+
+fn fixture_path(file_name: &str) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests")
+        .join("fixtures")
+        .join(file_name)
+}
+
+#[test]
+fn fixtures_match_golden_srt() {
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let mut failures = Vec::new();
+
+    for fixture in FIXTURES {
+        let bytes = fs::read(fixture_path(&format!("{}.edl", fixture.name))).unwrap();
+        let decoded = decode_edl_bytes(&bytes, None).unwrap();
+        assert_eq!(
+            decoded.encoding_name, fixture.encoding,
+            "{}: 编码检测结果不对",
+            fixture.name
+        );
+        assert!(!decoded.had_errors, "{}: 解码出错", fixture.name);
+
+        let cues = entries_to_cues(&parse_edl_to_entries(&decoded.content), fixture.fps);
+        let mut out = Vec::new();
+        write_srt(&mut out, &cues).unwrap();
+        let actual = String::from_utf8(out).unwrap();
+
+        let golden_path = fixture_path(&format!("{}.srt", fixture.name));
+        if update {
+            fs::write(&golden_path, &actual).unwrap();
+            continue;
+        }
+
+        let expected = fs::read_to_string(&golden_path).unwrap_or_default();
+        if actual != expected {
+            failures.push(format!(
+                "{}:\n--- 金样\n{}\n--- 实际\n{}",
+                fixture.name, expected, actual
+            ));
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "输出与金样不一致（确认无误后用 UPDATE_GOLDEN=1 重新生成）:\n{}",
+        failures.join("\n")
+    );
+}
+
+#[test]
+fn streaming_matches_golden_srt() {
+    for fixture in FIXTURES {
+        let bytes = fs::read(fixture_path(&format!("{}.edl", fixture.name))).unwrap();
+        let expected = fs::read(fixture_path(&format!("{}.srt", fixture.name))).unwrap();
+
+        let mut events = EventStream::new(BufReader::new(&bytes[..]), None).unwrap();
+        assert_eq!(events.encoding_name(), fixture.encoding, "{}", fixture.name);
+
+        let mut out = Vec::new();
+        let options = StreamOptions::new(fixture.fps);
+        stream_convert(&mut events, &SrtFormat, &mut out, &options, &mut |_| {}).unwrap();
+        assert!(!events.had_errors(), "{}: 解码出错", fixture.name);
+        assert!(out == expected, "{}: 流式输出与金样不一致", fixture.name);
+    }
+}
@@ -0,0 +1,145 @@
+//! 属性测试：时间码换算在各种帧率下可以往返，解析器对任意输入都不崩溃。
+
+use edl2srt::fps::nominal_rate;
+use edl2srt::{
+    Timecode, convert_timecode, decode_edl_bytes, entries_to_cues, events_to_entries,
+    format_srt_timestamp, infer_fps, locators, parse_edl, parse_edl_to_entries,
+};
+use proptest::prelude::*;
+
+/// 常见帧率
+const RATES: &[f64] = &[23.976, 24.0, 25.0, 29.97, 30.0, 48.0, 50.0, 59.94, 60.0];
+
+fn rate() -> impl Strategy<Value = f64> {
+    proptest::sample::select(RATES)
+}
+
+/// 在 `fps` 下合法的时间码（帧号小于名义帧率）
+fn timecode(fps: f64) -> impl Strategy<Value = Timecode> {
+    let frames = nominal_rate(fps);
+    (0u32..24, 0u32..60, 0u32..60, 0..frames, any::<bool>()).prop_map(
+        |(hours, minutes, seconds, frames, drop_frame)| Timecode {
+            hours,
+            minutes,
+            seconds,
+            frames,
+            drop_frame,
+        },
+    )
+}
+
+fn rate_and_timecode() -> impl Strategy<Value = (f64, Timecode)> {
+    rate().prop_flat_map(|fps| (Just(fps), timecode(fps)))
+}
+
+proptest! {
+    #[test]
+    fn display_then_parse_round_trips((_, tc) in rate_and_timecode()) {
+        prop_assert_eq!(Timecode::parse(&tc.to_string()), Some(tc));
+    }
+
+    #[test]
+    fn frames_round_trip((fps, tc) in rate_and_timecode()) {
+        let rate = nominal_rate(fps);
+        prop_assert_eq!(Timecode::from_frames(tc.to_frames(rate), rate, tc.drop_frame), tc);
+    }
+
+    #[test]
+    fn millis_round_trip((fps, tc) in rate_and_timecode()) {
+        let ms = tc.to_millis(fps);
+        prop_assert_eq!(Timecode::from_millis(ms, fps, tc.drop_frame), tc);
+    }
+
+    #[test]
+    fn millis_increase_with_frames((fps, tc) in rate_and_timecode(), step in 1i64..100) {
+        let rate = nominal_rate(fps);
+        let later = tc.offset_frames(step, rate);
+        prop_assert!(later.to_millis(fps) > tc.to_millis(fps));
+    }
+
+    #[test]
+    fn convert_timecode_matches_millis((fps, tc) in rate_and_timecode()) {
+        prop_assert_eq!(
+            convert_timecode(&tc.to_string(), fps),
+            format_srt_timestamp(tc.to_millis(fps))
+        );
+    }
+
+    #[test]
+    fn convert_timecode_keeps_unparsable_text(text in "[^0-9]*") {
+        prop_assert_eq!(convert_timecode(&text, 25.0), text);
+    }
+
+    /// 按生成的字段拼出的事件行，解析后字段不变
+    #[test]
+    fn generated_event_lines_parse(
+        (fps, record_in) in rate_and_timecode(),
+        number in 1u32..1000,
+        reel in "[A-Z][A-Z0-9_]{0,7}",
+        track in proptest::sample::select(vec!["V", "A", "AA", "A2", "AA/V"]),
+        length in 1i64..500,
+        clip in "[a-zA-Z0-9一-龥]([a-zA-Z0-9一-龥 ]{0,18}[a-zA-Z0-9一-龥])?",
+    ) {
+        let rate = nominal_rate(fps);
+        let record_out = record_in.offset_frames(length, rate);
+        let text = format!(
+            "{:03}  {:<8} {:<5} C        {} {} {} {}\n* FROM CLIP NAME: {}.wav\n",
+            number, reel, track, record_in, record_out, record_in, record_out, clip
+        );
+
+        let edl = parse_edl(&text);
+        prop_assert_eq!(edl.events.len(), 1, "{}", text);
+        let event = &edl.events[0];
+        prop_assert_eq!(&event.reel, &reel);
+        prop_assert_eq!(event.record_in, record_in);
+        prop_assert_eq!(event.record_out, record_out);
+
+        let entries = parse_edl_to_entries(&text);
+        prop_assert_eq!(entries.len(), 1);
+        prop_assert_eq!(&entries[0].text, &clip);
+    }
+
+    /// 任意字节：解码、解析、换算都不能崩溃
+    #[test]
+    fn arbitrary_bytes_do_not_panic(
+        bytes in proptest::collection::vec(any::<u8>(), 0..2048),
+        fps in rate(),
+    ) {
+        if let Ok(decoded) = decode_edl_bytes(&bytes, None) {
+            let edl = parse_edl(&decoded.content);
+            let _ = infer_fps(&edl);
+            let _ = locators(&edl);
+            let _ = entries_to_cues(&events_to_entries(&edl.events), fps);
+        }
+    }
+
+    /// 由 EDL 常见记号拼成的文本，比纯随机字节更容易走到解析器深处
+    #[test]
+    fn edl_like_text_does_not_panic(
+        lines in proptest::collection::vec(
+            "(TITLE:|FCM:|\\* FROM CLIP NAME:|\\* LOC:|M2|AUD|[0-9]{1,4})?[ A-Z0-9:;.+-]{0,60}",
+            0..30,
+        ),
+        fps in rate(),
+    ) {
+        let text = lines.join("\n");
+        let edl = parse_edl(&text);
+        let _ = infer_fps(&edl);
+        let _ = locators(&edl);
+        let _ = entries_to_cues(&events_to_entries(&edl.events), fps);
+    }
+
+    /// 带 BOM 的 UTF-16 文本解码后与原文一致
+    #[test]
+    fn utf16_bom_round_trip(text in "\\PC{0,200}", big_endian in any::<bool>()) {
+        let mut bytes = if big_endian { vec![0xFE, 0xFF] } else { vec![0xFF, 0xFE] };
+        for unit in text.encode_utf16() {
+            let pair = if big_endian { unit.to_be_bytes() } else { unit.to_le_bytes() };
+            bytes.extend_from_slice(&pair);
+        }
+
+        let decoded = decode_edl_bytes(&bytes, None).unwrap();
+        prop_assert_eq!(decoded.content, text);
+        prop_assert!(!decoded.had_errors);
+    }
+}
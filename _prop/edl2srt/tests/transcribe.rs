@@ -0,0 +1,175 @@
+//! 用本地的模拟转写服务测试 `--transcribe` 的完整流程：查找源文件、上传、缓存。
+
+use edl2srt::transcribe::{Endpoint, Transcriber};
+use edl2srt::{events_to_entries, parse_edl};
+use std::fs;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+/// 启动一个模拟的转写服务，按上传内容返回固定文本。返回地址和请求计数。
+fn mock_server() -> (String, Arc<AtomicUsize>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!(
+        "http://{}/v1/audio/transcriptions",
+        listener.local_addr().unwrap()
+    );
+    let count = Arc::new(AtomicUsize::new(0));
+    let counter = Arc::clone(&count);
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break;
+                }
+                if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    length = value.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+            counter.fetch_add(1, Ordering::SeqCst);
+
+            let body = String::from_utf8_lossy(&body);
+            let (status, json) = if !request_line.starts_with("POST /v1/audio/transcriptions ") {
+                ("404 Not Found", r#"{"error": "path"}"#)
+            } else if body.contains("AUDIO-ONE") && body.contains("name=\"language\"\r\n\r\nzh") {
+                ("200 OK", r#"{"text": " 第一句台词 "}"#)
+            } else if body.contains("AUDIO-TWO") {
+                (
+                    "200 OK",
+                    r#"{"lines": [{"text": "第二句"}, {"text": "接着说"}]}"#,
+                )
+            } else {
+                ("500 Internal Server Error", r#"{"error": "unknown audio"}"#)
+            };
+
+            write!(
+                stream,
+                "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                status,
+                json.len(),
+                json
+            )
+            .unwrap();
+        }
+    });
+
+    (url, count)
+}
+
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("edl2srt_{}_{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn transcribes_audio_events_and_caches_by_hash() {
+    let (url, requests) = mock_server();
+    let dir = scratch_dir("transcribe");
+    let media = dir.join("media");
+    fs::create_dir_all(media.join("day2")).unwrap();
+    fs::write(media.join("VO_001.wav"), "AUDIO-ONE").unwrap();
+    fs::write(media.join("day2").join("VO_002.wav"), "AUDIO-TWO").unwrap();
+    fs::write(media.join("VO_003.wav"), "AUDIO-BAD").unwrap();
+
+    let text = "\
+001 AX A C 00:00:00:00 00:00:01:00 01:00:00:00 01:00:01:00
+* FROM CLIP NAME: VO_001.wav
+* SOURCE FILE: /edit/machine/VO_001.wav
+002 AX A2 C 00:00:00:00 00:00:01:00 01:00:01:00 01:00:02:00
+* FROM CLIP NAME: VO_002.wav
+003 AX V C 00:00:00:00 00:00:01:00 01:00:02:00 01:00:03:00
+* FROM CLIP NAME: 画面.mov
+004 AX A C 00:00:00:00 00:00:01:00 01:00:03:00 01:00:04:00
+* FROM CLIP NAME: VO_404.wav
+005 AX A C 00:00:00:00 00:00:01:00 01:00:04:00 01:00:05:00
+* FROM CLIP NAME: VO_003.wav
+006 AX A C 00:00:00:00 00:00:01:00 01:00:05:00 01:00:06:00
+* FROM CLIP NAME: VO_001.wav
+";
+
+    let mut transcriber = Transcriber::new(Endpoint::parse(&url).unwrap(), dir.join("cache"));
+    transcriber.media_paths.push(media.clone());
+    transcriber.language = Some("zh".to_string());
+
+    let mut edl = parse_edl(text);
+    let report = transcriber.transcribe_events(&mut edl);
+
+    let texts: Vec<String> = events_to_entries(&edl.events)
+        .into_iter()
+        .map(|e| e.text)
+        .collect();
+    assert_eq!(
+        texts,
+        [
+            "第一句台词",
+            "第二句\n接着说",
+            "画面.mov",
+            "VO_404",
+            "VO_003",
+            "第一句台词"
+        ]
+    );
+
+    // VO_001 的第二次出现命中缓存；VO_003 服务端报错
+    assert_eq!(report.transcribed, 2);
+    assert_eq!(report.cached, 1);
+    assert_eq!(report.warnings.len(), 2);
+    assert_eq!(report.warnings[0].line, 8);
+    assert!(report.warnings[0].message.contains("VO_404.wav"));
+    assert!(report.warnings[1].message.contains("HTTP 500"));
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+
+    // 再跑一遍，成功过的文件都走缓存，只有失败的那个会重试
+    let mut edl = parse_edl(text);
+    let report = transcriber.transcribe_events(&mut edl);
+    assert_eq!(report.transcribed, 0);
+    assert_eq!(report.cached, 3);
+    assert_eq!(requests.load(Ordering::SeqCst), 4);
+
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn unreachable_service_becomes_a_warning() {
+    let dir = scratch_dir("transcribe_down");
+    fs::write(dir.join("VO_001.wav"), "AUDIO-ONE").unwrap();
+
+    // 绑定后立即释放，得到一个大概率没有服务的端口
+    let port = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap()
+        .port();
+    let mut transcriber = Transcriber::new(
+        Endpoint::parse(&format!("http://127.0.0.1:{}", port)).unwrap(),
+        dir.join("cache"),
+    );
+    transcriber.media_paths.push(dir.clone());
+
+    let mut edl = parse_edl(
+        "001 AX A C 00:00:00:00 00:00:01:00 01:00:00:00 01:00:01:00\n\
+         * FROM CLIP NAME: VO_001.wav\n",
+    );
+    let report = transcriber.transcribe_events(&mut edl);
+
+    assert_eq!(report.warnings.len(), 1);
+    assert_eq!(edl.events[0].text, None);
+
+    let _ = fs::remove_dir_all(&dir);
+}
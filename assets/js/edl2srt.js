@@ -0,0 +1,191 @@
+// edl2srt 网页版：加载编译成 WebAssembly 的 Rust 库，在浏览器里完成转换。
+// wasm 的构建方法见仓库根目录的 README.md（英文版 docs/README_en.md）或 _prop/edl2srt/src/web.rs 顶部说明。
+
+const WASM_URL = "./assets/wasm/edl2srt.wasm";
+
+const dropZone = document.getElementById("dropZone");
+const dropText = document.getElementById("dropText");
+const fileInput = document.getElementById("fileInput");
+const fpsSelect = document.getElementById("fpsSelect");
+const formatSelect = document.getElementById("formatSelect");
+const encodingInput = document.getElementById("encodingInput");
+const downloadButton = document.getElementById("downloadButton");
+const statusEl = document.getElementById("status");
+const warningsEl = document.getElementById("warnings");
+const tableBody = document.querySelector("#cueTable tbody");
+
+let wasm = null;
+let currentFile = null;
+let currentBytes = null;
+let currentResult = null;
+
+function setStatus(text, isError = false) {
+  statusEl.textContent = text;
+  statusEl.classList.toggle("status--error", isError);
+}
+
+// ===== 与 wasm 交换数据 =====
+
+function readResult() {
+  const ptr = wasm.edl2srt_result_ptr();
+  const len = wasm.edl2srt_result_len();
+  const bytes = new Uint8Array(wasm.memory.buffer, ptr, len);
+  return JSON.parse(new TextDecoder().decode(bytes));
+}
+
+function withBuffer(bytes, callback) {
+  const ptr = wasm.edl2srt_alloc(bytes.length);
+  new Uint8Array(wasm.memory.buffer, ptr, bytes.length).set(bytes);
+  try {
+    return callback(ptr, bytes.length);
+  } finally {
+    wasm.edl2srt_free(ptr, bytes.length);
+  }
+}
+
+function convert(bytes, request) {
+  const requestBytes = new TextEncoder().encode(JSON.stringify(request));
+  withBuffer(bytes, (inputPtr, inputLen) =>
+    withBuffer(requestBytes, (requestPtr, requestLen) =>
+      wasm.edl2srt_convert(inputPtr, inputLen, requestPtr, requestLen)
+    )
+  );
+  return readResult();
+}
+
+async function loadWasm() {
+  const response = await fetch(WASM_URL);
+  if (!response.ok) {
+    throw new Error(`找不到 ${WASM_URL}（HTTP ${response.status}），请先按说明构建 wasm`);
+  }
+  const { instance } = await WebAssembly.instantiate(await response.arrayBuffer(), {});
+  return instance.exports;
+}
+
+// ===== 界面 =====
+
+function fillFormats() {
+  wasm.edl2srt_formats();
+  for (const format of readResult()) {
+    const option = document.createElement("option");
+    option.value = format.name;
+    option.textContent = `${format.name} — ${format.description}`;
+    formatSelect.appendChild(option);
+  }
+}
+
+function formatDuration(ms) {
+  return `${(ms / 1000).toFixed(3)} s`;
+}
+
+function render(result) {
+  warningsEl.replaceChildren();
+  tableBody.replaceChildren();
+
+  if (!result.ok) {
+    currentResult = null;
+    downloadButton.disabled = true;
+    setStatus(`转换失败: ${result.error}`, true);
+    return;
+  }
+
+  currentResult = result;
+  downloadButton.disabled = false;
+  const fpsNote = result.fps_inferred ? "（自动推断）" : "";
+  setStatus(
+    `${currentFile.name}：编码 ${result.encoding}，帧率 ${result.fps}${fpsNote}，共 ${result.cues.length} 条字幕`
+  );
+
+  for (const warning of result.warnings) {
+    const item = document.createElement("li");
+    item.textContent = `警告: ${warning}`;
+    warningsEl.appendChild(item);
+  }
+
+  for (const cue of result.cues) {
+    const row = document.createElement("tr");
+    for (const value of [cue.index, cue.start, cue.end, formatDuration(cue.duration_ms), cue.text]) {
+      const cell = document.createElement("td");
+      cell.textContent = value;
+      row.appendChild(cell);
+    }
+    tableBody.appendChild(row);
+  }
+}
+
+function refresh() {
+  if (!wasm || !currentBytes) {
+    return;
+  }
+  const request = {
+    file_name: currentFile.name,
+    fps: fpsSelect.value ? Number(fpsSelect.value) : null,
+    format: formatSelect.value || null,
+    encoding: encodingInput.value.trim() || null,
+  };
+  render(convert(currentBytes, request));
+}
+
+async function openFile(file) {
+  currentFile = file;
+  currentBytes = new Uint8Array(await file.arrayBuffer());
+  dropText.textContent = file.name;
+  refresh();
+}
+
+function download() {
+  if (!currentResult) {
+    return;
+  }
+  const stem = currentFile.name.replace(/\.[^.]*$/, "");
+  const blob = new Blob([currentResult.output], { type: "text/plain;charset=utf-8" });
+  const link = document.createElement("a");
+  link.href = URL.createObjectURL(blob);
+  link.download = `${stem}.${currentResult.extension}`;
+  link.click();
+  URL.revokeObjectURL(link.href);
+}
+
+dropZone.addEventListener("dragover", (event) => {
+  event.preventDefault();
+  dropZone.classList.add("drop--active");
+});
+
+dropZone.addEventListener("dragleave", () => dropZone.classList.remove("drop--active"));
+
+dropZone.addEventListener("drop", (event) => {
+  event.preventDefault();
+  dropZone.classList.remove("drop--active");
+  const [file] = event.dataTransfer.files;
+  if (file) {
+    openFile(file);
+  }
+});
+
+dropZone.addEventListener("keydown", (event) => {
+  if (event.key === "Enter" || event.key === " ") {
+    event.preventDefault();
+    fileInput.click();
+  }
+});
+
+fileInput.addEventListener("change", () => {
+  const [file] = fileInput.files;
+  if (file) {
+    openFile(file);
+  }
+});
+
+fpsSelect.addEventListener("change", refresh);
+formatSelect.addEventListener("change", refresh);
+encodingInput.addEventListener("change", refresh);
+downloadButton.addEventListener("click", download);
+
+loadWasm()
+  .then((exports) => {
+    wasm = exports;
+    fillFormats();
+    setStatus("准备就绪，请选择 EDL 文件");
+    refresh();
+  })
+  .catch((err) => setStatus(`WebAssembly 加载失败: ${err.message}`, true));